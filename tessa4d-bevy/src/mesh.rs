@@ -1,10 +1,11 @@
 use bevy::{
     app::{Plugin, PostUpdate},
-    asset::{Asset, AssetApp, Assets, Handle},
+    asset::{Asset, AssetApp, AssetEvent, AssetId, Assets, Handle},
     ecs::{
         bundle::Bundle,
+        event::EventReader,
         schedule::IntoSystemConfigs,
-        system::{Query, Res, ResMut},
+        system::{Local, Query, Res, ResMut},
     },
     math::{Vec3, Vec4},
     pbr::{Material, StandardMaterial},
@@ -15,12 +16,16 @@ use bevy::{
         render_resource::PrimitiveTopology,
         view::VisibilityBundle,
     },
+    utils::HashMap,
+};
+use tessa4d::mesh::{
+    ops::{CrossSection, CrossSectionSlicer},
+    TetrahedronMesh, TriangleMesh3D,
 };
-use tessa4d::mesh::{ops::CrossSection, TetrahedronMesh};
 
 use crate::transform::{
-    transform4d_cross_section, GlobalTransform4D, Transform4D, Transform4DBundle,
-    Transform4DSystemSet,
+    transform4d_cross_section, GlobalTransform4D, Inverse, Transform, Transform4D,
+    Transform4DBundle, Transform4DSystemSet,
 };
 
 pub type Vertex4 = tessa4d::mesh::Vertex4<Vec4>;
@@ -62,20 +67,36 @@ pub fn update_tetmesh4d_cross_sections(
         &GlobalTransform4D,
     )>,
     tetmesh_assets: Res<Assets<TetrahedronMesh4D>>,
+    mut tetmesh_events: EventReader<AssetEvent<TetrahedronMesh4D>>,
+    mut slicers: Local<HashMap<AssetId<TetrahedronMesh4D>, CrossSectionSlicer<Vertex4>>>,
+    mut cross_section: Local<TriangleMesh3D<Vec3>>,
     mut mesh_assets: ResMut<Assets<Mesh>>,
 ) {
+    // Slicers cache the edges of the tetmesh, so they're stale once it changes.
+    for event in tetmesh_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            slicers.remove(id);
+        }
+    }
     // TODO: Optimize to only update if the transform or tetmesh change.
     // Maybe TODO: Move this into an extract system in the Render app, do custom render pipeline for GPU cross-sections.
     for (tetmesh_handle, mesh_handle, transform4d) in tetmesh_query.iter() {
         if let Some(tetmesh) = tetmesh_assets.get(tetmesh_handle) {
+            let slicer = slicers
+                .entry(tetmesh_handle.id())
+                .or_insert_with(|| CrossSectionSlicer::new(&tetmesh.0));
             let (_, cross_transform) = transform4d_cross_section(transform4d);
-            let mesh = cross_section_tetmesh4d(tetmesh.clone(), &cross_transform.to_transform());
-            mesh_assets.insert(mesh_handle, mesh);
+            slicer.cross_section_into(&cross_transform.to_transform(), &mut cross_section);
+            mesh_assets.insert(mesh_handle, finish_cross_section_mesh(&cross_section));
         }
     }
 }
 
-pub fn to_bevy_mesh(mesh: tessa4d::mesh::TriangleMesh3D<Vec3>) -> Mesh {
+pub fn to_bevy_mesh(mesh: TriangleMesh3D<Vec3>) -> Mesh {
+    triangle_mesh_to_bevy(&mesh)
+}
+
+fn triangle_mesh_to_bevy(mesh: &TriangleMesh3D<Vec3>) -> Mesh {
     Mesh::new(
         PrimitiveTopology::TriangleList,
         RenderAssetUsages::RENDER_WORLD,
@@ -83,23 +104,47 @@ pub fn to_bevy_mesh(mesh: tessa4d::mesh::TriangleMesh3D<Vec3>) -> Mesh {
     .with_inserted_attribute(
         Mesh::ATTRIBUTE_POSITION,
         mesh.vertices
-            .into_iter()
+            .iter()
             .map(|v| [v.position.x, v.position.y, v.position.z])
             .collect::<Vec<_>>(),
     )
     .with_inserted_indices(Indices::U32(
         mesh.simplexes
-            .into_iter()
+            .iter()
             .flat_map(|triangle| triangle.map(|i| i as u32))
             .collect(),
     ))
 }
 
-pub fn cross_section_tetmesh4d(tetmesh: TetrahedronMesh4D, transform: &Transform4D) -> Mesh {
-    let mut tetmesh = tetmesh.0;
-    tetmesh.apply_transform(transform);
-    let cross_section = tetmesh.cross_section();
-    to_bevy_mesh(cross_section)
+/// Turns a cross-section into a flat shaded bevy mesh.
+fn finish_cross_section_mesh(cross_section: &TriangleMesh3D<Vec3>) -> Mesh {
+    triangle_mesh_to_bevy(cross_section)
         .with_duplicated_vertices()
         .with_computed_flat_normals()
 }
+
+pub fn cross_section_tetmesh4d(
+    tetmesh: TetrahedronMesh4D,
+    transform: &impl Transform<Vec4>,
+) -> Mesh {
+    let mut tetmesh = tetmesh.0;
+    tetmesh.apply_transform(transform);
+    finish_cross_section_mesh(&tetmesh.cross_section())
+}
+
+/// Cross-sections an implicit 4D shape given by the signed distance function `sdf`, like [`cross_section_tetmesh4d`] but without needing a [`TetrahedronMesh4D`].
+/// `sdf` is sampled on a grid centered at the origin of the cross-section, with side lengths from `size` and `cells` boxes along each axis.
+pub fn cross_section_sdf4d(
+    sdf: impl Fn(Vec4) -> f32,
+    transform: &Transform4D,
+    size: Vec3,
+    cells: [usize; 3],
+) -> Mesh {
+    let cross_section = tessa4d::mesh::TriangleMesh3D::from_sdf4d_cross_section(
+        sdf,
+        &transform.inverse(),
+        size,
+        cells,
+    );
+    finish_cross_section_mesh(&cross_section)
+}
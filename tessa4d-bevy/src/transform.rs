@@ -17,7 +17,10 @@ use bevy::{
     },
     utils::HashSet,
 };
-use tessa4d::transform::rotate_scale_translate4::RotateScaleTranslate4;
+use tessa4d::transform::{
+    affine4::Affine4, rotate_scale_translate4::RotateScaleTranslate4,
+    scale_rotate_translate4::ScaleRotateTranslate4, traits::TryInverse,
+};
 pub use tessa4d::transform::{
     rotor4::Bivec4,
     rotor4::Rotor4,
@@ -27,20 +30,22 @@ pub use tessa4d::transform::{
 pub type Transform4D = RotateScaleTranslate4<Vec4>;
 
 /// Read-only global transform component.
-/// If you want to do anything with the transform, use [`GlobalTransform4D::to_transform`] to get a regular Transform4D.
+/// If you want to do anything with the transform, use [`GlobalTransform4D::to_transform`] to get a regular transform.
+///
+/// Unlike [`Transform4D`] this keeps a separate scale per axis, so non-uniform scale from 3D parents isn't lost.
 #[derive(Debug, Clone, Copy, Component)]
-pub struct GlobalTransform4D(RotateScaleTranslate4<Vec4>);
+pub struct GlobalTransform4D(ScaleRotateTranslate4<Vec4>);
 
 impl GlobalTransform4D {
-    pub const IDENTITY: Self = GlobalTransform4D(RotateScaleTranslate4::IDENTITY);
+    pub const IDENTITY: Self = GlobalTransform4D(ScaleRotateTranslate4::IDENTITY);
 
-    // Copies this transform to a local transform.
-    pub fn to_transform(&self) -> Transform4D {
+    // Copies this transform out of the component.
+    pub fn to_transform(&self) -> ScaleRotateTranslate4<Vec4> {
         self.0
     }
 
-    pub fn from_transform(transform: Transform4D) -> Self {
-        Self(transform)
+    pub fn from_transform(transform: impl Into<ScaleRotateTranslate4<Vec4>>) -> Self {
+        Self(transform.into())
     }
 
     /// Returns the translation component of the transform.
@@ -53,15 +58,22 @@ impl GlobalTransform4D {
         self.0.rotation
     }
 
-    /// Returns the scale component of the transform.
-    pub fn scale(&self) -> f32 {
+    /// Returns the per-axis scale component of the transform.
+    pub fn scale(&self) -> Vec4 {
         self.0.scale
     }
 
     /// Returns the local transform that will maintain the same global transform when reparenting to `new_parent`.
     /// See the docs for [`bevy::prelude::GlobalTransform::reparented_to`].
-    pub fn reparented_to(&self, new_parent: &Self) -> Transform4D {
-        self.0.compose(new_parent.0.inverse())
+    ///
+    /// [`Transform4D`] only has uniform scale, so this is approximate if either transform has non-uniform scale.
+    /// Returns None if either transform is singular, e.g. has a zero scale, since no local transform would work.
+    pub fn reparented_to(&self, new_parent: &Self) -> Option<Transform4D> {
+        let parent_inverse = Affine4::from(new_parent.0).try_inverse()?;
+        let local = Affine4::from(self.0)
+            .compose(parent_inverse)
+            .approx_scale_rotate_translate()?;
+        Some(with_uniform_scale(local))
     }
 }
 
@@ -139,7 +151,7 @@ impl Transform4DBundle {
     pub fn from_transform(transform: Transform4D) -> Self {
         Self {
             local: transform,
-            global: GlobalTransform4D(transform),
+            global: GlobalTransform4D::from_transform(transform),
             cross_section: transform4d_cross_section(&transform.into()).0,
         }
     }
@@ -209,7 +221,7 @@ pub fn propagate_4d_transforms(
             if let Ok((mut global_transform4d, local_transform4d)) =
                 transforms_4d_query.get_mut(entity)
             {
-                *global_transform4d = GlobalTransform4D(*local_transform4d);
+                *global_transform4d = GlobalTransform4D::from_transform(*local_transform4d);
             }
         }
         if let Ok(children) = tree_query.get(entity) {
@@ -276,7 +288,8 @@ pub fn update_global_transform3d_from_global_transform4d(
 }
 
 /// Lifts a 3D transform into an equivalent 4D transform.
-pub fn lift_transform(transform: Transform3D) -> Transform4D {
+/// The w axis has no 3D counterpart, so it is scaled by the largest-magnitude scale on the other axes.
+pub fn lift_transform(transform: Transform3D) -> ScaleRotateTranslate4<Vec4> {
     let axis_angle = transform.rotation.to_axis_angle();
     let rotation = Rotor4::from_bivec_angles(Bivec4 {
         xy: axis_angle.0.z * axis_angle.1,
@@ -284,27 +297,38 @@ pub fn lift_transform(transform: Transform3D) -> Transform4D {
         yz: axis_angle.0.x * axis_angle.1,
         ..Bivec4::ZERO
     });
-    let min_scale = transform.scale.min_element();
-    let max_scale = transform.scale.max_element();
-    // Only support uniform scale in 4d, so this mapping is very lossy but probably fine for most cases.
-    let scale = if max_scale.abs() > min_scale.abs() {
-        max_scale
-    } else {
-        min_scale
-    };
+    let scale = transform
+        .scale
+        .extend(largest_magnitude(transform.scale.to_array()));
     let translation = Vec4::new(
         transform.translation.x,
         transform.translation.y,
         transform.translation.z,
         0.0,
     );
-    Transform4D {
-        rotation,
+    ScaleRotateTranslate4 {
         scale,
+        rotation,
         translation,
     }
 }
 
+/// Drops everything but the largest-magnitude scale, lossy but probably fine for most cases.
+fn with_uniform_scale(transform: ScaleRotateTranslate4<Vec4>) -> Transform4D {
+    Transform4D {
+        rotation: transform.rotation,
+        scale: largest_magnitude(transform.scale.to_array()),
+        translation: transform.translation,
+    }
+}
+
+fn largest_magnitude<const N: usize>(values: [f32; N]) -> f32 {
+    values
+        .into_iter()
+        .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+        .unwrap_or(1.0)
+}
+
 /// Decomposes a 4D transform `T4` into a pair of transforms:
 /// * a 3D transform `T3` that can be applied to the cross-section of a 4D object at `w=0`
 /// * a 4D transform `T4'` that can be applied before the cross-section operation
@@ -318,14 +342,18 @@ pub fn transform4d_cross_section(
     // pulling out translation and scale allows some more numerical stability for cross-section, and
     // probably helps with visibility checks and similar stuff in 3D land.
     let mut t4 = transform4d.to_transform();
+    // Non-uniform scale happens before the rotation so it can only be pulled out if it is uniform.
+    let uniform_scale = uniform_scale(t4.scale);
     let t3 = Transform3D {
         translation: t4.translation.xyz(),
-        scale: Vec3::ONE * t4.scale,
+        scale: Vec3::ONE * uniform_scale.unwrap_or(1.0),
         rotation: Quat::IDENTITY,
     };
     t4.translation = Vec4::new(0.0, 0.0, 0.0, t4.translation.w);
-    t4.scale = 1.0;
-    (t3.into(), t4.into())
+    if uniform_scale.is_some() {
+        t4.scale = Vec4::ONE;
+    }
+    (t3.into(), GlobalTransform4D(t4))
 }
 
 fn get_global_transform(parent_global: EitherTransform, local: Transform4D) -> GlobalTransform4D {
@@ -335,7 +363,22 @@ fn get_global_transform(parent_global: EitherTransform, local: Transform4D) -> G
         }
         EitherTransform::T4(transform4d) => transform4d,
     };
-    GlobalTransform4D(local.compose(parent_transform4d.to_transform()))
+    let parent = parent_transform4d.to_transform();
+    let local = ScaleRotateTranslate4::from(local);
+    match uniform_scale(parent.scale) {
+        Some(scale) => GlobalTransform4D(local.compose(Transform4D {
+            rotation: parent.rotation,
+            scale,
+            translation: parent.translation,
+        })),
+        // Only a rotated child under a non-uniformly scaled parent shears, so this is usually still exact.
+        None => GlobalTransform4D(local.compose_approx(parent)),
+    }
+}
+
+/// Returns the scale if it is the same on every axis.
+fn uniform_scale(scale: Vec4) -> Option<f32> {
+    (scale.min_element() == scale.max_element()).then_some(scale.x)
 }
 
 #[cfg(test)]
@@ -551,11 +594,31 @@ mod test {
         assert!(t4_cross.abs_diff_eq(t4_cross_t3, 1e-5));
     }
 
+    #[test]
+    fn transform4d_parent3d_non_uniform_scale_applies_to_global() {
+        let mut app = App::new();
+        app.add_plugins(Transform4DPlugin::default())
+            .add_plugins(TransformPlugin::default());
+        let child_entity_id = app.world.spawn(Transform4DBundle::IDENTITY).id();
+        let mut parent_entity = app.world.spawn(TransformBundle::IDENTITY);
+        parent_entity.add_child(child_entity_id);
+        let parent_entity_id = parent_entity.id();
+
+        app.update();
+        let mut transform = app.world.get_mut::<Transform3D>(parent_entity_id).unwrap();
+        transform.scale = Vec3::new(1.0, 2.0, 3.0);
+        app.update();
+
+        let global_transform = dbg!(app.world.get::<GlobalTransform4D>(child_entity_id).unwrap());
+        let transformed_point = dbg!(global_transform.to_transform().transform(Vec4::ONE));
+        assert!(transformed_point.abs_diff_eq(Vec4::new(1.0, 2.0, 3.0, 3.0), 1e-4));
+    }
+
     #[test]
     fn lift_transform_preserves_transform() {
         let mut transform3d = Transform3D::from_xyz(1.0, 2.0, 3.0);
         transform3d.rotate_z(PI / 2.0);
-        transform3d.scale = Vec3::ONE * 2.0;
+        transform3d.scale = Vec3::new(2.0, 3.0, 0.5);
         let vec3 = Vec3::new(4.0, 5.0, 6.0);
         let transform4d = lift_transform(transform3d);
         let vec4 = Vec4::new(4.0, 5.0, 6.0, 0.0);
@@ -571,4 +634,31 @@ mod test {
 
         assert!(lifted_vec3.abs_diff_eq(transformed_vec4, 1e-5));
     }
+
+    #[test]
+    fn reparented_to_keeps_global_transform() {
+        let global = GlobalTransform4D::from_transform(Transform4D::IDENTITY.translated(Vec4::X));
+        let new_parent = GlobalTransform4D::from_transform(
+            Transform4D::IDENTITY
+                .rotated(Rotor4::from_bivec_angles(Bivec4 {
+                    xw: FRAC_PI_2,
+                    ..Bivec4::ZERO
+                }))
+                .scaled(2.0),
+        );
+
+        let local = dbg!(global.reparented_to(&new_parent).unwrap());
+        let point = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let reparented = new_parent.to_transform().transform(local.transform(point));
+
+        assert!(dbg!(reparented).abs_diff_eq(global.to_transform().transform(point), 1e-4));
+    }
+
+    #[test]
+    fn reparented_to_singular_parent_is_none() {
+        let global = GlobalTransform4D::from_transform(Transform4D::IDENTITY.translated(Vec4::X));
+        let new_parent = GlobalTransform4D::from_transform(Transform4D::IDENTITY.scaled(0.0));
+
+        assert!(global.reparented_to(&new_parent).is_none());
+    }
 }
@@ -0,0 +1,164 @@
+//! Compares transforming mesh vertices one at a time against the batch path in `apply_transform`,
+//! and against the structure-of-arrays layout in `SoaMesh4D`. Also compares cross-sectioning a moving mesh
+//! from scratch against the slicers that reuse its edges.
+
+use std::f32::consts::TAU;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use glam::{Vec3, Vec4};
+use tessa4d::{
+    mesh::{
+        ops::{CrossSection, CrossSectionSlicer, SoaCrossSectionSlicer},
+        parametric::ParametricSurface4D,
+        soa::SoaTetrahedronMesh4D,
+        TetrahedronMesh4D, TriangleMesh,
+    },
+    transform::{
+        affine4::Affine4,
+        rotate_scale_translate4::RotateScaleTranslate4,
+        rotor4::{Bivec4, Rotor4},
+        traits::Transform,
+    },
+};
+
+/// Many tesseracts side by side, for a large mesh with the same vertices as the tesseract constructor.
+fn tesseracts(count: usize) -> TetrahedronMesh4D<Vec4> {
+    let mut mesh = TetrahedronMesh4D::tesseract_cube(1.0);
+    let single = mesh.clone();
+    for i in 1..count {
+        let offset = RotateScaleTranslate4 {
+            translation: Vec4::X * (2 * i) as f32,
+            ..RotateScaleTranslate4::IDENTITY
+        };
+        mesh.join(single.clone().apply_transform(&offset).clone());
+    }
+    mesh
+}
+
+/// Unit glome (hypersphere) in Hopf coordinates.
+fn hypersphere(cells: usize) -> TetrahedronMesh4D<Vec4> {
+    let glome = |p: Vec3| {
+        let (sin_eta, cos_eta) = p.x.sin_cos();
+        let (sin_xi1, cos_xi1) = p.y.sin_cos();
+        let (sin_xi2, cos_xi2) = p.z.sin_cos();
+        Vec4::new(
+            cos_eta * cos_xi1,
+            cos_eta * sin_xi1,
+            sin_eta * cos_xi2,
+            sin_eta * sin_xi2,
+        )
+    };
+    ParametricSurface4D::new(glome, Vec3::ZERO, Vec3::new(TAU / 4.0, TAU, TAU))
+        .with_cells([cells, 2 * cells, 2 * cells])
+        .build()
+}
+
+fn rotor() -> Rotor4 {
+    Rotor4::from_bivec_angles(Bivec4 {
+        xy: 0.3,
+        xw: -1.1,
+        zw: 0.7,
+        ..Bivec4::ZERO
+    })
+}
+
+fn bench_mesh<T: Transform<Vec4> + Into<Affine4<Vec4>> + Copy>(
+    c: &mut Criterion,
+    group_name: &str,
+    transform: &T,
+    meshes: &[(&str, TetrahedronMesh4D<Vec4>)],
+) {
+    let mut group = c.benchmark_group(group_name);
+    for (name, mesh) in meshes {
+        group.bench_with_input(BenchmarkId::new("per_vertex", name), mesh, |b, mesh| {
+            let mut mesh = mesh.clone();
+            b.iter(|| {
+                for vertex in mesh.vertices.iter_mut() {
+                    vertex.position = transform.transform(vertex.position);
+                }
+                black_box(&mesh);
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("apply_transform", name),
+            mesh,
+            |b, mesh| {
+                let mut mesh = mesh.clone();
+                b.iter(|| {
+                    black_box(mesh.apply_transform(transform));
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("soa_apply_transform", name),
+            mesh,
+            |b, mesh| {
+                let mut mesh = SoaTetrahedronMesh4D::from(mesh);
+                b.iter(|| {
+                    black_box(mesh.apply_transform(transform));
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn transform_meshes(c: &mut Criterion) {
+    let meshes = [
+        ("tesseracts_1000", tesseracts(1000)),
+        ("hypersphere_32", hypersphere(32)),
+    ];
+    bench_mesh(c, "rotor4", &rotor(), &meshes);
+    bench_mesh(
+        c,
+        "rotate_scale_translate4",
+        &RotateScaleTranslate4 {
+            rotation: rotor(),
+            scale: 1.5,
+            translation: Vec4::new(1.0, 2.0, 3.0, 4.0),
+        },
+        &meshes,
+    );
+}
+
+fn cross_section_meshes(c: &mut Criterion) {
+    let meshes = [
+        ("tesseracts_1000", tesseracts(1000)),
+        ("hypersphere_32", hypersphere(32)),
+    ];
+    let transform = RotateScaleTranslate4 {
+        rotation: rotor(),
+        scale: 1.0,
+        translation: Vec4::new(0.0, 0.0, 0.0, 0.1),
+    };
+    let mut group = c.benchmark_group("cross_section");
+    for (name, mesh) in &meshes {
+        group.bench_with_input(
+            BenchmarkId::new("transform_and_cross_section", name),
+            mesh,
+            |b, mesh| {
+                b.iter(|| black_box(mesh.clone().apply_transform(&transform).cross_section()))
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("slicer", name), mesh, |b, mesh| {
+            let mut slicer = CrossSectionSlicer::new(mesh);
+            let mut output = TriangleMesh::default();
+            b.iter(|| {
+                slicer.cross_section_into(&transform, &mut output);
+                black_box(&output);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("soa_slicer", name), mesh, |b, mesh| {
+            let mut slicer = SoaCrossSectionSlicer::new(&SoaTetrahedronMesh4D::from(mesh));
+            let mut output = TriangleMesh::default();
+            b.iter(|| {
+                slicer.cross_section_into(&transform, &mut output);
+                black_box(&output);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, transform_meshes, cross_section_meshes);
+criterion_main!(benches);
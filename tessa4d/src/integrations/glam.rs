@@ -2,94 +2,195 @@
 
 //! Implementations of traits for Glam structs.
 
-use glam::{Affine2, Affine3A, Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
+use glam::{
+    Affine2, Affine3A, DAffine2, DAffine3, DMat2, DMat3, DMat4, DQuat, DVec2, DVec3, DVec4, Mat2,
+    Mat3, Mat4, Quat, Vec2, Vec3, Vec4,
+};
 
 use crate::{
-    linear_algebra::{Matrix4, Vector, Vector2, Vector3, Vector4},
-    transform::traits::Transform,
+    linear_algebra::{Matrix4, Quaternion, Vector, Vector2, Vector3, Vector4},
+    transform::{
+        rotate_scale_translate4::GenericRotateScaleTranslate4,
+        rotor4::{GenericRotor4, RotorError},
+        traits::Transform,
+    },
 };
 
 macro_rules! impl_vector_trait {
-    ($($vec_type:ty),*) => {
+    ($scalar_type:ty: $($vec_type:ty),*) => {
         $(
-            impl Vector for $vec_type {
+            impl Vector<$scalar_type> for $vec_type {
                 const ZERO: Self = Self::ZERO;
-                fn dot(self, other:Self) -> f32 {
+                const ONE: Self = Self::ONE;
+                fn dot(self, other:Self) -> $scalar_type {
                     Self::dot(self, other)
                 }
 
                 fn normalized(self) -> Self {
                     Self::normalize(self)
                 }
+
+                fn min_elementwise(self, other: Self) -> Self {
+                    Self::min(self, other)
+                }
+
+                fn max_elementwise(self, other: Self) -> Self {
+                    Self::max(self, other)
+                }
             }
         )*
     };
 }
-impl_vector_trait!(Vec2, Vec3, Vec4);
+impl_vector_trait!(f32: Vec2, Vec3, Vec4);
+impl_vector_trait!(f64: DVec2, DVec3, DVec4);
 
-impl Matrix4 for Mat4 {
-    type Vector4 = Vec4;
-    const IDENTITY: Self = Mat4::IDENTITY;
-    fn from_cols_array(arr: [[f32; 4]; 4]) -> Self {
-        Mat4::from_cols_array_2d(&arr)
-    }
-}
+macro_rules! impl_fixed_size_traits {
+    ($scalar_type:ty, $vec2:ident, $vec3:ident, $vec4:ident, $mat4:ident, $quat:ident) => {
+        impl Matrix4<$scalar_type> for $mat4 {
+            type Vector4 = $vec4;
+            const IDENTITY: Self = $mat4::IDENTITY;
+            fn from_cols_array(arr: [[$scalar_type; 4]; 4]) -> Self {
+                $mat4::from_cols_array_2d(&arr)
+            }
+            fn to_cols_array(&self) -> [[$scalar_type; 4]; 4] {
+                $mat4::to_cols_array_2d(self)
+            }
+            fn transpose(&self) -> Self {
+                $mat4::transpose(self)
+            }
+            fn determinant(&self) -> $scalar_type {
+                $mat4::determinant(self)
+            }
+            fn try_inverse(&self) -> Option<Self> {
+                // glam doesn't check, a singular matrix gives infinities or NaNs.
+                Some($mat4::inverse(self)).filter(|inverse| inverse.is_finite())
+            }
+        }
 
-impl Vector4 for Vec4 {
-    type Matrix4 = Mat4;
-    type Vector3 = Vec3;
-    fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
-        Vec4::new(x, y, z, w)
-    }
-    fn x(self) -> f32 {
-        self.x
-    }
-    fn y(self) -> f32 {
-        self.y
-    }
-    fn z(self) -> f32 {
-        self.z
-    }
-    fn w(self) -> f32 {
-        self.w
-    }
-}
+        impl Vector4<$scalar_type> for $vec4 {
+            type Matrix4 = $mat4;
+            type Vector3 = $vec3;
+            fn new(x: $scalar_type, y: $scalar_type, z: $scalar_type, w: $scalar_type) -> Self {
+                $vec4::new(x, y, z, w)
+            }
+            fn x(self) -> $scalar_type {
+                self.x
+            }
+            fn y(self) -> $scalar_type {
+                self.y
+            }
+            fn z(self) -> $scalar_type {
+                self.z
+            }
+            fn w(self) -> $scalar_type {
+                self.w
+            }
+        }
 
-impl Vector3 for Vec3 {
-    type Vector2 = Vec2;
-    type Vector4 = Vec4;
+        impl Vector3<$scalar_type> for $vec3 {
+            type Vector2 = $vec2;
+            type Vector4 = $vec4;
 
-    fn new(x: f32, y: f32, z: f32) -> Self {
-        glam::vec3(x, y, z)
-    }
-    fn x(self) -> f32 {
-        self.x
-    }
-    fn y(self) -> f32 {
-        self.y
-    }
-    fn z(self) -> f32 {
-        self.z
-    }
+            fn new(x: $scalar_type, y: $scalar_type, z: $scalar_type) -> Self {
+                $vec3::new(x, y, z)
+            }
+            fn x(self) -> $scalar_type {
+                self.x
+            }
+            fn y(self) -> $scalar_type {
+                self.y
+            }
+            fn z(self) -> $scalar_type {
+                self.z
+            }
 
-    fn cross(self, other: Self) -> Self {
-        Vec3::cross(self, other)
-    }
+            fn cross(self, other: Self) -> Self {
+                $vec3::cross(self, other)
+            }
+        }
+
+        impl Vector2<$scalar_type> for $vec2 {
+            type Vector3 = $vec3;
+
+            fn new(x: $scalar_type, y: $scalar_type) -> Self {
+                $vec2::new(x, y)
+            }
+            fn x(self) -> $scalar_type {
+                self.x
+            }
+            fn y(self) -> $scalar_type {
+                self.y
+            }
+        }
+
+        impl Quaternion<$scalar_type> for $quat {
+            fn from_xyzw(
+                x: $scalar_type,
+                y: $scalar_type,
+                z: $scalar_type,
+                w: $scalar_type,
+            ) -> Self {
+                $quat::from_xyzw(x, y, z, w)
+            }
+            fn x(self) -> $scalar_type {
+                self.x
+            }
+            fn y(self) -> $scalar_type {
+                self.y
+            }
+            fn z(self) -> $scalar_type {
+                self.z
+            }
+            fn w(self) -> $scalar_type {
+                self.w
+            }
+        }
+    };
 }
+impl_fixed_size_traits!(f32, Vec2, Vec3, Vec4, Mat4, Quat);
+impl_fixed_size_traits!(f64, DVec2, DVec3, DVec4, DMat4, DQuat);
 
-impl Vector2 for Vec2 {
-    type Vector3 = Vec3;
+macro_rules! impl_rotor_conversions {
+    ($scalar_type:ty, $vec4:ident, $mat4:ident) => {
+        impl From<GenericRotor4<$scalar_type>> for $mat4 {
+            fn from(value: GenericRotor4<$scalar_type>) -> Self {
+                value.into_mat4()
+            }
+        }
 
-    fn new(x: f32, y: f32) -> Self {
-        glam::vec2(x, y)
-    }
-    fn x(self) -> f32 {
-        self.x
-    }
-    fn y(self) -> f32 {
-        self.y
-    }
+        impl TryFrom<$mat4> for GenericRotor4<$scalar_type> {
+            type Error = RotorError<$scalar_type>;
+            fn try_from(value: $mat4) -> Result<Self, Self::Error> {
+                Self::from_mat4_array(value.to_cols_array_2d())
+            }
+        }
+
+        /// A 4x4 matrix has no room for a 4D translation, so the transform comes out with none.
+        /// Use [Affine4::to_homogeneous_array](crate::transform::affine4::Affine4::to_homogeneous_array) for the full transform as a matrix.
+        impl TryFrom<$mat4> for GenericRotateScaleTranslate4<$scalar_type, $vec4> {
+            type Error = RotorError<$scalar_type>;
+            fn try_from(value: $mat4) -> Result<Self, Self::Error> {
+                Self::from_rotate_scale_array(value.to_cols_array_2d(), $vec4::ZERO)
+            }
+        }
+
+        /// A 4x4 matrix has no room for a 4D translation, so this fails with [RotorError::Translated] unless the translation is zero.
+        /// Use [Affine4::to_homogeneous_array](crate::transform::affine4::Affine4::to_homogeneous_array) for the full transform as a matrix.
+        impl TryFrom<GenericRotateScaleTranslate4<$scalar_type, $vec4>> for $mat4 {
+            type Error = RotorError<$scalar_type>;
+            fn try_from(
+                value: GenericRotateScaleTranslate4<$scalar_type, $vec4>,
+            ) -> Result<Self, Self::Error> {
+                if value.translation != $vec4::ZERO {
+                    return Err(RotorError::Translated(value.translation.to_array()));
+                }
+                Ok(value.get_rotate_scale_matrix())
+            }
+        }
+    };
 }
+impl_rotor_conversions!(f32, Vec4, Mat4);
+impl_rotor_conversions!(f64, DVec4, DMat4);
 
 impl Transform<Vec2> for Mat2 {
     fn transform(&self, operand: Vec2) -> Vec2 {
@@ -120,3 +221,135 @@ impl Transform<Vec4> for Mat4 {
         self.mul_vec4(operand)
     }
 }
+
+impl Transform<DVec2> for DMat2 {
+    fn transform(&self, operand: DVec2) -> DVec2 {
+        self.mul_vec2(operand)
+    }
+}
+
+impl Transform<DVec2> for DAffine2 {
+    fn transform(&self, operand: DVec2) -> DVec2 {
+        self.transform_point2(operand)
+    }
+}
+
+impl Transform<DVec3> for DMat3 {
+    fn transform(&self, operand: DVec3) -> DVec3 {
+        self.mul_vec3(operand)
+    }
+}
+
+impl Transform<DVec3> for DAffine3 {
+    fn transform(&self, operand: DVec3) -> DVec3 {
+        self.transform_point3(operand)
+    }
+}
+
+impl Transform<DVec4> for DMat4 {
+    fn transform(&self, operand: DVec4) -> DVec4 {
+        self.mul_vec4(operand)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transform::{
+        rotate_scale_translate4::{DRotateScaleTranslate4, RotateScaleTranslate4},
+        rotor4::{Bivec4, DBivec4, DRotor4, Rotor4},
+    };
+
+    const EPS: f32 = 1e-3;
+
+    #[test]
+    fn rotor_mat4_round_trip() {
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xy: 0.4,
+            zw: 1.3,
+            ..Bivec4::ZERO
+        });
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+
+        let matrix = Mat4::from(rotor);
+        let got = Rotor4::try_from(matrix).unwrap();
+
+        assert!((matrix * vector).abs_diff_eq(rotor.transform(vector), EPS));
+        assert!(got
+            .transform(vector)
+            .abs_diff_eq(rotor.transform(vector), EPS));
+    }
+
+    #[test]
+    fn double_precision_rotor_mat4_round_trip() {
+        let rotor = DRotor4::from_bivec_angles(DBivec4 {
+            xw: -0.9,
+            yz: 2.1,
+            ..DBivec4::ZERO
+        });
+        let vector = glam::dvec4(1.0, 2.0, 3.0, 4.0);
+
+        let got = DRotor4::try_from(DMat4::from(rotor)).unwrap();
+
+        assert!(got
+            .transform(vector)
+            .abs_diff_eq(rotor.transform(vector), 1e-9));
+    }
+
+    #[test]
+    fn mat4_with_shear_is_not_rotor() {
+        let shear = Mat4::from_cols_array_2d(&[
+            [1.0, 0.0, 0.0, 0.0],
+            [0.5, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        assert!(Rotor4::try_from(shear).is_err());
+        assert!(RotateScaleTranslate4::<Vec4>::try_from(shear).is_err());
+    }
+
+    #[test]
+    fn rotate_scale_mat4_to_transform() {
+        let rotation = Rotor4::from_bivec_angles(Bivec4 {
+            xz: 1.0,
+            ..Bivec4::ZERO
+        });
+        let matrix = Mat4::from(rotation) * 2.0;
+        let vector = glam::vec4(-1.0, 0.5, 2.0, 3.0);
+
+        let got = dbg!(RotateScaleTranslate4::<Vec4>::try_from(matrix).unwrap());
+
+        assert!((got.scale - 2.0).abs() < EPS);
+        assert!(got.transform(vector).abs_diff_eq(matrix * vector, EPS));
+    }
+
+    #[test]
+    fn transform_to_mat4_requires_zero_translation() {
+        let transform = DRotateScaleTranslate4 {
+            rotation: DRotor4::from_bivec_angles(DBivec4 {
+                xy: 0.5,
+                zw: -1.2,
+                ..DBivec4::ZERO
+            }),
+            scale: 3.0,
+            translation: glam::dvec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let vector = glam::dvec4(-1.0, 0.5, 2.0, 3.0);
+
+        let got = dbg!(DMat4::try_from(transform));
+        assert!(matches!(
+            got,
+            Err(RotorError::Translated([1.0, 2.0, 3.0, 4.0]))
+        ));
+
+        let untranslated = DRotateScaleTranslate4 {
+            translation: DVec4::ZERO,
+            ..transform
+        };
+        let got = dbg!(DMat4::try_from(untranslated).unwrap());
+        assert!((got * vector).abs_diff_eq(untranslated.transform(vector), 1e-9));
+        let round_trip = DRotateScaleTranslate4::try_from(got).unwrap();
+        assert!((round_trip.scale - 3.0).abs() < 1e-9);
+    }
+}
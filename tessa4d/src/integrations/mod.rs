@@ -1 +1,2 @@
 mod glam;
+mod nalgebra;
@@ -0,0 +1,313 @@
+#![cfg(feature = "nalgebra")]
+
+//! Implementations of traits for nalgebra structs.
+
+use nalgebra as na;
+
+use crate::{
+    linear_algebra::{Matrix4, Quaternion, Vector, Vector2, Vector3, Vector4},
+    transform::{
+        rotate_scale_translate4::GenericRotateScaleTranslate4,
+        rotor4::{GenericBivec4, GenericRotor4, RotorError},
+        traits::Transform,
+    },
+};
+
+macro_rules! impl_vector_trait {
+    ($scalar_type:ty: $($vec_type:ident),*) => {
+        $(
+            impl Vector<$scalar_type> for na::$vec_type<$scalar_type> {
+                const ZERO: Self = impl_vector_trait!(@splat $vec_type, 0.0);
+                const ONE: Self = impl_vector_trait!(@splat $vec_type, 1.0);
+                fn dot(self, other: Self) -> $scalar_type {
+                    na::Matrix::dot(&self, &other)
+                }
+
+                fn normalized(self) -> Self {
+                    self.normalize()
+                }
+
+                fn min_elementwise(self, other: Self) -> Self {
+                    self.inf(&other)
+                }
+
+                fn max_elementwise(self, other: Self) -> Self {
+                    self.sup(&other)
+                }
+            }
+        )*
+    };
+    (@splat Vector2, $value:expr) => { na::Vector2::new($value, $value) };
+    (@splat Vector3, $value:expr) => { na::Vector3::new($value, $value, $value) };
+    (@splat Vector4, $value:expr) => { na::Vector4::new($value, $value, $value, $value) };
+}
+impl_vector_trait!(f32: Vector2, Vector3, Vector4);
+impl_vector_trait!(f64: Vector2, Vector3, Vector4);
+
+macro_rules! impl_fixed_size_traits {
+    ($($scalar_type:ty),*) => {
+        $(
+            impl Matrix4<$scalar_type> for na::Matrix4<$scalar_type> {
+                type Vector4 = na::Vector4<$scalar_type>;
+                const IDENTITY: Self = Self::new(
+                    1.0, 0.0, 0.0, 0.0,
+                    0.0, 1.0, 0.0, 0.0,
+                    0.0, 0.0, 1.0, 0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                );
+                fn from_cols_array(arr: [[$scalar_type; 4]; 4]) -> Self {
+                    Self::from_columns(&arr.map(na::Vector4::from))
+                }
+                fn to_cols_array(&self) -> [[$scalar_type; 4]; 4] {
+                    std::array::from_fn(|col| std::array::from_fn(|row| self[(row, col)]))
+                }
+                fn transpose(&self) -> Self {
+                    na::Matrix4::transpose(self)
+                }
+                fn determinant(&self) -> $scalar_type {
+                    na::Matrix4::determinant(self)
+                }
+                fn try_inverse(&self) -> Option<Self> {
+                    na::Matrix4::try_inverse(*self)
+                }
+            }
+
+            impl Vector4<$scalar_type> for na::Vector4<$scalar_type> {
+                type Matrix4 = na::Matrix4<$scalar_type>;
+                type Vector3 = na::Vector3<$scalar_type>;
+                fn new(x: $scalar_type, y: $scalar_type, z: $scalar_type, w: $scalar_type) -> Self {
+                    Self::new(x, y, z, w)
+                }
+                fn x(self) -> $scalar_type {
+                    self.x
+                }
+                fn y(self) -> $scalar_type {
+                    self.y
+                }
+                fn z(self) -> $scalar_type {
+                    self.z
+                }
+                fn w(self) -> $scalar_type {
+                    self.w
+                }
+            }
+
+            impl Vector3<$scalar_type> for na::Vector3<$scalar_type> {
+                type Vector2 = na::Vector2<$scalar_type>;
+                type Vector4 = na::Vector4<$scalar_type>;
+
+                fn new(x: $scalar_type, y: $scalar_type, z: $scalar_type) -> Self {
+                    Self::new(x, y, z)
+                }
+                fn x(self) -> $scalar_type {
+                    self.x
+                }
+                fn y(self) -> $scalar_type {
+                    self.y
+                }
+                fn z(self) -> $scalar_type {
+                    self.z
+                }
+
+                fn cross(self, other: Self) -> Self {
+                    na::Matrix::cross(&self, &other)
+                }
+            }
+
+            impl Vector2<$scalar_type> for na::Vector2<$scalar_type> {
+                type Vector3 = na::Vector3<$scalar_type>;
+
+                fn new(x: $scalar_type, y: $scalar_type) -> Self {
+                    Self::new(x, y)
+                }
+                fn x(self) -> $scalar_type {
+                    self.x
+                }
+                fn y(self) -> $scalar_type {
+                    self.y
+                }
+            }
+
+            impl Quaternion<$scalar_type> for na::Quaternion<$scalar_type> {
+                fn from_xyzw(
+                    x: $scalar_type,
+                    y: $scalar_type,
+                    z: $scalar_type,
+                    w: $scalar_type,
+                ) -> Self {
+                    Self::new(w, x, y, z)
+                }
+                fn x(self) -> $scalar_type {
+                    self.i
+                }
+                fn y(self) -> $scalar_type {
+                    self.j
+                }
+                fn z(self) -> $scalar_type {
+                    self.k
+                }
+                fn w(self) -> $scalar_type {
+                    self.w
+                }
+            }
+
+            impl From<GenericRotor4<$scalar_type>> for na::Matrix4<$scalar_type> {
+                fn from(value: GenericRotor4<$scalar_type>) -> Self {
+                    value.into_mat4()
+                }
+            }
+
+            impl TryFrom<na::Matrix4<$scalar_type>> for GenericRotor4<$scalar_type> {
+                type Error = RotorError<$scalar_type>;
+                fn try_from(value: na::Matrix4<$scalar_type>) -> Result<Self, Self::Error> {
+                    Self::from_mat4_array(value.into())
+                }
+            }
+
+            /// A 4x4 matrix has no room for a 4D translation, so the transform comes out with none.
+            impl TryFrom<na::Matrix4<$scalar_type>>
+                for GenericRotateScaleTranslate4<$scalar_type, na::Vector4<$scalar_type>>
+            {
+                type Error = RotorError<$scalar_type>;
+                fn try_from(value: na::Matrix4<$scalar_type>) -> Result<Self, Self::Error> {
+                    Self::from_rotate_scale_array(value.into(), na::Vector4::zeros())
+                }
+            }
+
+            /// Components in the order `[xy, xz, xw, yz, wy, zw]`.
+            impl From<GenericBivec4<$scalar_type>> for na::Vector6<$scalar_type> {
+                fn from(value: GenericBivec4<$scalar_type>) -> Self {
+                    <[$scalar_type; 6]>::from(value).into()
+                }
+            }
+
+            /// Takes components in the order `[xy, xz, xw, yz, wy, zw]`.
+            impl From<na::Vector6<$scalar_type>> for GenericBivec4<$scalar_type> {
+                fn from(value: na::Vector6<$scalar_type>) -> Self {
+                    <[$scalar_type; 6]>::from(value).into()
+                }
+            }
+
+            impl Transform<na::Vector2<$scalar_type>> for na::Matrix2<$scalar_type> {
+                fn transform(&self, operand: na::Vector2<$scalar_type>) -> na::Vector2<$scalar_type> {
+                    self * operand
+                }
+            }
+
+            impl Transform<na::Vector2<$scalar_type>> for na::Isometry2<$scalar_type> {
+                fn transform(&self, operand: na::Vector2<$scalar_type>) -> na::Vector2<$scalar_type> {
+                    self.transform_point(&operand.into()).coords
+                }
+            }
+
+            impl Transform<na::Vector3<$scalar_type>> for na::Matrix3<$scalar_type> {
+                fn transform(&self, operand: na::Vector3<$scalar_type>) -> na::Vector3<$scalar_type> {
+                    self * operand
+                }
+            }
+
+            impl Transform<na::Vector3<$scalar_type>> for na::Isometry3<$scalar_type> {
+                fn transform(&self, operand: na::Vector3<$scalar_type>) -> na::Vector3<$scalar_type> {
+                    self.transform_point(&operand.into()).coords
+                }
+            }
+
+            impl Transform<na::Vector4<$scalar_type>> for na::Matrix4<$scalar_type> {
+                fn transform(&self, operand: na::Vector4<$scalar_type>) -> na::Vector4<$scalar_type> {
+                    self * operand
+                }
+            }
+        )*
+    };
+}
+impl_fixed_size_traits!(f32, f64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transform::rotor4::{Bivec4, DBivec4, DRotor4, Rotor4};
+
+    #[test]
+    fn from_cols_array_is_column_major() {
+        let got = <na::Matrix4<f32> as Matrix4>::from_cols_array([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+
+        assert_eq!(got.column(1), na::Vector4::new(5.0, 6.0, 7.0, 8.0));
+    }
+
+    #[test]
+    #[cfg(feature = "glam")]
+    fn rotor_transforms_nalgebra_vectors_like_glam() {
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xy: 0.5,
+            zw: -1.2,
+            xw: 0.3,
+            ..Bivec4::ZERO
+        });
+
+        let got = rotor.transform(na::Vector4::new(1.0, 2.0, 3.0, 4.0));
+        let expected = rotor.transform(glam::vec4(1.0, 2.0, 3.0, 4.0));
+
+        assert!((got - na::Vector4::from(expected.to_array())).norm() < 1e-5);
+    }
+
+    #[test]
+    fn rotor_matrix_matches_transform_in_double_precision() {
+        let rotor = DRotor4::from_bivec_angles(DBivec4 {
+            xz: 1.0,
+            wy: 2.0,
+            ..DBivec4::ZERO
+        });
+        let vector = na::Vector4::new(1.0, -2.0, 0.5, 3.0);
+
+        let matrix: na::Matrix4<f64> = rotor.into_mat4();
+
+        assert!((matrix * vector - rotor.transform(vector)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn rotor_matrix4_round_trip() {
+        let rotor = DRotor4::from_bivec_angles(DBivec4 {
+            xy: -0.6,
+            zw: 0.2,
+            ..DBivec4::ZERO
+        });
+        let vector = na::Vector4::new(1.0, 2.0, 3.0, 4.0);
+
+        let matrix = na::Matrix4::from(rotor);
+        let got = DRotor4::try_from(matrix).unwrap();
+
+        assert!((got.transform(vector) - matrix * vector).norm() < 1e-9);
+    }
+
+    #[test]
+    fn bivec_vector6_round_trip() {
+        let bivec = Bivec4 {
+            xy: 1.0,
+            xz: 2.0,
+            xw: 3.0,
+            yz: 4.0,
+            wy: 5.0,
+            zw: 6.0,
+        };
+
+        let vector = na::Vector6::from(bivec);
+        let got = Bivec4::from(vector);
+
+        assert_eq!(vector, na::Vector6::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0));
+        assert_eq!(<[f32; 6]>::from(got), <[f32; 6]>::from(bivec));
+    }
+
+    #[test]
+    fn isometry_transforms_as_point() {
+        let isometry = na::Isometry3::translation(1.0, 2.0, 3.0);
+
+        let got = isometry.transform(na::Vector3::new(1.0, 1.0, 1.0));
+
+        assert_eq!(got, na::Vector3::new(2.0, 3.0, 4.0));
+    }
+}
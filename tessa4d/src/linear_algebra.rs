@@ -3,32 +3,135 @@
 //! For example, if you want to use the vectors/matrices provided by a specific game engine.  
 //!
 
-use std::ops::{Add, Mul};
+use std::{
+    fmt::Debug,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
-use crate::transform::rotor4::Bivec4;
+use crate::transform::rotor4::GenericBivec4;
+
+/// Floating point type for the components of vectors and rotors, implemented for `f32` and `f64`.
+pub trait Scalar:
+    Copy
+    + Debug
+    + PartialOrd
+    + Sum
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const PI: Self;
+    const FRAC_PI_2: Self;
+    /// Tolerance for comparisons that need to absorb rounding error, e.g. to pick out special cases of rotors.
+    /// Much larger than the machine epsilon, as some operations lose half the precision to square roots.
+    const EPSILON: Self;
+
+    /// Converts from `f32`, for constants in generic code.
+    fn from_f32(value: f32) -> Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn recip(self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn total_cmp(&self, other: &Self) -> std::cmp::Ordering;
+    fn sin_cos(self) -> (Self, Self);
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+}
+
+macro_rules! impl_scalar_trait {
+    ($($scalar_type:ident: $epsilon:expr),*) => {
+        $(
+            impl Scalar for $scalar_type {
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+                const PI: Self = std::$scalar_type::consts::PI;
+                const FRAC_PI_2: Self = std::$scalar_type::consts::FRAC_PI_2;
+                const EPSILON: Self = $epsilon;
+
+                fn from_f32(value: f32) -> Self {
+                    value as Self
+                }
+
+                fn abs(self) -> Self {
+                    $scalar_type::abs(self)
+                }
+                fn signum(self) -> Self {
+                    $scalar_type::signum(self)
+                }
+                fn sqrt(self) -> Self {
+                    $scalar_type::sqrt(self)
+                }
+                fn recip(self) -> Self {
+                    $scalar_type::recip(self)
+                }
+                fn max(self, other: Self) -> Self {
+                    $scalar_type::max(self, other)
+                }
+                fn total_cmp(&self, other: &Self) -> std::cmp::Ordering {
+                    $scalar_type::total_cmp(self, other)
+                }
+                fn sin_cos(self) -> (Self, Self) {
+                    $scalar_type::sin_cos(self)
+                }
+                fn sin(self) -> Self {
+                    $scalar_type::sin(self)
+                }
+                fn cos(self) -> Self {
+                    $scalar_type::cos(self)
+                }
+                fn atan(self) -> Self {
+                    $scalar_type::atan(self)
+                }
+                fn atan2(self, other: Self) -> Self {
+                    $scalar_type::atan2(self, other)
+                }
+            }
+        )*
+    };
+}
+impl_scalar_trait!(f32: 1e-3, f64: 1e-6);
 
 /// Common trait bound for all vector types, used for implementations that are generic across the dimension of a vector,
-pub trait Vector: Copy + Add<Self, Output = Self> + Mul<f32, Output = Self> {
+/// `S` is the type of the components, single precision unless specified.
+pub trait Vector<S: Scalar = f32>: Copy + Add<Self, Output = Self> + Mul<S, Output = Self> {
     const ZERO: Self;
+    const ONE: Self;
 
-    fn dot(self, other: Self) -> f32;
+    fn dot(self, other: Self) -> S;
     fn normalized(self) -> Self;
+    /// Smallest value on each axis of the two vectors.
+    fn min_elementwise(self, other: Self) -> Self;
+    /// Largest value on each axis of the two vectors.
+    fn max_elementwise(self, other: Self) -> Self;
 }
 
 /// 4-element vector. Allows swapping out linear algebra implementations.
-pub trait Vector4: Vector {
-    type Matrix4: Matrix4<Vector4 = Self>;
-    type Vector3: Vector3<Vector4 = Self>;
+pub trait Vector4<S: Scalar = f32>: Vector<S> {
+    type Matrix4: Matrix4<S, Vector4 = Self>;
+    type Vector3: Vector3<S, Vector4 = Self>;
 
-    fn new(x: f32, y: f32, z: f32, w: f32) -> Self;
+    fn new(x: S, y: S, z: S, w: S) -> Self;
 
-    fn x(self) -> f32;
-    fn y(self) -> f32;
-    fn z(self) -> f32;
-    fn w(self) -> f32;
+    fn x(self) -> S;
+    fn y(self) -> S;
+    fn z(self) -> S;
+    fn w(self) -> S;
 
-    fn wedge(self, other: Self) -> Bivec4 {
-        Bivec4 {
+    fn wedge(self, other: Self) -> GenericBivec4<S> {
+        GenericBivec4 {
             xy: self.x() * other.y() - self.y() * other.x(),
             xz: self.x() * other.z() - self.z() * other.x(),
             xw: self.x() * other.w() - self.w() * other.x(),
@@ -40,34 +143,58 @@ pub trait Vector4: Vector {
 }
 
 /// 4x4 matrix. Allows swapping out linear algebra implementations.
-pub trait Matrix4: Mul<Self::Vector4, Output = Self::Vector4> {
-    type Vector4: Vector4<Matrix4 = Self>;
+/// Multiplying two matrices gives the transform that applies the right hand one first.
+/// Implementations must be `Copy` so that transforms holding a matrix, like [crate::transform::affine4::Affine4], can be too.
+pub trait Matrix4<S: Scalar = f32>:
+    Copy
+    + Add<Self, Output = Self>
+    + Mul<S, Output = Self>
+    + Mul<Self, Output = Self>
+    + Mul<Self::Vector4, Output = Self::Vector4>
+{
+    type Vector4: Vector4<S, Matrix4 = Self>;
     /// Identity matrix, 1s along the diagonal and 0s elsewhere.
     const IDENTITY: Self;
     /// Construct a 4x4 matrix from an array, takes input in column-major order.
-    fn from_cols_array(arr: [[f32; 4]; 4]) -> Self;
+    fn from_cols_array(arr: [[S; 4]; 4]) -> Self;
+    /// Inverse of [Matrix4::from_cols_array], returns the columns.
+    fn to_cols_array(&self) -> [[S; 4]; 4];
+    fn transpose(&self) -> Self;
+    fn determinant(&self) -> S;
+    /// Returns None if the matrix is singular.
+    fn try_inverse(&self) -> Option<Self>;
 }
 
-pub trait Vector3: Vector {
-    type Vector2: Vector2<Vector3 = Self>;
-    type Vector4: Vector4<Vector3 = Self>;
+pub trait Vector3<S: Scalar = f32>: Vector<S> {
+    type Vector2: Vector2<S, Vector3 = Self>;
+    type Vector4: Vector4<S, Vector3 = Self>;
 
-    fn new(x: f32, y: f32, z: f32) -> Self;
+    fn new(x: S, y: S, z: S) -> Self;
 
-    fn x(self) -> f32;
-    fn y(self) -> f32;
-    fn z(self) -> f32;
+    fn x(self) -> S;
+    fn y(self) -> S;
+    fn z(self) -> S;
 
     fn cross(self, other: Self) -> Self;
 }
 
-pub trait Vector2: Vector {
-    type Vector3: Vector3<Vector2 = Self>;
+pub trait Vector2<S: Scalar = f32>: Vector<S> {
+    type Vector3: Vector3<S, Vector2 = Self>;
+
+    fn new(x: S, y: S) -> Self;
+
+    fn x(self) -> S;
+    fn y(self) -> S;
+}
 
-    fn new(x: f32, y: f32) -> Self;
+/// Quaternion `w + xi + yj + zk`, for interop with [Rotor4](crate::transform::rotor4::Rotor4::to_quaternion_pair). Allows swapping out linear algebra implementations.
+pub trait Quaternion<S: Scalar = f32>: Copy {
+    fn from_xyzw(x: S, y: S, z: S, w: S) -> Self;
 
-    fn x(self) -> f32;
-    fn y(self) -> f32;
+    fn x(self) -> S;
+    fn y(self) -> S;
+    fn z(self) -> S;
+    fn w(self) -> S;
 }
 
 #[cfg(test)]
@@ -90,12 +217,24 @@ pub(crate) mod test_util {
             z: 0.0,
             w: 0.0,
         };
+        const ONE: Self = TestVec4 {
+            x: 1.0,
+            y: 1.0,
+            z: 1.0,
+            w: 1.0,
+        };
         fn dot(self, _: Self) -> f32 {
             0.0
         }
         fn normalized(self) -> Self {
             self
         }
+        fn min_elementwise(self, _: Self) -> Self {
+            self
+        }
+        fn max_elementwise(self, _: Self) -> Self {
+            self
+        }
     }
     impl Vector4 for TestVec4 {
         type Matrix4 = TestMat4;
@@ -142,12 +281,23 @@ pub(crate) mod test_util {
             y: 0.0,
             z: 0.0,
         };
+        const ONE: Self = Self {
+            x: 1.0,
+            y: 1.0,
+            z: 1.0,
+        };
         fn dot(self, _: Self) -> f32 {
             0.0
         }
         fn normalized(self) -> Self {
             self
         }
+        fn min_elementwise(self, _: Self) -> Self {
+            self
+        }
+        fn max_elementwise(self, _: Self) -> Self {
+            self
+        }
     }
     impl Vector3 for TestVec3 {
         type Vector2 = TestVec2;
@@ -183,6 +333,7 @@ pub(crate) mod test_util {
         }
     }
 
+    #[derive(Clone, Copy)]
     pub struct TestMat4;
     impl Matrix4 for TestMat4 {
         type Vector4 = TestVec4;
@@ -190,6 +341,36 @@ pub(crate) mod test_util {
         fn from_cols_array(_: [[f32; 4]; 4]) -> Self {
             Self
         }
+        fn to_cols_array(&self) -> [[f32; 4]; 4] {
+            [[0.0; 4]; 4]
+        }
+        fn transpose(&self) -> Self {
+            Self
+        }
+        fn determinant(&self) -> f32 {
+            0.0
+        }
+        fn try_inverse(&self) -> Option<Self> {
+            None
+        }
+    }
+    impl Add for TestMat4 {
+        type Output = Self;
+        fn add(self, _: Self) -> Self::Output {
+            self
+        }
+    }
+    impl Mul<f32> for TestMat4 {
+        type Output = Self;
+        fn mul(self, _: f32) -> Self::Output {
+            self
+        }
+    }
+    impl Mul for TestMat4 {
+        type Output = Self;
+        fn mul(self, _: Self) -> Self::Output {
+            self
+        }
     }
     impl Mul<TestVec4> for TestMat4 {
         type Output = TestVec4;
@@ -202,12 +383,19 @@ pub(crate) mod test_util {
     pub struct TestVec2;
     impl Vector for TestVec2 {
         const ZERO: Self = Self;
+        const ONE: Self = Self;
         fn dot(self, _: Self) -> f32 {
             0.0
         }
         fn normalized(self) -> Self {
             self
         }
+        fn min_elementwise(self, _: Self) -> Self {
+            self
+        }
+        fn max_elementwise(self, _: Self) -> Self {
+            self
+        }
     }
     impl Vector2 for TestVec2 {
         type Vector3 = TestVec3;
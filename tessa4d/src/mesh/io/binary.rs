@@ -0,0 +1,239 @@
+//! Compact binary format for tetrahedron meshes. All numbers are little-endian.
+//!
+//! | Field | Type |
+//! |---|---|
+//! | Magic bytes | `b"T4DM"` |
+//! | Format version, currently [VERSION] | `u16` |
+//! | Vertex count | `u32` |
+//! | Tetrahedron count | `u32` |
+//! | Attribute count | `u32` |
+//! | For each attribute: name length, name in UTF-8, components | `u16`, bytes, `u32` |
+//! | For each vertex: x, y, z, w | 4 × `f32` |
+//! | For each attribute: its values for every vertex | components × vertex count × `f32` |
+//! | For each tetrahedron: vertex indices | 4 × `u32` |
+//!
+//! Readers reject versions newer than they know about, so the layout can change in later versions.
+use std::io::{Read, Write};
+
+use super::{MeshFile4D, MeshFormatError, VertexAttribute};
+use crate::{
+    linear_algebra::Vector4,
+    mesh::{TetrahedronMesh4D, Vertex4},
+};
+
+const MAGIC: [u8; 4] = *b"T4DM";
+/// Version written by [write_binary], and the newest one [read_binary] understands.
+pub const VERSION: u16 = 1;
+
+/// Writes `file` in the binary format.
+pub fn write_binary<V: Vector4>(
+    mut writer: impl Write,
+    file: &MeshFile4D<V>,
+) -> Result<(), MeshFormatError> {
+    file.validate()?;
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    write_u32(&mut writer, file.mesh.vertices.len())?;
+    write_u32(&mut writer, file.mesh.simplexes.len())?;
+    write_u32(&mut writer, file.attributes.len())?;
+    for attribute in &file.attributes {
+        let name_length = u16::try_from(attribute.name.len())
+            .map_err(|_| MeshFormatError::TooLarge(attribute.name.len()))?;
+        writer.write_all(&name_length.to_le_bytes())?;
+        writer.write_all(attribute.name.as_bytes())?;
+        write_u32(&mut writer, attribute.components)?;
+    }
+    for vertex in &file.mesh.vertices {
+        let p = vertex.position;
+        for value in [p.x(), p.y(), p.z(), p.w()] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+    for value in file.attributes.iter().flat_map(|a| &a.values) {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    for &index in file.mesh.simplexes.iter().flatten() {
+        write_u32(&mut writer, index)?;
+    }
+    Ok(())
+}
+
+/// Reads a mesh in the binary format.
+pub fn read_binary<V: Vector4>(mut reader: impl Read) -> Result<MeshFile4D<V>, MeshFormatError> {
+    let magic: [u8; 4] = read_bytes(&mut reader, "header")?;
+    if magic != MAGIC {
+        return Err(MeshFormatError::Header {
+            expected: "tessa4d binary mesh",
+            found: String::from_utf8_lossy(&magic).into_owned(),
+        });
+    }
+    let version = u16::from_le_bytes(read_bytes(&mut reader, "version")?);
+    if version == 0 || version > VERSION {
+        return Err(MeshFormatError::UnsupportedVersion(version));
+    }
+    let vertex_count = read_u32(&mut reader, "vertex count")?;
+    let tetrahedron_count = read_u32(&mut reader, "tetrahedron count")?;
+    let attribute_count = read_u32(&mut reader, "attribute count")?;
+
+    let mut attributes = vec![];
+    for _ in 0..attribute_count {
+        let name_length = u16::from_le_bytes(read_bytes(&mut reader, "attribute name")?);
+        let mut name = vec![0; name_length.into()];
+        read_exact(&mut reader, &mut name, "attribute name")?;
+        let name = String::from_utf8(name).map_err(|err| {
+            MeshFormatError::AttributeName(String::from_utf8_lossy(err.as_bytes()).into_owned())
+        })?;
+        attributes.push(VertexAttribute {
+            name,
+            components: read_u32(&mut reader, "attribute components")?,
+            values: vec![],
+        });
+    }
+
+    // Grows the vecs as it reads rather than trusting the counts, so a corrupt count fails at the end of the file instead of allocating.
+    let mut vertices = vec![];
+    for _ in 0..vertex_count {
+        let [x, y, z, w] = [(); 4].map(|_| read_f32(&mut reader));
+        vertices.push(Vertex4 {
+            position: V::new(x?, y?, z?, w?),
+        });
+    }
+    for attribute in attributes.iter_mut() {
+        for _ in 0..attribute.value_count(vertex_count)? {
+            attribute.values.push(read_f32(&mut reader)?);
+        }
+    }
+    let mut simplexes = vec![];
+    for _ in 0..tetrahedron_count {
+        let [a, b, c, d] = [(); 4].map(|_| read_u32(&mut reader, "tetrahedron"));
+        simplexes.push([a?, b?, c?, d?]);
+    }
+
+    let file = MeshFile4D {
+        mesh: TetrahedronMesh4D {
+            vertices,
+            simplexes,
+        },
+        attributes,
+    };
+    file.validate()?;
+    Ok(file)
+}
+
+fn write_u32(writer: &mut impl Write, value: usize) -> Result<(), MeshFormatError> {
+    let value = u32::try_from(value).map_err(|_| MeshFormatError::TooLarge(value))?;
+    writer.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+/// Fills `buffer`, turning the end of the file into [MeshFormatError::UnexpectedEof] for the `expected` field.
+fn read_exact(
+    reader: &mut impl Read,
+    buffer: &mut [u8],
+    expected: &'static str,
+) -> Result<(), MeshFormatError> {
+    reader.read_exact(buffer).map_err(|err| match err.kind() {
+        std::io::ErrorKind::UnexpectedEof => MeshFormatError::UnexpectedEof(expected),
+        _ => err.into(),
+    })
+}
+
+fn read_bytes<const N: usize>(
+    reader: &mut impl Read,
+    expected: &'static str,
+) -> Result<[u8; N], MeshFormatError> {
+    let mut bytes = [0; N];
+    read_exact(reader, &mut bytes, expected)?;
+    Ok(bytes)
+}
+
+fn read_u32(reader: &mut impl Read, expected: &'static str) -> Result<usize, MeshFormatError> {
+    Ok(u32::from_le_bytes(read_bytes(reader, expected)?) as usize)
+}
+
+fn read_f32(reader: &mut impl Read) -> Result<f32, MeshFormatError> {
+    Ok(f32::from_le_bytes(read_bytes(reader, "vertex data")?))
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec4;
+
+    use super::super::test_util::{assert_files_equal, example_file};
+    use super::*;
+
+    fn write(file: &MeshFile4D<Vec4>) -> Vec<u8> {
+        let mut bytes = vec![];
+        write_binary(&mut bytes, file).unwrap();
+        bytes
+    }
+
+    fn read(bytes: &[u8]) -> Result<MeshFile4D<Vec4>, MeshFormatError> {
+        read_binary(bytes)
+    }
+
+    #[test]
+    fn round_trip() {
+        let file = example_file();
+
+        let got = read(&write(&file)).unwrap();
+
+        assert_files_equal(&got, &file);
+    }
+
+    #[test]
+    fn empty_mesh_is_header_only() {
+        let bytes = write(&MeshFile4D::from(TetrahedronMesh4D::<Vec4>::default()));
+
+        assert_eq!(bytes.len(), 4 + 2 + 3 * 4);
+        assert_eq!(&bytes[..6], b"T4DM\x01\x00");
+    }
+
+    #[test]
+    fn rejects_wrong_magic() {
+        let mut bytes = write(&example_file());
+        bytes[0] = b'X';
+
+        let got = dbg!(read(&bytes));
+
+        assert!(matches!(got, Err(MeshFormatError::Header { .. })));
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let mut bytes = write(&example_file());
+        bytes[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes());
+
+        let got = dbg!(read(&bytes));
+
+        assert!(matches!(got, Err(MeshFormatError::UnsupportedVersion(2))));
+    }
+
+    #[test]
+    fn rejects_truncated_file() {
+        let bytes = write(&example_file());
+
+        let got = dbg!(read(&bytes[..bytes.len() - 1]));
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::UnexpectedEof("tetrahedron"))
+        ));
+    }
+
+    #[test]
+    fn rejects_out_of_range_index() {
+        let mut bytes = write(&MeshFile4D::from(
+            TetrahedronMesh4D::<Vec4>::tesseract_cube(1.0),
+        ));
+        let last = bytes.len() - 4;
+        bytes[last..].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let got = dbg!(read(&bytes));
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::IndexOutOfRange { index, .. }) if index == u32::MAX as usize
+        ));
+    }
+}
@@ -0,0 +1,390 @@
+//! Reading and writing meshes in files.
+//!
+//! For [TetrahedronMesh4D]s:
+//! * [off4] is a plain text format extending OFF to 4D, easy to write by hand or from other tools.
+//! * [binary] is a compact versioned binary format, for loading large meshes quickly.
+//!
+//! Both formats can carry extra per-vertex data alongside the positions, see [VertexAttribute].
+//!
+//! For [TriangleMesh3D]s, e.g. cross-sections, there are writers for common 3D formats: [obj], [ply] and [stl].
+//! [obj] and [ply] can also be read, to import surfaces made in other tools, see [Tetrahedralize](super::ops::Tetrahedralize) to fill them in.
+pub mod binary;
+pub mod obj;
+pub mod off4;
+pub mod ply;
+pub mod stl;
+
+use std::io::BufRead;
+
+use thiserror::Error;
+
+use super::{
+    polygon::PolygonError, SimplexMesh, TetrahedronMesh4D, TriangleMesh2D, TriangleMesh3D, Vertex3,
+};
+use crate::linear_algebra::{Vector2, Vector3, Vector4};
+
+/// Extra data stored for every vertex of a mesh, e.g. colors or texture coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VertexAttribute {
+    /// Identifies the attribute in the file, must be a single word without whitespace.
+    pub name: String,
+    /// Number of values for each vertex, e.g. 4 for an RGBA color.
+    pub components: usize,
+    /// Values for all vertices in order, `components` values per vertex.
+    pub values: Vec<f32>,
+}
+
+impl VertexAttribute {
+    /// Values for the vertex at `index`.
+    pub fn get(&self, index: usize) -> &[f32] {
+        &self.values[index * self.components..(index + 1) * self.components]
+    }
+
+    /// Number of values needed for `vertex_count` vertices, an error if it overflows.
+    pub(super) fn value_count(&self, vertex_count: usize) -> Result<usize, MeshFormatError> {
+        self.components.checked_mul(vertex_count).ok_or_else(|| {
+            MeshFormatError::AttributeOverflow {
+                name: self.name.clone(),
+                components: self.components,
+                vertex_count,
+            }
+        })
+    }
+}
+
+/// Contents of a mesh file, the mesh and any vertex attributes stored with it.
+#[derive(Clone, Debug)]
+pub struct MeshFile4D<V: Vector4> {
+    pub mesh: TetrahedronMesh4D<V>,
+    pub attributes: Vec<VertexAttribute>,
+}
+
+impl<V: Vector4> MeshFile4D<V> {
+    /// Checks that every attribute has a usable name and one set of values per vertex, as the writers require.
+    pub fn validate(&self) -> Result<(), MeshFormatError> {
+        let vertex_count = self.mesh.vertices.len();
+        for attribute in &self.attributes {
+            if attribute.name.is_empty() || attribute.name.contains(char::is_whitespace) {
+                return Err(MeshFormatError::AttributeName(attribute.name.clone()));
+            }
+            let expected = attribute.value_count(vertex_count)?;
+            if attribute.values.len() != expected {
+                return Err(MeshFormatError::AttributeLength {
+                    name: attribute.name.clone(),
+                    expected,
+                    found: attribute.values.len(),
+                });
+            }
+        }
+        check_indices(&self.mesh)
+    }
+}
+
+/// File with just the mesh and no attributes.
+impl<V: Vector4> From<TetrahedronMesh4D<V>> for MeshFile4D<V> {
+    fn from(mesh: TetrahedronMesh4D<V>) -> Self {
+        Self {
+            mesh,
+            attributes: vec![],
+        }
+    }
+}
+
+/// Problems reading or writing mesh files.
+#[derive(Debug, Error)]
+pub enum MeshFormatError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Expected a {expected} file, found header {found:?}")]
+    Header {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("Line {line}: {message}")]
+    Parse { line: usize, message: String },
+    #[error("File ended early, expected {0}")]
+    UnexpectedEof(&'static str),
+    #[error("Unsupported format version {0}")]
+    UnsupportedVersion(u16),
+    #[error(
+        "Simplex {simplex} refers to vertex {index}, but there are only {vertex_count} vertices"
+    )]
+    IndexOutOfRange {
+        simplex: usize,
+        index: usize,
+        vertex_count: usize,
+    },
+    #[error("Attribute name {0:?} must be a non-empty single word")]
+    AttributeName(String),
+    #[error("Attribute {name:?} needs {expected} values for the mesh, found {found}")]
+    AttributeLength {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Attribute {name:?} has too many values, {components} components for each of {vertex_count} vertices")]
+    AttributeOverflow {
+        name: String,
+        components: usize,
+        vertex_count: usize,
+    },
+    #[error("Mesh is too large for the format, {0} doesn't fit in 32 bits")]
+    TooLarge(usize),
+    #[error("Expected one normal per vertex, {expected} of them, found {found}")]
+    NormalCount { expected: usize, found: usize },
+    #[error("Couldn't split face {face} into triangles, {error}")]
+    Face { face: usize, error: PolygonError },
+}
+
+fn check_indices<V, const N: usize>(mesh: &SimplexMesh<V, N>) -> Result<(), MeshFormatError> {
+    let vertex_count = mesh.vertices.len();
+    for (simplex, indices) in mesh.simplexes.iter().enumerate() {
+        if let Some(&index) = indices.iter().find(|&&index| index >= vertex_count) {
+            return Err(MeshFormatError::IndexOutOfRange {
+                simplex,
+                index,
+                vertex_count,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Splits faces with any number of corners into triangles with the same winding, as indices into `vertices`.
+/// Faces with more than 3 corners are ear clipped in the plane they are flattest in, so they can be concave but mustn't cross themselves.
+/// Faces with fewer than 3 corners are skipped.
+fn triangulate_faces<V: Vector3>(
+    vertices: &[Vertex3<V>],
+    faces: &[Vec<usize>],
+) -> Result<Vec<[usize; 3]>, MeshFormatError> {
+    let mut triangles = vec![];
+    for (face, corners) in faces.iter().enumerate() {
+        if let Some(&index) = corners.iter().find(|&&index| index >= vertices.len()) {
+            return Err(MeshFormatError::IndexOutOfRange {
+                simplex: face,
+                index,
+                vertex_count: vertices.len(),
+            });
+        }
+        match corners[..] {
+            [] | [_] | [_, _] => continue,
+            [a, b, c] => {
+                triangles.push([a, b, c]);
+                continue;
+            }
+            _ => {}
+        }
+        let positions: Vec<V> = corners.iter().map(|&i| vertices[i].position).collect();
+        let origin = positions[0] * -1.0;
+        // Newell's method, the cross products around the face add up to its normal whatever its shape.
+        let normal = positions.windows(2).fold(V::ZERO, |sum, edge| {
+            sum + (edge[0] + origin).cross(edge[1] + origin)
+        });
+        let normal = [normal.x(), normal.y(), normal.z()];
+        let axis = (0..3)
+            .max_by(|&i, &j| normal[i].abs().total_cmp(&normal[j].abs()))
+            .unwrap_or(2);
+        // Dropping the axis closest to the normal, the other two stay in cyclic order,
+        // so the face winds counterclockwise in them if its normal is positive on that axis.
+        let outline: Vec<V::Vector2> = positions
+            .iter()
+            .map(|p| {
+                let p = [p.x(), p.y(), p.z()];
+                V::Vector2::new(p[(axis + 1) % 3], p[(axis + 2) % 3])
+            })
+            .collect();
+        match TriangleMesh2D::polygon(&outline, &[]) {
+            // Polygon triangles go clockwise like TriangleMesh2D::rectangle, flip them to follow the face.
+            Ok(polygon) => triangles.extend(polygon.simplexes.into_iter().map(|triangle| {
+                let [a, b, c] = triangle.map(|i| corners[i]);
+                if normal[axis] > 0.0 {
+                    [a, c, b]
+                } else {
+                    [a, b, c]
+                }
+            })),
+            // Without any area there's no wrong way to split it, keep the fan rather than dropping the face.
+            Err(PolygonError::EmptyOutline) => triangles
+                .extend((1..corners.len() - 1).map(|i| [corners[0], corners[i], corners[i + 1]])),
+            Err(error) => return Err(MeshFormatError::Face { face, error }),
+        }
+    }
+    Ok(triangles)
+}
+
+/// Checks a triangle mesh, and optional per-vertex normals for it, before writing them out.
+fn check_triangle_mesh<V: Vector3>(
+    mesh: &TriangleMesh3D<V>,
+    normals: Option<&[V]>,
+) -> Result<(), MeshFormatError> {
+    if let Some(normals) = normals {
+        if normals.len() != mesh.vertices.len() {
+            return Err(MeshFormatError::NormalCount {
+                expected: mesh.vertices.len(),
+                found: normals.len(),
+            });
+        }
+    }
+    check_indices(mesh)
+}
+
+/// Unit normal of a triangle, facing the side its vertices wind counterclockwise around. Zero for degenerate triangles.
+fn face_normal<V: Vector3>(mesh: &TriangleMesh3D<V>, triangle: [usize; 3]) -> V {
+    let [a, b, c] = triangle.map(|i| mesh.vertices[i].position);
+    let normal = (b + a * -1.0).cross(c + a * -1.0);
+    if normal.dot(normal) > 0.0 {
+        normal.normalized()
+    } else {
+        V::ZERO
+    }
+}
+
+/// Non-empty lines of the file split into whitespace separated tokens, with comments removed.
+pub(super) struct Lines<R> {
+    reader: R,
+    buffer: String,
+    line: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub(super) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            line: 0,
+        }
+    }
+
+    /// Returns the line number and tokens of the next non-empty line, `expected` describes it for the error at the end of the file.
+    pub(super) fn next_line(
+        &mut self,
+        expected: &'static str,
+    ) -> Result<(usize, Vec<&str>), MeshFormatError> {
+        loop {
+            self.buffer.clear();
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                return Err(MeshFormatError::UnexpectedEof(expected));
+            }
+            self.line += 1;
+            let content = self.buffer.split('#').next().unwrap_or_default();
+            if !content.trim().is_empty() {
+                break;
+            }
+        }
+        let content = self.buffer.split('#').next().unwrap_or_default();
+        Ok((self.line, content.split_whitespace().collect()))
+    }
+}
+
+pub(super) fn parse<T: std::str::FromStr>(line: usize, token: &str) -> Result<T, MeshFormatError>
+where
+    T::Err: std::fmt::Display,
+{
+    token
+        .parse()
+        .map_err(|err| parse_error(line, format!("invalid number {token:?}, {err}")))
+}
+
+pub(super) fn parse_error(line: usize, message: impl Into<String>) -> MeshFormatError {
+    MeshFormatError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use glam::Vec4;
+
+    use super::*;
+
+    /// Small mesh with a couple of attributes, for round trip tests.
+    pub fn example_file() -> MeshFile4D<Vec4> {
+        let mesh = TetrahedronMesh4D::<Vec4>::tesseract_cube(1.5);
+        let vertex_count = mesh.vertices.len();
+        MeshFile4D {
+            attributes: vec![
+                VertexAttribute {
+                    name: "color".to_string(),
+                    components: 4,
+                    values: (0..vertex_count * 4).map(|i| i as f32 / 7.0).collect(),
+                },
+                VertexAttribute {
+                    name: "weight".to_string(),
+                    components: 1,
+                    values: (0..vertex_count).map(|i| -0.1 * i as f32).collect(),
+                },
+            ],
+            mesh,
+        }
+    }
+
+    pub fn assert_files_equal(got: &MeshFile4D<Vec4>, expected: &MeshFile4D<Vec4>) {
+        assert_eq!(got.mesh.simplexes, expected.mesh.simplexes);
+        let positions = |file: &MeshFile4D<Vec4>| {
+            file.mesh
+                .vertices
+                .iter()
+                .map(|v| v.position)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(positions(got), positions(expected));
+        assert_eq!(got.attributes, expected.attributes);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec4;
+
+    use super::test_util::example_file;
+    use super::*;
+
+    #[test]
+    fn validate_accepts_example() {
+        assert!(example_file().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_short_attribute() {
+        let mut file = example_file();
+        file.attributes[1].values.pop();
+
+        let got = dbg!(file.validate());
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::AttributeLength { found, expected, .. }) if found + 1 == expected
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_overflowing_attribute() {
+        let mut file = example_file();
+        file.attributes[1].components = usize::MAX;
+
+        let got = dbg!(file.validate());
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::AttributeOverflow {
+                components: usize::MAX,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_bad_index() {
+        let mut file = MeshFile4D::from(TetrahedronMesh4D::<Vec4>::tesseract_cube(1.0));
+        let vertex_count = file.mesh.vertices.len();
+        file.mesh.simplexes[3][2] = vertex_count;
+
+        let got = dbg!(file.validate());
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::IndexOutOfRange { simplex: 3, .. })
+        ));
+    }
+}
@@ -0,0 +1,223 @@
+//! Reader and writer for Wavefront OBJ, the plain text format most 3D modelling tools can import and export.
+use std::io::{BufRead, Write};
+
+use super::{check_triangle_mesh, parse, parse_error, triangulate_faces, Lines, MeshFormatError};
+use crate::{
+    linear_algebra::Vector3,
+    mesh::{TriangleMesh3D, Vertex3},
+};
+
+/// Writes `mesh` as a Wavefront OBJ file, with a vertex normal for each vertex if `normals` is given.
+/// Triangles keep their winding, OBJ faces are counterclockwise around their front side.
+pub fn write_obj<V: Vector3>(
+    mut writer: impl Write,
+    mesh: &TriangleMesh3D<V>,
+    normals: Option<&[V]>,
+) -> Result<(), MeshFormatError> {
+    check_triangle_mesh(mesh, normals)?;
+    writeln!(writer, "# Written by tessa4d")?;
+    for vertex in &mesh.vertices {
+        let p = vertex.position;
+        writeln!(writer, "v {} {} {}", p.x(), p.y(), p.z())?;
+    }
+    for n in normals.unwrap_or_default() {
+        writeln!(writer, "vn {} {} {}", n.x(), n.y(), n.z())?;
+    }
+    for triangle in &mesh.simplexes {
+        // OBJ indices start from 1, and normals share the vertex indices.
+        let [a, b, c] = triangle.map(|i| i + 1);
+        if normals.is_some() {
+            writeln!(writer, "f {a}//{a} {b}//{b} {c}//{c}")?;
+        } else {
+            writeln!(writer, "f {a} {b} {c}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the vertices and faces of a Wavefront OBJ file, any other data such as normals, texture coordinates and groups is ignored.
+/// Faces with more than 3 vertices are split into triangles, they can be concave but mustn't cross themselves.
+pub fn read_obj<V: Vector3>(reader: impl BufRead) -> Result<TriangleMesh3D<V>, MeshFormatError> {
+    let mut lines = Lines::new(reader);
+    let mut mesh = TriangleMesh3D::default();
+    let mut faces = vec![];
+    loop {
+        let (line, tokens) = match lines.next_line("OBJ data") {
+            Ok(line) => line,
+            Err(MeshFormatError::UnexpectedEof(_)) => break,
+            Err(err) => return Err(err),
+        };
+        match tokens[..] {
+            ["v", x, y, z, ..] => mesh.vertices.push(Vertex3 {
+                position: V::new(parse(line, x)?, parse(line, y)?, parse(line, z)?),
+            }),
+            ["v", ..] => return Err(parse_error(line, "expected 3 coordinates for a vertex")),
+            ["f", ref corners @ ..] => {
+                if corners.len() < 3 {
+                    return Err(parse_error(line, "expected at least 3 vertices for a face"));
+                }
+                let indices = corners
+                    .iter()
+                    .map(|corner| obj_index(line, corner, mesh.vertices.len()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                faces.push(indices);
+            }
+            _ => {}
+        }
+    }
+    mesh.simplexes = triangulate_faces(&mesh.vertices, &faces)?;
+    Ok(mesh)
+}
+
+/// Converts the vertex part of a face corner, e.g. `3`, `3/1`, `3//2` or `-1`, to an index from 0.
+/// OBJ counts from 1, and negative indices count back from the latest vertex.
+fn obj_index(line: usize, corner: &str, vertex_count: usize) -> Result<usize, MeshFormatError> {
+    let vertex = corner.split('/').next().unwrap_or_default();
+    let index: isize = parse(line, vertex)?;
+    let index = match index {
+        1.. => index - 1,
+        ..=-1 => vertex_count as isize + index,
+        0 => return Err(parse_error(line, "vertex indices start from 1")),
+    };
+    usize::try_from(index)
+        .map_err(|_| parse_error(line, format!("vertex {vertex} is before the first vertex")))
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec3;
+
+    use super::*;
+    use crate::mesh::{TriangleMesh, Vertex3};
+
+    fn triangle() -> TriangleMesh3D<Vec3> {
+        TriangleMesh {
+            vertices: [Vec3::ZERO, Vec3::X, Vec3::new(0.0, 1.5, 0.0)]
+                .map(|position| Vertex3 { position })
+                .to_vec(),
+            simplexes: vec![[0, 1, 2]],
+        }
+    }
+
+    #[test]
+    fn writes_vertices_and_faces() {
+        let mut bytes = vec![];
+
+        write_obj(&mut bytes, &triangle(), None).unwrap();
+
+        let got = String::from_utf8(bytes).unwrap();
+        assert_eq!(
+            got,
+            "# Written by tessa4d\nv 0 0 0\nv 1 0 0\nv 0 1.5 0\nf 1 2 3\n"
+        );
+    }
+
+    #[test]
+    fn writes_normals() {
+        let mut bytes = vec![];
+
+        write_obj(&mut bytes, &triangle(), Some(&[Vec3::Z; 3])).unwrap();
+
+        let got = String::from_utf8(bytes).unwrap();
+        assert!(got.contains("vn 0 0 1\n"));
+        assert!(got.ends_with("f 1//1 2//2 3//3\n"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let cube = TriangleMesh3D::<Vec3>::cube(2.0);
+        let mut bytes = vec![];
+
+        write_obj(&mut bytes, &cube, Some(&[Vec3::Z; 8])).unwrap();
+        let got: TriangleMesh3D<Vec3> = read_obj(bytes.as_slice()).unwrap();
+
+        assert_eq!(got.simplexes, cube.simplexes);
+        for (got, expected) in got.vertices.iter().zip(&cube.vertices) {
+            assert_eq!(got.position, expected.position);
+        }
+    }
+
+    #[test]
+    fn reads_polygons() {
+        let text = "# Blender export
+            o Quad
+            v 0 0 0
+            v 1 0 0
+            v 1 1 0
+            v 0 1 0
+            vt 0 0
+            vn 0 0 1
+            s off
+            f 1/1/1 2/1/1 3/1/1 -1//1
+        ";
+
+        let got: TriangleMesh3D<Vec3> = read_obj(text.as_bytes()).unwrap();
+
+        assert_eq!(got.vertices.len(), 4);
+        assert_eq!(got.simplexes, vec![[3, 0, 1], [1, 2, 3]]);
+    }
+
+    #[test]
+    fn reads_concave_polygons() {
+        // An arrowhead pointing along +x with its notch at vertex 3, facing +z and then -z.
+        let text = "v 0 0 0\nv 2 1 0\nv 0 2 0\nv 1 1 0\nf 1 2 3 4\nf 4 3 2 1\n";
+
+        let got: TriangleMesh3D<Vec3> = read_obj(text.as_bytes()).unwrap();
+
+        assert_eq!(got.simplexes.len(), 4);
+        for (i, triangle) in got.simplexes.iter().enumerate() {
+            let [a, b, c] = triangle.map(|i| got.vertices[i].position);
+            let facing = if i < 2 { 1.0 } else { -1.0 };
+            assert!(dbg!((b - a).cross(c - a)).z * facing > 0.0);
+            // Every triangle uses the notch, a fan from the first vertex would cover the outside of the notch instead.
+            assert!(triangle.contains(&3));
+        }
+    }
+
+    #[test]
+    fn rejects_self_intersecting_polygons() {
+        // A 7 pointed star drawn in one stroke, in the xz plane.
+        let text = "v 10 0 0\nv -9 0 4\nv 6 0 -8\nv -2 0 10\nv -2 0 -10\nv 6 0 8\nv -9 0 -4\nf 1 2 3 4 5 6 7\n";
+
+        let got = dbg!(read_obj::<Vec3>(text.as_bytes()));
+
+        assert!(matches!(got, Err(MeshFormatError::Face { face: 0, .. })));
+    }
+
+    #[test]
+    fn rejects_bad_index() {
+        let got = dbg!(read_obj::<Vec3>("v 0 0 0\nf 1 2 0\n".as_bytes()));
+
+        assert!(matches!(got, Err(MeshFormatError::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn rejects_out_of_range_index() {
+        // The quad before it splits into 2 triangles, but the error still names the face.
+        let text = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\nf 1 2 5\n";
+
+        let got = dbg!(read_obj::<Vec3>(text.as_bytes()));
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::IndexOutOfRange {
+                simplex: 1,
+                index: 4,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_wrong_normal_count() {
+        let got = dbg!(write_obj(vec![], &triangle(), Some(&[Vec3::Z; 2])));
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::NormalCount {
+                expected: 3,
+                found: 2
+            })
+        ));
+    }
+}
@@ -0,0 +1,262 @@
+//! Text format for tetrahedron meshes, the OFF format extended to 4D.
+//!
+//! ```text
+//! 4OFF
+//! # Comments run from '#' to the end of the line, blank lines are ignored.
+//! attribute color 4
+//! attribute weight 1
+//! 5 2 0
+//! 0 0 0 0   1 0 0 1   0.5
+//! 1 0 0 0   0 1 0 1   0.5
+//! 0 1 0 0   0 0 1 1   0.5
+//! 0 0 1 0   1 1 0 1   0.5
+//! 0 0 0 1   1 0 1 1   0.5
+//! 4 0 1 2 3
+//! 4 0 1 2 4
+//! ```
+//!
+//! * The header is `4OFF`, optionally followed by `attribute <name> <components>` lines declaring extra per-vertex values.
+//! * Then the counts of vertices and tetrahedra, and optionally a count of edges which is ignored, as in OFF.
+//! * Each vertex is its x, y, z and w coordinates, followed by the values of each attribute in the order they were declared.
+//! * Each tetrahedron is the number of vertices, which must be 4, followed by the indices of its vertices starting from 0.
+use std::io::{BufRead, Write};
+
+use super::{parse, parse_error, Lines, MeshFile4D, MeshFormatError, VertexAttribute};
+use crate::{
+    linear_algebra::Vector4,
+    mesh::{TetrahedronMesh4D, Vertex4},
+};
+
+const HEADER: &str = "4OFF";
+
+/// Writes `file` in the 4OFF text format.
+pub fn write_off4<V: Vector4>(
+    mut writer: impl Write,
+    file: &MeshFile4D<V>,
+) -> Result<(), MeshFormatError> {
+    file.validate()?;
+    writeln!(writer, "{HEADER}")?;
+    for attribute in &file.attributes {
+        writeln!(
+            writer,
+            "attribute {} {}",
+            attribute.name, attribute.components
+        )?;
+    }
+    writeln!(
+        writer,
+        "{} {} 0",
+        file.mesh.vertices.len(),
+        file.mesh.simplexes.len()
+    )?;
+    for (i, vertex) in file.mesh.vertices.iter().enumerate() {
+        let p = vertex.position;
+        // Display for floats prints the shortest string that parses back to the same value, so positions round trip exactly.
+        write!(writer, "{} {} {} {}", p.x(), p.y(), p.z(), p.w())?;
+        for value in file.attributes.iter().flat_map(|a| a.get(i)) {
+            write!(writer, " {value}")?;
+        }
+        writeln!(writer)?;
+    }
+    for [a, b, c, d] in &file.mesh.simplexes {
+        writeln!(writer, "4 {a} {b} {c} {d}")?;
+    }
+    Ok(())
+}
+
+/// Reads a mesh in the 4OFF text format.
+pub fn read_off4<V: Vector4>(reader: impl BufRead) -> Result<MeshFile4D<V>, MeshFormatError> {
+    let mut lines = Lines::new(reader);
+    let (_, header) = lines.next_line("header")?;
+    if header != [HEADER] {
+        return Err(MeshFormatError::Header {
+            expected: HEADER,
+            found: header.join(" "),
+        });
+    }
+
+    let mut attributes = vec![];
+    let mut values_per_vertex: usize = 4;
+    let (line, counts) = loop {
+        let (line, tokens) = lines.next_line("vertex and tetrahedron counts")?;
+        if tokens[0] != "attribute" {
+            break (line, tokens);
+        }
+        let [_, name, components] = tokens[..] else {
+            return Err(parse_error(
+                line,
+                "expected `attribute <name> <components>`",
+            ));
+        };
+        let components = parse(line, components)?;
+        values_per_vertex = values_per_vertex
+            .checked_add(components)
+            .ok_or_else(|| parse_error(line, "too many attribute components"))?;
+        attributes.push(VertexAttribute {
+            name: name.to_string(),
+            components,
+            values: vec![],
+        });
+    };
+    let (vertex_count, tetrahedron_count) = match counts[..] {
+        [vertices, tetrahedra] | [vertices, tetrahedra, _] => {
+            (parse(line, vertices)?, parse(line, tetrahedra)?)
+        }
+        _ => return Err(parse_error(line, "expected vertex and tetrahedron counts")),
+    };
+
+    let mut vertices = vec![];
+    for _ in 0..vertex_count {
+        let (line, tokens) = lines.next_line("vertex")?;
+        if tokens.len() != values_per_vertex {
+            return Err(parse_error(
+                line,
+                format!(
+                    "expected {values_per_vertex} values for a vertex, found {}",
+                    tokens.len()
+                ),
+            ));
+        }
+        let values = tokens
+            .iter()
+            .map(|token| parse::<f32>(line, token))
+            .collect::<Result<Vec<_>, _>>()?;
+        vertices.push(Vertex4 {
+            position: V::new(values[0], values[1], values[2], values[3]),
+        });
+        let mut rest = &values[4..];
+        for attribute in attributes.iter_mut() {
+            let (values, remaining) = rest.split_at(attribute.components);
+            attribute.values.extend_from_slice(values);
+            rest = remaining;
+        }
+    }
+
+    let mut simplexes = vec![];
+    for _ in 0..tetrahedron_count {
+        let (line, tokens) = lines.next_line("tetrahedron")?;
+        let ["4", a, b, c, d] = tokens[..] else {
+            return Err(parse_error(
+                line,
+                "expected a tetrahedron, `4` and then 4 vertex indices",
+            ));
+        };
+        simplexes.push([
+            parse(line, a)?,
+            parse(line, b)?,
+            parse(line, c)?,
+            parse(line, d)?,
+        ]);
+    }
+
+    let file = MeshFile4D {
+        mesh: TetrahedronMesh4D {
+            vertices,
+            simplexes,
+        },
+        attributes,
+    };
+    file.validate()?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec4;
+
+    use super::super::test_util::{assert_files_equal, example_file};
+    use super::*;
+
+    fn read(text: &str) -> Result<MeshFile4D<Vec4>, MeshFormatError> {
+        read_off4(text.as_bytes())
+    }
+
+    #[test]
+    fn round_trip() {
+        let file = example_file();
+        let mut text = vec![];
+
+        write_off4(&mut text, &file).unwrap();
+        let got = read_off4(text.as_slice()).unwrap();
+
+        assert_files_equal(&got, &file);
+    }
+
+    #[test]
+    fn reads_documented_example() {
+        let text = "4OFF
+            # Comments run from '#' to the end of the line, blank lines are ignored.
+            attribute color 4
+
+            5 2 0
+            0 0 0 0   1 0 0 1
+            1 0 0 0   0 1 0 1
+            0 1 0 0   0 0 1 1
+            0 0 1 0   1 1 0 1
+            0 0 0 1   1 0 1 1 # trailing comment
+            4 0 1 2 3
+            4 0 1 2 4
+        ";
+
+        let got = read(text).unwrap();
+
+        assert_eq!(got.mesh.simplexes, vec![[0, 1, 2, 3], [0, 1, 2, 4]]);
+        assert_eq!(got.mesh.vertices[4].position, Vec4::W);
+        assert_eq!(got.attributes[0].get(2), &[0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn rejects_wrong_header() {
+        let got = dbg!(read("OFF\n0 0 0\n"));
+
+        assert!(matches!(got, Err(MeshFormatError::Header { .. })));
+    }
+
+    #[test]
+    fn rejects_triangle_cells() {
+        let got = dbg!(read("4OFF\n3 1\n0 0 0 0\n1 0 0 0\n0 1 0 0\n3 0 1 2\n"));
+
+        assert!(matches!(got, Err(MeshFormatError::Parse { line: 6, .. })));
+    }
+
+    #[test]
+    fn rejects_invalid_number() {
+        let got = dbg!(read("4OFF\n1 0\n0 zero 0 0\n"));
+
+        assert!(matches!(got, Err(MeshFormatError::Parse { line: 3, .. })));
+    }
+
+    #[test]
+    fn rejects_missing_attribute_values() {
+        let got = dbg!(read("4OFF\nattribute weight 1\n1 0\n0 0 0 0\n"));
+
+        assert!(matches!(got, Err(MeshFormatError::Parse { line: 4, .. })));
+    }
+
+    #[test]
+    fn rejects_overflowing_attribute_components() {
+        let got = dbg!(read(&format!(
+            "4OFF\nattribute a {}\nattribute b 4\n0 0\n",
+            usize::MAX - 5
+        )));
+
+        assert!(matches!(got, Err(MeshFormatError::Parse { line: 3, .. })));
+    }
+
+    #[test]
+    fn rejects_truncated_file() {
+        let got = dbg!(read("4OFF\n2 0\n0 0 0 0\n"));
+
+        assert!(matches!(got, Err(MeshFormatError::UnexpectedEof(_))));
+    }
+
+    #[test]
+    fn rejects_out_of_range_index() {
+        let got = dbg!(read("4OFF\n1 1\n0 0 0 0\n4 0 0 0 1\n"));
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::IndexOutOfRange { index: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,609 @@
+//! Reader and writer for the Stanford PLY format, in either its ASCII or binary encodings.
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+};
+
+use super::{check_triangle_mesh, parse, parse_error, triangulate_faces, Lines, MeshFormatError};
+use crate::{
+    linear_algebra::Vector3,
+    mesh::{TriangleMesh3D, Vertex3},
+};
+
+/// Encoding for the body of a PLY file, the header is always text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlyFormat {
+    /// Human readable, one vertex or face per line.
+    Ascii,
+    /// Compact, little-endian `f32` coordinates and `u32` indices.
+    BinaryLittleEndian,
+    /// Like [PlyFormat::BinaryLittleEndian] but big-endian.
+    BinaryBigEndian,
+}
+
+/// Writes `mesh` as a PLY file, with `nx`, `ny` and `nz` vertex properties if `normals` is given.
+/// Faces keep their winding, counterclockwise around their front side.
+pub fn write_ply<V: Vector3>(
+    mut writer: impl Write,
+    mesh: &TriangleMesh3D<V>,
+    normals: Option<&[V]>,
+    format: PlyFormat,
+) -> Result<(), MeshFormatError> {
+    check_triangle_mesh(mesh, normals)?;
+    let format_name = match format {
+        PlyFormat::Ascii => "ascii",
+        PlyFormat::BinaryLittleEndian => "binary_little_endian",
+        PlyFormat::BinaryBigEndian => "binary_big_endian",
+    };
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format {format_name} 1.0")?;
+    writeln!(writer, "comment Written by tessa4d")?;
+    writeln!(writer, "element vertex {}", mesh.vertices.len())?;
+    for axis in ["x", "y", "z"] {
+        writeln!(writer, "property float {axis}")?;
+    }
+    if normals.is_some() {
+        for axis in ["nx", "ny", "nz"] {
+            writeln!(writer, "property float {axis}")?;
+        }
+    }
+    writeln!(writer, "element face {}", mesh.simplexes.len())?;
+    writeln!(writer, "property list uchar uint vertex_indices")?;
+    writeln!(writer, "end_header")?;
+
+    let normals = normals.unwrap_or_default();
+    for (i, vertex) in mesh.vertices.iter().enumerate() {
+        let p = vertex.position;
+        let mut values = vec![p.x(), p.y(), p.z()];
+        if let Some(n) = normals.get(i) {
+            values.extend([n.x(), n.y(), n.z()]);
+        }
+        match format {
+            PlyFormat::Ascii => {
+                let line: Vec<_> = values.iter().map(f32::to_string).collect();
+                writeln!(writer, "{}", line.join(" "))?;
+            }
+            PlyFormat::BinaryLittleEndian => {
+                for value in values {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+            }
+            PlyFormat::BinaryBigEndian => {
+                for value in values {
+                    writer.write_all(&value.to_be_bytes())?;
+                }
+            }
+        }
+    }
+    for triangle in &mesh.simplexes {
+        match format {
+            PlyFormat::Ascii => {
+                let [a, b, c] = triangle;
+                writeln!(writer, "3 {a} {b} {c}")?;
+            }
+            PlyFormat::BinaryLittleEndian | PlyFormat::BinaryBigEndian => {
+                writer.write_all(&[3])?;
+                for &index in triangle {
+                    let index =
+                        u32::try_from(index).map_err(|_| MeshFormatError::TooLarge(index))?;
+                    let bytes = match format {
+                        PlyFormat::BinaryBigEndian => index.to_be_bytes(),
+                        _ => index.to_le_bytes(),
+                    };
+                    writer.write_all(&bytes)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the vertex positions and faces of a PLY file, in any of its encodings.
+/// Other elements and properties, e.g. normals and colors, are skipped.
+/// Faces with more than 3 vertices are split into triangles, they can be concave but mustn't cross themselves.
+/// Vertices at exactly the same position are merged into one.
+pub fn read_ply<V: Vector3>(reader: impl BufRead) -> Result<TriangleMesh3D<V>, MeshFormatError> {
+    let mut lines = Lines::new(reader);
+    let (format, elements) = read_header(&mut lines)?;
+    for element in elements.iter().filter(|element| element.name == "vertex") {
+        for axis in ["x", "y", "z"] {
+            let is_coordinate =
+                |property: &PlyProperty| property.name == axis && property.list_count.is_none();
+            if !element.properties.iter().any(is_coordinate) {
+                return Err(parse_error(
+                    element.line,
+                    format!("vertex element has no {axis} property"),
+                ));
+            }
+        }
+    }
+    let mut values = match format {
+        PlyFormat::Ascii => PlyValues::Ascii {
+            lines,
+            line: 0,
+            tokens: vec![],
+        },
+        _ => PlyValues::Binary {
+            reader: lines.reader,
+            big_endian: format == PlyFormat::BinaryBigEndian,
+        },
+    };
+
+    let mut mesh = TriangleMesh3D::default();
+    let mut faces = vec![];
+    // Exporters like Blender split vertices along UV seams and sharp edges, welding them back closes the surface up again.
+    let mut welded = HashMap::new();
+    let mut vertex_indices = vec![];
+    for element in &elements {
+        let axis_properties = ["x", "y", "z"].map(|axis| {
+            element
+                .properties
+                .iter()
+                .position(|property| property.name == axis)
+        });
+        for _ in 0..element.count {
+            values.start_item()?;
+            let mut position = [0.0; 3];
+            for (i, property) in element.properties.iter().enumerate() {
+                match property.list_count {
+                    None => {
+                        let value = values.read(property.item_type)?;
+                        if let Some(axis) = axis_properties.iter().position(|&p| p == Some(i)) {
+                            position[axis] = value as f32;
+                        }
+                    }
+                    Some(count_type) => {
+                        let count = values.read(count_type)? as usize;
+                        let indices = (0..count)
+                            // Negative indices become out of range rather than saturating to 0, so triangulate_faces rejects them.
+                            .map(|_| match values.read(property.item_type)? {
+                                index if index < 0.0 => Ok(usize::MAX),
+                                index => Ok(index as usize),
+                            })
+                            .collect::<Result<Vec<_>, MeshFormatError>>()?;
+                        let is_face_indices = element.name == "face"
+                            && matches!(&property.name[..], "vertex_indices" | "vertex_index");
+                        if is_face_indices {
+                            faces.push(indices);
+                        }
+                    }
+                }
+            }
+            if element.name == "vertex" {
+                // Adding 0 turns -0 into 0, so the two weld together.
+                let key = position.map(|x| (x + 0.0).to_bits());
+                let index = *welded.entry(key).or_insert_with(|| {
+                    let [x, y, z] = position;
+                    mesh.vertices.push(Vertex3 {
+                        position: V::new(x, y, z),
+                    });
+                    mesh.vertices.len() - 1
+                });
+                vertex_indices.push(index);
+            }
+        }
+    }
+    for index in faces.iter_mut().flatten() {
+        if let Some(&welded) = vertex_indices.get(*index) {
+            *index = welded;
+        }
+    }
+    mesh.simplexes = triangulate_faces(&mesh.vertices, &faces)?;
+    Ok(mesh)
+}
+
+/// Types of the numbers in a PLY file.
+#[derive(Clone, Copy, Debug)]
+enum PlyScalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl PlyScalar {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+
+    /// Decodes a value of this type, `bytes` must be [PlyScalar::size] long.
+    fn decode(self, bytes: &[u8], big_endian: bool) -> f64 {
+        let mut array = [0; 8];
+        array[..bytes.len()].copy_from_slice(bytes);
+        if big_endian {
+            array[..bytes.len()].reverse();
+        }
+        match self {
+            Self::I8 => i8::from_le_bytes([array[0]]).into(),
+            Self::U8 => array[0].into(),
+            Self::I16 => i16::from_le_bytes([array[0], array[1]]).into(),
+            Self::U16 => u16::from_le_bytes([array[0], array[1]]).into(),
+            Self::I32 => i32::from_le_bytes([array[0], array[1], array[2], array[3]]).into(),
+            Self::U32 => u32::from_le_bytes([array[0], array[1], array[2], array[3]]).into(),
+            Self::F32 => f32::from_le_bytes([array[0], array[1], array[2], array[3]]).into(),
+            Self::F64 => f64::from_le_bytes(array),
+        }
+    }
+}
+
+struct PlyProperty {
+    name: String,
+    /// Type of the length prefix, for list properties.
+    list_count: Option<PlyScalar>,
+    /// Type of the value, or of each item for list properties.
+    item_type: PlyScalar,
+}
+
+struct PlyElement {
+    name: String,
+    /// Line of the header that declares the element.
+    line: usize,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+fn read_header<R: BufRead>(
+    lines: &mut Lines<R>,
+) -> Result<(PlyFormat, Vec<PlyElement>), MeshFormatError> {
+    let (_, magic) = lines.next_line("header")?;
+    if magic != ["ply"] {
+        return Err(MeshFormatError::Header {
+            expected: "PLY",
+            found: magic.join(" "),
+        });
+    }
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = vec![];
+    loop {
+        let (line, tokens) = lines.next_line("end_header")?;
+        let scalar = |name: &str| {
+            PlyScalar::from_name(name)
+                .ok_or_else(|| parse_error(line, format!("unknown property type {name:?}")))
+        };
+        match tokens[..] {
+            ["end_header"] => break,
+            ["comment", ..] | ["obj_info", ..] => {}
+            ["format", name, _version] => {
+                format = Some(match name {
+                    "ascii" => PlyFormat::Ascii,
+                    "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                    "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                    _ => return Err(parse_error(line, format!("unknown format {name:?}"))),
+                })
+            }
+            ["element", name, count] => elements.push(PlyElement {
+                name: name.to_string(),
+                line,
+                count: parse(line, count)?,
+                properties: vec![],
+            }),
+            ["property", "list", count_type, item_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| parse_error(line, "property before any element"))?
+                .properties
+                .push(PlyProperty {
+                    name: name.to_string(),
+                    list_count: Some(scalar(count_type)?),
+                    item_type: scalar(item_type)?,
+                }),
+            ["property", item_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| parse_error(line, "property before any element"))?
+                .properties
+                .push(PlyProperty {
+                    name: name.to_string(),
+                    list_count: None,
+                    item_type: scalar(item_type)?,
+                }),
+            _ => return Err(parse_error(line, "unrecognized header line")),
+        }
+    }
+    let format = format.ok_or(MeshFormatError::UnexpectedEof("PLY format line"))?;
+    Ok((format, elements))
+}
+
+/// Source of the numbers in the body of a PLY file.
+enum PlyValues<R> {
+    /// Each element is on its own line.
+    Ascii {
+        lines: Lines<R>,
+        line: usize,
+        /// Remaining tokens on the current line, in reverse order.
+        tokens: Vec<String>,
+    },
+    Binary {
+        reader: R,
+        big_endian: bool,
+    },
+}
+
+impl<R: BufRead> PlyValues<R> {
+    /// Moves on to the next element.
+    fn start_item(&mut self) -> Result<(), MeshFormatError> {
+        if let Self::Ascii {
+            lines,
+            line,
+            tokens,
+        } = self
+        {
+            let (next_line, next_tokens) = lines.next_line("PLY element")?;
+            *line = next_line;
+            *tokens = next_tokens.into_iter().rev().map(str::to_string).collect();
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, scalar: PlyScalar) -> Result<f64, MeshFormatError> {
+        match self {
+            Self::Ascii { line, tokens, .. } => {
+                let token = tokens
+                    .pop()
+                    .ok_or_else(|| parse_error(*line, "not enough values for the element"))?;
+                parse(*line, &token)
+            }
+            Self::Binary { reader, big_endian } => {
+                let mut bytes = [0; 8];
+                let bytes = &mut bytes[..scalar.size()];
+                reader.read_exact(bytes).map_err(|err| match err.kind() {
+                    std::io::ErrorKind::UnexpectedEof => {
+                        MeshFormatError::UnexpectedEof("PLY element")
+                    }
+                    _ => err.into(),
+                })?;
+                Ok(scalar.decode(bytes, *big_endian))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec3;
+
+    use super::*;
+    use crate::mesh::{ops::Tetrahedralize, TriangleMesh, Vertex3};
+
+    fn triangle() -> TriangleMesh3D<Vec3> {
+        TriangleMesh {
+            vertices: [Vec3::ZERO, Vec3::X, Vec3::Y]
+                .map(|position| Vertex3 { position })
+                .to_vec(),
+            simplexes: vec![[0, 1, 2]],
+        }
+    }
+
+    /// Splits the file after the header, which is always text.
+    fn split_header(bytes: &[u8]) -> (String, &[u8]) {
+        let end = b"end_header\n";
+        let body_start = bytes
+            .windows(end.len())
+            .position(|window| window == end)
+            .unwrap()
+            + end.len();
+        (
+            String::from_utf8(bytes[..body_start].to_vec()).unwrap(),
+            &bytes[body_start..],
+        )
+    }
+
+    #[test]
+    fn writes_ascii() {
+        let mut bytes = vec![];
+
+        write_ply(&mut bytes, &triangle(), None, PlyFormat::Ascii).unwrap();
+
+        let (header, body) = split_header(&bytes);
+        assert!(header.starts_with("ply\nformat ascii 1.0\n"));
+        assert!(header.contains("element vertex 3\n"));
+        assert!(!header.contains("property float nx"));
+        assert_eq!(body, b"0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n");
+    }
+
+    #[test]
+    fn round_trip() {
+        let cube = TriangleMesh3D::<Vec3>::cube(2.0);
+        for format in [
+            PlyFormat::Ascii,
+            PlyFormat::BinaryLittleEndian,
+            PlyFormat::BinaryBigEndian,
+        ] {
+            let mut bytes = vec![];
+
+            write_ply(&mut bytes, &cube, Some(&[Vec3::X; 8]), format).unwrap();
+            let got: TriangleMesh3D<Vec3> = read_ply(bytes.as_slice()).unwrap();
+
+            assert_eq!(got.simplexes, cube.simplexes);
+            for (got, expected) in got.vertices.iter().zip(&cube.vertices) {
+                assert_eq!(got.position, expected.position);
+            }
+        }
+    }
+
+    #[test]
+    fn reads_big_endian_with_extra_elements() {
+        let mut bytes = b"ply
+format binary_big_endian 1.0
+element vertex 4
+property double x
+property uchar red
+property double y
+property double z
+element face 1
+property list uchar int vertex_index
+element material 1
+property float shininess
+end_header
+"
+        .to_vec();
+        for [x, y] in [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]] {
+            bytes.extend(f64::to_be_bytes(x));
+            bytes.push(255);
+            bytes.extend(f64::to_be_bytes(y));
+            bytes.extend(f64::to_be_bytes(2.0));
+        }
+        bytes.push(4);
+        for i in 0..4 {
+            bytes.extend(i32::to_be_bytes(i));
+        }
+        bytes.extend(0.5f32.to_be_bytes());
+
+        let got: TriangleMesh3D<Vec3> = read_ply(bytes.as_slice()).unwrap();
+
+        assert_eq!(got.vertices[2].position, Vec3::new(1.0, 1.0, 2.0));
+        assert_eq!(got.simplexes, vec![[3, 0, 1], [1, 2, 3]]);
+    }
+
+    #[test]
+    fn rejects_short_ascii_element() {
+        let text = "ply
+format ascii 1.0
+element vertex 1
+property float x
+property float y
+property float z
+end_header
+0 0
+";
+
+        let got = dbg!(read_ply::<Vec3>(text.as_bytes()));
+
+        assert!(matches!(got, Err(MeshFormatError::Parse { line: 8, .. })));
+    }
+
+    #[test]
+    fn rejects_vertex_without_z() {
+        let text = "ply
+format ascii 1.0
+element vertex 1
+property float x
+property float y
+end_header
+0 0
+";
+
+        let got = dbg!(read_ply::<Vec3>(text.as_bytes()));
+
+        assert!(matches!(got, Err(MeshFormatError::Parse { line: 3, .. })));
+    }
+
+    #[test]
+    fn reads_concave_faces() {
+        let text = "ply
+format ascii 1.0
+element vertex 4
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+2 1 0
+0 2 0
+1 1 0
+4 0 1 2 3
+";
+
+        let got: TriangleMesh3D<Vec3> = read_ply(text.as_bytes()).unwrap();
+
+        assert_eq!(got.simplexes.len(), 2);
+        assert!(got.simplexes.iter().all(|triangle| triangle.contains(&3)));
+    }
+
+    #[test]
+    fn welds_split_vertices() {
+        // A cube with its own 4 vertices on each side, like Blender writes one with flat shading.
+        let mut text = String::from(
+            "ply
+format ascii 1.0
+element vertex 24
+property float x
+property float y
+property float z
+element face 6
+property list uchar int vertex_indices
+end_header
+",
+        );
+        let corner = |axis: usize, side: f32, [u, v]: [f32; 2]| {
+            let mut position = [0.0; 3];
+            position[axis] = side;
+            position[(axis + 1) % 3] = u;
+            position[(axis + 2) % 3] = v;
+            position
+        };
+        for axis in 0..3 {
+            for side in [-1.0, 1.0] {
+                // Counterclockwise seen from outside, so the far side goes round the other way.
+                let mut square = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+                if side < 0.0 {
+                    square.reverse();
+                }
+                for uv in square {
+                    let [x, y, z] = corner(axis, side, uv);
+                    text.push_str(&format!("{x} {y} {z}\n"));
+                }
+            }
+        }
+        for face in 0..6 {
+            let [a, b, c, d] = [0, 1, 2, 3].map(|i| face * 4 + i);
+            text.push_str(&format!("4 {a} {b} {c} {d}\n"));
+        }
+
+        let got: TriangleMesh3D<Vec3> = read_ply(text.as_bytes()).unwrap();
+
+        assert_eq!(got.vertices.len(), 8);
+        assert_eq!(got.simplexes.len(), 12);
+        let tetrahedra = got.tetrahedralize().unwrap();
+        assert!((tetrahedra.volume() - 8.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn writes_binary_with_normals() {
+        let mut bytes = vec![];
+
+        write_ply(
+            &mut bytes,
+            &triangle(),
+            Some(&[Vec3::Z; 3]),
+            PlyFormat::BinaryLittleEndian,
+        )
+        .unwrap();
+
+        let (header, body) = split_header(&bytes);
+        assert!(header.contains("format binary_little_endian 1.0\n"));
+        assert!(header.contains("property float nz\n"));
+        // 3 vertices of 6 floats, then a count byte and 3 indices.
+        assert_eq!(body.len(), 3 * 6 * 4 + 1 + 3 * 4);
+        let float = |i: usize| f32::from_le_bytes(body[i * 4..i * 4 + 4].try_into().unwrap());
+        assert_eq!(
+            (0..6).map(float).collect::<Vec<_>>(),
+            [0.0, 0.0, 0.0, 0.0, 0.0, 1.0]
+        );
+        assert_eq!(body[72], 3);
+        assert_eq!(&body[body.len() - 4..], &2u32.to_le_bytes());
+    }
+}
@@ -0,0 +1,83 @@
+//! Writer for binary STL, the usual input format for 3D printing slicers.
+use std::io::Write;
+
+use super::{check_triangle_mesh, face_normal, MeshFormatError};
+use crate::{linear_algebra::Vector3, mesh::TriangleMesh3D};
+
+/// Writes `mesh` as a binary STL file.
+/// STL only stores one normal per triangle, so these are always computed from the winding of each triangle, facing the side it is counterclockwise around.
+pub fn write_stl<V: Vector3>(
+    mut writer: impl Write,
+    mesh: &TriangleMesh3D<V>,
+) -> Result<(), MeshFormatError> {
+    check_triangle_mesh(mesh, None)?;
+    let mut header = [0; 80];
+    let title = b"Binary STL written by tessa4d";
+    header[..title.len()].copy_from_slice(title);
+    writer.write_all(&header)?;
+    let count = mesh.simplexes.len();
+    let count = u32::try_from(count).map_err(|_| MeshFormatError::TooLarge(count))?;
+    writer.write_all(&count.to_le_bytes())?;
+    for &triangle in &mesh.simplexes {
+        let normal = face_normal(mesh, triangle);
+        let corners = triangle.map(|i| mesh.vertices[i].position);
+        for v in [normal].into_iter().chain(corners) {
+            for value in [v.x(), v.y(), v.z()] {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        // Attribute byte count, unused.
+        writer.write_all(&[0; 2])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec3;
+
+    use super::*;
+    use crate::mesh::TriangleMesh3D;
+
+    fn read_vec3(bytes: &[u8]) -> Vec3 {
+        let float = |i: usize| f32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap());
+        Vec3::new(float(0), float(1), float(2))
+    }
+
+    #[test]
+    fn writes_triangles_with_outward_normals() {
+        // The cube's triangles are clockwise seen from outside, flip them to face outwards.
+        let mut cube = TriangleMesh3D::<Vec3>::cube(2.0);
+        cube.invert();
+        let mut bytes = vec![];
+
+        write_stl(&mut bytes, &cube).unwrap();
+
+        assert_eq!(bytes.len(), 84 + 12 * 50);
+        assert_eq!(u32::from_le_bytes(bytes[80..84].try_into().unwrap()), 12);
+        for record in bytes[84..].chunks(50) {
+            let normal = read_vec3(&record[0..12]);
+            let corners = [1, 2, 3].map(|i| read_vec3(&record[i * 12..i * 12 + 12]));
+            let center = (corners[0] + corners[1] + corners[2]) / 3.0;
+            assert!(dbg!(normal).is_normalized());
+            assert!(normal.dot(center) > 0.0);
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_index() {
+        let mut cube = TriangleMesh3D::<Vec3>::cube(1.0);
+        cube.simplexes[0][0] = 8;
+
+        let got = dbg!(write_stl(vec![], &cube));
+
+        assert!(matches!(
+            got,
+            Err(MeshFormatError::IndexOutOfRange {
+                simplex: 0,
+                index: 8,
+                ..
+            })
+        ));
+    }
+}
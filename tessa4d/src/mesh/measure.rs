@@ -0,0 +1,280 @@
+//! Geometric measurements of meshes, e.g. volume, surface area, centroids, and bounding boxes.
+use super::{SimplexMesh, TetrahedronMesh, TetrahedronMesh4D, TriangleMesh, Vertex};
+use crate::{
+    linear_algebra::{Vector, Vector4},
+    util::determinant,
+};
+
+/// Axis-aligned bounding box, the smallest box with sides parallel to the axes that contains every vertex of a mesh.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb<V> {
+    /// Corner of the box with the lowest value on each axis.
+    pub min: V,
+    /// Corner of the box with the highest value on each axis.
+    pub max: V,
+}
+
+impl<V: Vector> Aabb<V> {
+    /// Center point of the box.
+    pub fn center(&self) -> V {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Side lengths of the box along each axis.
+    pub fn size(&self) -> V {
+        self.max + self.min * -1.0
+    }
+}
+
+impl<V: Vertex, const N: usize> SimplexMesh<V, N> {
+    /// Average position of the simplexes in the mesh weighted by their size, e.g. the center of mass of a solid tetrahedron mesh with uniform density.
+    /// Returns `None` if the mesh has no size, e.g. if it is empty.
+    pub fn centroid(&self) -> Option<V::Position> {
+        let (total, weighted_sum) = self.simplexes.iter().fold(
+            (0.0, V::Position::ZERO),
+            |(total, weighted_sum), simplex| {
+                let positions = simplex.map(|i| self.vertices[i].position());
+                let size = simplex_size(positions);
+                let center = positions
+                    .into_iter()
+                    .fold(V::Position::ZERO, |sum, position| sum + position)
+                    * (1.0 / N as f32);
+                (total + size, weighted_sum + center * size)
+            },
+        );
+        (total > 0.0).then(|| weighted_sum * total.recip())
+    }
+
+    /// Axis-aligned bounding box of all the vertices in the mesh, or `None` if there are no vertices.
+    pub fn bounds(&self) -> Option<Aabb<V::Position>> {
+        let first = self.vertices.first()?.position();
+        Some(self.vertices.iter().fold(
+            Aabb {
+                min: first,
+                max: first,
+            },
+            |aabb, vertex| Aabb {
+                min: aabb.min.min_elementwise(vertex.position()),
+                max: aabb.max.max_elementwise(vertex.position()),
+            },
+        ))
+    }
+
+    /// Sum of the sizes of all the simplexes in the mesh.
+    fn total_size(&self) -> f32 {
+        self.simplexes
+            .iter()
+            .map(|simplex| simplex_size(simplex.map(|i| self.vertices[i].position())))
+            .sum()
+    }
+}
+
+impl<V: Vertex> TriangleMesh<V> {
+    /// Total area of all the triangles in the mesh. Works in any number of dimensions, e.g. for the surface area of a 3D shell.
+    pub fn area(&self) -> f32 {
+        self.total_size()
+    }
+}
+
+impl<V: Vertex> TetrahedronMesh<V> {
+    /// Total volume of all the tetrahedra in the mesh. Works in any number of dimensions,
+    /// e.g. for the volume of a solid 3D mesh or the surface volume of a 4D shell.
+    pub fn volume(&self) -> f32 {
+        self.total_size()
+    }
+}
+
+impl<V: Vector4> TetrahedronMesh4D<V> {
+    /// 4D volume enclosed by this mesh, assuming it is a closed shell like [TetrahedronMesh4D::tesseract].
+    /// Negative if the shell is inside-out.
+    pub fn hypervolume(&self) -> f32 {
+        self.simplexes
+            .iter()
+            .map(|simplex| cone_hypervolume(simplex.map(|i| self.vertices[i].position)))
+            .sum()
+    }
+
+    /// Center of mass of the 4D volume enclosed by this mesh with uniform density, assuming it is a closed shell like [TetrahedronMesh4D::tesseract].
+    /// Returns `None` if the shell doesn't enclose any volume.
+    pub fn hypervolume_centroid(&self) -> Option<V> {
+        let (total, weighted_sum) =
+            self.simplexes
+                .iter()
+                .fold((0.0, V::ZERO), |(total, weighted_sum), simplex| {
+                    let positions = simplex.map(|i| self.vertices[i].position);
+                    let hypervolume = cone_hypervolume(positions);
+                    // Centroid of the 4-simplex made from the tetrahedron and the origin.
+                    let center = positions
+                        .into_iter()
+                        .fold(V::ZERO, |sum, position| sum + position)
+                        * 0.2;
+                    (total + hypervolume, weighted_sum + center * hypervolume)
+                });
+        (total.abs() > 0.0).then(|| weighted_sum * total.recip())
+    }
+}
+
+/// Size of a simplex with `N` vertices in any number of dimensions, e.g. length for `N = 2`, area for `N = 3`, volume for `N = 4`.
+/// Calculated from the determinant of the Gram matrix of the edges from the first vertex, which only needs dot products.
+fn simplex_size<V: Vector, const N: usize>(positions: [V; N]) -> f32 {
+    if N < 2 {
+        return 0.0;
+    }
+    let edges = positions.map(|position| position + positions[0] * -1.0);
+    // Gram matrix of edges[1..], padded out to NxN with an identity row and column which doesn't change the determinant.
+    let gram: [[f32; N]; N] = std::array::from_fn(|i| {
+        std::array::from_fn(|j| match (i + 1 < N, j + 1 < N) {
+            (true, true) => edges[i + 1].dot(edges[j + 1]),
+            (false, false) => 1.0,
+            _ => 0.0,
+        })
+    });
+    let factorial: f32 = (1..N).map(|i| i as f32).product();
+    determinant(gram).max(0.0).sqrt() / factorial
+}
+
+/// Signed 4D volume of the cone from the origin to a tetrahedron, positive for tetrahedra facing away from the origin.
+fn cone_hypervolume<V: Vector4>(positions: [V; 4]) -> f32 {
+    let matrix = positions.map(|p| [p.x(), p.y(), p.z(), p.w()]);
+    -determinant(matrix) / 24.0
+}
+
+#[cfg(test)]
+mod test {
+    use glam::{Vec2, Vec3, Vec4};
+    use proptest::proptest;
+
+    use super::*;
+    use crate::mesh::{TetrahedronMesh3D, TriangleMesh2D, TriangleMesh3D, Vertex2};
+    use crate::transform::{
+        rotate_scale_translate4::RotateScaleTranslate4, rotor4::test_util::arbitrary_rotor4,
+    };
+    use crate::util::{approx_equal, test::proptest::vec4_uniform};
+
+    const EPS: f32 = 1e-3;
+
+    #[test]
+    fn determinant_matches_known_value() {
+        let matrix = [
+            [2.0, 0.0, 1.0, 0.0],
+            [1.0, 3.0, 0.0, 0.0],
+            [0.0, 1.0, 4.0, 1.0],
+            [0.0, 0.0, 2.0, 5.0],
+        ];
+
+        let got = determinant(matrix);
+
+        assert!(approx_equal(got, 113.0, EPS));
+    }
+
+    #[test]
+    fn square_area() {
+        let got = TriangleMesh2D::<Vec2>::square(2.0).area();
+
+        assert!(approx_equal(got, 4.0, EPS));
+    }
+
+    #[test]
+    fn cube_shell_area() {
+        let got = TriangleMesh3D::<Vec3>::cube(2.0).area();
+
+        assert!(approx_equal(got, 24.0, EPS));
+    }
+
+    #[test]
+    fn cube_volume() {
+        let got = TetrahedronMesh3D::<Vec3>::rectangular_prism(Vec3::new(1.0, 2.0, 3.0)).volume();
+
+        assert!(approx_equal(got, 6.0, EPS));
+    }
+
+    #[test]
+    fn tesseract_surface_volume() {
+        let got = TetrahedronMesh4D::<Vec4>::tesseract(Vec4::new(1.0, 2.0, 3.0, 4.0)).volume();
+
+        // Two cells perpendicular to each axis, with the volume of the other three side lengths.
+        let expected =
+            2.0 * (2.0 * 3.0 * 4.0 + 1.0 * 3.0 * 4.0 + 1.0 * 2.0 * 4.0 + 1.0 * 2.0 * 3.0);
+        assert!(approx_equal(got, expected, EPS));
+    }
+
+    #[test]
+    fn tesseract_hypervolume() {
+        let got = TetrahedronMesh4D::<Vec4>::tesseract(Vec4::new(1.0, 2.0, 3.0, 4.0)).hypervolume();
+
+        assert!(approx_equal(got, 24.0, EPS));
+    }
+
+    #[test]
+    fn inverted_tesseract_negative_hypervolume() {
+        let got = TetrahedronMesh4D::<Vec4>::tesseract_cube(2.0)
+            .invert()
+            .hypervolume();
+
+        assert!(approx_equal(got, -16.0, EPS));
+    }
+
+    #[test]
+    fn centroid_of_empty_mesh_is_none() {
+        let mesh = TriangleMesh2D::<Vec2> {
+            vertices: vec![],
+            simplexes: vec![],
+        };
+
+        assert!(mesh.centroid().is_none());
+        assert!(mesh.bounds().is_none());
+    }
+
+    #[test]
+    fn centroid_weighted_by_area() {
+        let mut mesh = TriangleMesh2D::<Vec2>::square(2.0);
+        mesh.join(TriangleMesh2D::<Vec2> {
+            vertices: [
+                Vec2::new(3.0, 0.0),
+                Vec2::new(3.0, 0.1),
+                Vec2::new(3.1, 0.0),
+            ]
+            .map(|position| Vertex2 { position })
+            .to_vec(),
+            simplexes: vec![[0, 1, 2]],
+        });
+
+        let got = mesh.centroid().unwrap();
+
+        // Small triangle barely moves the centroid off the square's center.
+        assert!(got.x > 0.0 && got.x < 0.01);
+    }
+
+    #[test]
+    fn bounds_rectangular_prism() {
+        let got = TriangleMesh3D::<Vec3>::rectangular_prism(Vec3::new(1.0, 2.0, 3.0))
+            .bounds()
+            .unwrap();
+
+        assert!(got.min.abs_diff_eq(Vec3::new(-0.5, -1.0, -1.5), EPS));
+        assert!(got.max.abs_diff_eq(Vec3::new(0.5, 1.0, 1.5), EPS));
+        assert!(got.size().abs_diff_eq(Vec3::new(1.0, 2.0, 3.0), EPS));
+        assert!(got.center().abs_diff_eq(Vec3::ZERO, EPS));
+    }
+
+    proptest! {
+        #[test]
+        fn transformed_tesseract_measures(rotor in arbitrary_rotor4(), translation in vec4_uniform(4.0)) {
+            let transform = RotateScaleTranslate4 {
+                rotation: rotor,
+                scale: 2.0,
+                translation,
+            };
+            let mut mesh = TetrahedronMesh4D::<Vec4>::tesseract_cube(1.0);
+            mesh.apply_transform(&transform);
+
+            assert!(approx_equal(mesh.hypervolume(), 16.0, EPS));
+            assert!(approx_equal(mesh.volume(), 64.0, EPS));
+            assert!(mesh.centroid().unwrap().abs_diff_eq(translation, EPS));
+            assert!(mesh.hypervolume_centroid().unwrap().abs_diff_eq(translation, EPS));
+            let bounds = mesh.bounds().unwrap();
+            assert!(bounds.center().abs_diff_eq(translation, EPS));
+        }
+    }
+}
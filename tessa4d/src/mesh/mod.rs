@@ -1,15 +1,34 @@
+pub mod io;
+pub mod measure;
 pub mod ops;
+pub mod parametric;
+pub mod polygon;
+pub mod soa;
+
+#[cfg(feature = "serde")]
+mod serde_simplexes;
 
 use std::f32::consts::TAU;
 
-use self::ops::{Extrude, LiftOrthographic};
+use self::{
+    ops::{CrossSection, Extrude, LiftOrthographic},
+    parametric::{ParameterGrid, Seam},
+};
 use crate::{
-    linear_algebra::{Vector2, Vector3, Vector4},
+    linear_algebra::{Vector, Vector2, Vector3, Vector4},
     transform::traits::{InterpolateWith, Transform},
     util::lerp,
 };
 
+/// Common interface for vertex types, for operations that only need the position of each vertex.
+pub trait Vertex: Copy {
+    type Position: Vector;
+    /// Position of the vertex.
+    fn position(&self) -> Self::Position;
+}
+
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertex2<V: Vector2> {
     pub position: V,
 }
@@ -20,7 +39,15 @@ impl<V: Vector2> Default for Vertex2<V> {
     }
 }
 
+impl<V: Vector2> Vertex for Vertex2<V> {
+    type Position = V;
+    fn position(&self) -> V {
+        self.position
+    }
+}
+
 impl<V: Vector2> InterpolateWith for Vertex2<V> {
+    type Scalar = f32;
     fn interpolate_with(&self, other: &Self, fraction: f32) -> Self {
         Self {
             position: lerp(self.position, other.position, fraction),
@@ -34,10 +61,19 @@ impl<V: Vector2, T: Transform<V>> Transform<Vertex2<V>> for T {
             position: self.transform(operand.position),
         }
     }
+
+    fn transform_each<'a, I>(&self, operands: I)
+    where
+        I: IntoIterator<Item = &'a mut Vertex2<V>>,
+        V: 'a,
+    {
+        Transform::<V>::transform_each(self, operands.into_iter().map(|v| &mut v.position));
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "bevy", derive(bevy::reflect::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertex3<V: Vector3> {
     pub position: V,
 }
@@ -48,7 +84,15 @@ impl<V: Vector3> Default for Vertex3<V> {
     }
 }
 
+impl<V: Vector3> Vertex for Vertex3<V> {
+    type Position = V;
+    fn position(&self) -> V {
+        self.position
+    }
+}
+
 impl<V: Vector3> InterpolateWith for Vertex3<V> {
+    type Scalar = f32;
     fn interpolate_with(&self, other: &Self, fraction: f32) -> Self {
         Self {
             position: lerp(self.position, other.position, fraction),
@@ -62,10 +106,19 @@ impl<V: Vector3, T: Transform<V>> Transform<Vertex3<V>> for T {
             position: self.transform(operand.position),
         }
     }
+
+    fn transform_each<'a, I>(&self, operands: I)
+    where
+        I: IntoIterator<Item = &'a mut Vertex3<V>>,
+        V: 'a,
+    {
+        Transform::<V>::transform_each(self, operands.into_iter().map(|v| &mut v.position));
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "bevy", derive(bevy::reflect::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertex4<V: Vector4> {
     pub position: V,
 }
@@ -76,7 +129,15 @@ impl<V: Vector4> Default for Vertex4<V> {
     }
 }
 
+impl<V: Vector4> Vertex for Vertex4<V> {
+    type Position = V;
+    fn position(&self) -> V {
+        self.position
+    }
+}
+
 impl<V: Vector4> InterpolateWith for Vertex4<V> {
+    type Scalar = f32;
     fn interpolate_with(&self, other: &Self, fraction: f32) -> Self {
         Self {
             position: lerp(self.position, other.position, fraction),
@@ -90,15 +151,25 @@ impl<V: Vector4, T: Transform<V>> Transform<Vertex4<V>> for T {
             position: self.transform(operand.position),
         }
     }
+
+    fn transform_each<'a, I>(&self, operands: I)
+    where
+        I: IntoIterator<Item = &'a mut Vertex4<V>>,
+        V: 'a,
+    {
+        Transform::<V>::transform_each(self, operands.into_iter().map(|v| &mut v.position));
+    }
 }
 
 /// Generic mesh made of N-simplexes. e.g. a 3-simplex is a triangle, a 4-simplex is a tetrahedron.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SimplexMesh<V, const N: usize> {
     /// Unique vertices in the mesh.
     /// Uniqueness is not required, but it is more efficient.
     pub vertices: Vec<V>,
     /// Indices into the `vertices` vec representing the vertices of each N-simplex in the mesh.
+    #[cfg_attr(feature = "serde", serde(with = "serde_simplexes"))]
     pub simplexes: Vec<[usize; N]>,
 }
 
@@ -111,12 +182,34 @@ pub type TriangleMesh4D<V> = TriangleMesh<Vertex4<V>>;
 pub type TetrahedronMesh3D<V> = TetrahedronMesh<Vertex3<V>>;
 pub type TetrahedronMesh4D<V> = TetrahedronMesh<Vertex4<V>>;
 
+/// Empty mesh, e.g. as an output buffer for [CrossSectionSlicer](ops::CrossSectionSlicer).
+impl<V, const N: usize> Default for SimplexMesh<V, N> {
+    fn default() -> Self {
+        Self {
+            vertices: vec![],
+            simplexes: vec![],
+        }
+    }
+}
+
 impl<V: Copy, const N: usize> SimplexMesh<V, N> {
     /// Applies a transform to all verticies in the mesh in place.
     pub fn apply_transform<T: Transform<V>>(&mut self, transform: &T) -> &mut Self {
-        self.vertices.iter_mut().for_each(|p| {
-            *p = transform.transform(*p);
-        });
+        transform.transform_each(&mut self.vertices);
+        self
+    }
+
+    /// Same as [SimplexMesh::apply_transform], but transforms chunks of vertices in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn par_apply_transform<T: Transform<V> + Sync>(&mut self, transform: &T) -> &mut Self
+    where
+        V: Send,
+    {
+        use rayon::prelude::*;
+        const CHUNK_SIZE: usize = 4096;
+        self.vertices
+            .par_chunks_mut(CHUNK_SIZE)
+            .for_each(|chunk| transform.transform_each(chunk));
         self
     }
 
@@ -167,6 +260,7 @@ impl<V: Vector2> TriangleMesh2D<V> {
     }
 
     /// Makes a circle or regular polygon with the given `radius`, centered at the origin, with the number of sides given in `sides`.
+    /// For other polygons see [Self::polygon].
     pub fn circle(radius: f32, sides: usize) -> Self {
         Self {
             vertices: (0..sides)
@@ -228,6 +322,35 @@ impl<V: Vector3> TriangleMesh3D<V> {
     pub fn cube(size: f32) -> Self {
         Self::rectangular_prism(V::new(size, size, size))
     }
+
+    /// Makes the surface where the 4D signed distance function `sdf` is zero, within a 3D slice of 4D space.
+    /// `hyperplane` maps points in the slice, `(x, y, z, 0)`, into the space of `sdf`.
+    /// `sdf` is sampled on a [TetrahedronMesh3D::grid] in the slice, with side lengths from `size` and `cells` boxes along each axis.
+    /// Triangles face towards positive values of `sdf`.
+    pub fn from_sdf4d_cross_section<F, T>(
+        sdf: F,
+        hyperplane: &T,
+        size: V,
+        cells: [usize; 3],
+    ) -> Self
+    where
+        F: Fn(V::Vector4) -> f32,
+        T: Transform<V::Vector4>,
+    {
+        TetrahedronMesh4D::<V::Vector4>::graph(
+            |position: V| {
+                sdf(hyperplane.transform(V::Vector4::new(
+                    position.x(),
+                    position.y(),
+                    position.z(),
+                    0.0,
+                )))
+            },
+            size,
+            cells,
+        )
+        .cross_section()
+    }
 }
 
 impl<V: Vector3> TetrahedronMesh3D<V> {
@@ -240,26 +363,99 @@ impl<V: Vector3> TetrahedronMesh3D<V> {
     pub fn cube(size: f32) -> Self {
         Self::rectangular_prism(V::new(size, size, size))
     }
+
+    /// Makes a solid rectangular prism with side lengths from `size`, centered at the origin, divided into a grid of `cells` boxes along each axis.
+    /// Unlike [Self::rectangular_prism], neighboring tetrahedra always share whole faces, so cross-sections don't have any cracks.
+    pub fn grid(size: V, cells: [usize; 3]) -> Self {
+        let cells = cells.map(|n| n.max(1));
+        let [verts_x, verts_y, verts_z] = cells.map(|n| n + 1);
+        let corner = size * -0.5;
+        let vertices = (0..verts_z)
+            .flat_map(|k| (0..verts_y).flat_map(move |j| (0..verts_x).map(move |i| [i, j, k])))
+            .map(|[i, j, k]| Vertex3 {
+                position: V::new(
+                    corner.x() + size.x() * (i as f32 / cells[0] as f32),
+                    corner.y() + size.y() * (j as f32 / cells[1] as f32),
+                    corner.z() + size.z() * (k as f32 / cells[2] as f32),
+                ),
+            })
+            .collect();
+        let simplexes = ParameterGrid::new(cells, [Seam::Open; 3]).simplexes();
+        Self {
+            vertices,
+            simplexes,
+        }
+    }
 }
 
 impl<V: Vector4> TetrahedronMesh4D<V> {
     /// Makes the shell of a rectangular tesseract, with side lengths from `size` and centered at the origin.
+    /// The 8 cubic cells share the 16 corners of the tesseract.
     pub fn tesseract(size: V) -> Self {
-        let v3_size = V::Vector3::new(size.x(), size.y(), size.z());
-        let mut tesseract = TriangleMesh::rectangular_prism(v3_size).extrude(size.w());
-        let endcap = TetrahedronMesh::rectangular_prism(v3_size);
-        let w_comp = size.w() / 2.0;
-        let mut top_cap = endcap.lift_orthographic(w_comp);
-        top_cap.invert();
-        let bottom_cap = endcap.lift_orthographic(-w_comp);
-        tesseract.join(top_cap).join(bottom_cap);
-        tesseract
+        let size = [size.x(), size.y(), size.z(), size.w()];
+        // Bit `axis` of a corner's index is set when it is on the positive side of that axis.
+        let vertices = (0..16)
+            .map(|corner: usize| {
+                let [x, y, z, w] = std::array::from_fn(|axis| {
+                    let side = if corner >> axis & 1 == 1 { 0.5 } else { -0.5 };
+                    side * size[axis]
+                });
+                Vertex4 {
+                    position: V::new(x, y, z, w),
+                }
+            })
+            .collect();
+        let cube = ParameterGrid::new([1, 1, 1], [Seam::Open; 3]).simplexes();
+        let mut simplexes = vec![];
+        // One cubic cell on each side of each axis.
+        for axis in 0..4 {
+            for side in 0..2 {
+                let others: Vec<usize> = (0..4).filter(|&other| other != axis).collect();
+                // Cube corners are numbered with x fastest, which keeps the remaining axes in order, so each square face's lowest corner
+                // is the same in the cube and in the tesseract. Neighboring cells then split their shared faces along the same diagonal.
+                let to_corner = |local: usize| {
+                    (0..3).fold(side << axis, |corner, bit| {
+                        corner | (local >> bit & 1) << others[bit]
+                    })
+                };
+                for tetrahedron in &cube {
+                    let mut tetrahedron = tetrahedron.map(to_corner);
+                    // Parameter space is right-handed, flip the cells where the remaining axes and the outward direction are not.
+                    if (side == 1) == (axis % 2 == 0) {
+                        tetrahedron.swap(0, 1);
+                    }
+                    simplexes.push(tetrahedron);
+                }
+            }
+        }
+        Self {
+            vertices,
+            simplexes,
+        }
     }
 
     /// Makes the shell of a tesseract with identical side lengths of `size`, centered at the origin.
     pub fn tesseract_cube(size: f32) -> Self {
         Self::tesseract(V::new(size, size, size, size))
     }
+
+    /// Makes the graph of the function `w = f(x, y, z)`, a 3D surface in 4D space.
+    /// `f` is sampled on a [TetrahedronMesh3D::grid] centered at the origin, with side lengths from `size` and `cells` boxes along each axis.
+    /// Cross-sections of the graph are the isosurfaces of `f`, with triangles facing towards larger values of `f`.
+    pub fn graph<F: Fn(V::Vector3) -> f32>(f: F, size: V::Vector3, cells: [usize; 3]) -> Self {
+        let grid = TetrahedronMesh3D::grid(size, cells);
+        let mut graph = Self {
+            vertices: grid
+                .vertices
+                .iter()
+                .map(|vertex| vertex.lift_orthographic(f(vertex.position)))
+                .collect(),
+            simplexes: grid.simplexes,
+        };
+        // Right-handed grid tetrahedra give cross-sections facing towards smaller values, flip them.
+        graph.invert();
+        graph
+    }
 }
 
 #[cfg(test)]
@@ -283,6 +479,22 @@ pub(crate) mod test_util {
             .signum()
     }
 
+    /// Counts how many tetrahedra share each face of the mesh, faces are given as sorted vertex indices.
+    pub fn face_counts(simplexes: &[[usize; 4]]) -> HashMap<[usize; 3], usize> {
+        let mut faces = HashMap::new();
+        for simplex in simplexes {
+            for skip in 0..4 {
+                let mut face = [0; 3];
+                for (slot, i) in (0..4).filter(|&i| i != skip).enumerate() {
+                    face[slot] = simplex[i];
+                }
+                face.sort();
+                *faces.entry(face).or_insert(0) += 1;
+            }
+        }
+        faces
+    }
+
     /// Returns true if the mesh is a closed surface, without holes or a boundary, e.g. cube.
     /// Only works when there are no duplicated vertices, no overlapping edges with different endpoints, generally does not work after a cross-section.
     pub fn triangle_mesh_closed<V>(mesh: &TriangleMesh<V>) -> bool {
@@ -351,8 +563,9 @@ mod test {
     use crate::transform::rotate_scale_translate4::RotateScaleTranslate4;
     use crate::transform::rotor4::test_util::arbitrary_rotor4;
     use crate::transform::rotor4::{Bivec4, Rotor4};
+    use crate::util::approx_equal;
     use crate::util::test::proptest::vec3_uniform;
-    use glam::{vec2, Vec3};
+    use glam::{vec2, Vec3, Vec4};
     use proptest::proptest;
 
     proptest! {
@@ -370,6 +583,22 @@ mod test {
             assert!(intersect_count  == 2);
         }
 
+        #[test]
+        fn tesseract_cross_section_faces_outward(rotor in arbitrary_rotor4()) {
+            let mut mesh = TetrahedronMesh4D::<glam::Vec4>::tesseract(Vec4::new(1.0, 2.0, 3.0, 4.0));
+            let transform = RotateScaleTranslate4 {
+                rotation: rotor,
+                ..RotateScaleTranslate4::IDENTITY
+            };
+
+            let got = mesh.apply_transform(&transform).cross_section();
+
+            for simplex in got.simplexes.iter() {
+                let [a, b, c] = simplex.map(|i| got.vertices[i].position);
+                assert!((b - a).cross(c - a).dot(a + b + c) >= 0.0);
+            }
+        }
+
         #[test]
         fn cube_trimesh_closed_line_intersect(dir in vec3_uniform(1.0)) {
             let mesh = TriangleMesh3D::<Vec3>::cube(1.0);
@@ -385,6 +614,17 @@ mod test {
         dbg!(TetrahedronMesh4D::<glam::Vec4>::tesseract_cube(2.0));
     }
 
+    #[test]
+    fn tesseract_cells_share_corners_and_faces() {
+        let mesh = TetrahedronMesh4D::<glam::Vec4>::tesseract(Vec4::new(1.0, 2.0, 3.0, 4.0));
+
+        let faces = face_counts(&mesh.simplexes);
+
+        assert_eq!(mesh.vertices.len(), 16);
+        assert_eq!(mesh.simplexes.len(), 8 * 6);
+        assert!(faces.values().all(|count| *count == 2));
+    }
+
     #[test]
     fn tesseract_rotated_xw_cross_section_closed() {
         let mut mesh = TetrahedronMesh4D::<glam::Vec4>::tesseract_cube(1.0);
@@ -407,11 +647,177 @@ mod test {
         assert!(intersect_count == 2);
     }
 
+    #[test]
+    fn grid_tetrahedra_right_handed() {
+        let mesh = TetrahedronMesh3D::<Vec3>::grid(Vec3::new(1.0, 2.0, 3.0), [2, 3, 4]);
+
+        assert_eq!(mesh.vertices.len(), 3 * 4 * 5);
+        assert_eq!(mesh.simplexes.len(), 2 * 3 * 4 * 6);
+        for simplex in mesh.simplexes.iter() {
+            assert_eq!(
+                tetrahedron_sign(simplex.map(|i| mesh.vertices[i].position)),
+                1.0
+            );
+        }
+    }
+
+    #[test]
+    fn grid_faces_shared_by_at_most_two_tetrahedra() {
+        let mesh = TetrahedronMesh3D::<Vec3>::grid(Vec3::ONE, [3, 3, 3]);
+
+        let faces = face_counts(&mesh.simplexes);
+
+        // Every internal face is shared by exactly two tetrahedra, only the 2 * 6 * 9 faces on the boundary are not shared.
+        assert!(faces.values().all(|count| *count <= 2));
+        assert_eq!(
+            faces.values().filter(|count| **count == 1).count(),
+            2 * 6 * 9
+        );
+    }
+
+    #[test]
+    fn sdf4d_cross_section_of_glome_is_closed_sphere() {
+        let glome = |p: Vec4| p.length() - 1.0;
+
+        let got = TriangleMesh3D::<Vec3>::from_sdf4d_cross_section(
+            glome,
+            &RotateScaleTranslate4::IDENTITY,
+            Vec3::ONE * 3.0,
+            [12, 12, 12],
+        );
+
+        assert!(!got.simplexes.is_empty());
+        assert!(triangle_mesh_closed(&got));
+        for vertex in got.vertices.iter() {
+            assert!(approx_equal(vertex.position.length(), 1.0, 0.05));
+        }
+    }
+
+    #[test]
+    fn sdf4d_cross_section_faces_outward() {
+        let glome = |p: Vec4| p.length() - 1.0;
+
+        let got = TriangleMesh3D::<Vec3>::from_sdf4d_cross_section(
+            glome,
+            &RotateScaleTranslate4::IDENTITY,
+            Vec3::ONE * 3.0,
+            [8, 8, 8],
+        );
+
+        for simplex in got.simplexes.iter() {
+            let [a, b, c] = simplex.map(|i| got.vertices[i].position);
+            let normal = (b - a).cross(c - a);
+            assert!(normal.dot(a + b + c) > 0.0);
+        }
+    }
+
+    #[test]
+    fn sdf4d_cross_section_uses_hyperplane() {
+        let glome = |p: Vec4| p.length() - 1.0;
+        let hyperplane = RotateScaleTranslate4::IDENTITY.translated(Vec4::new(0.0, 0.0, 0.0, 0.6));
+
+        let got = TriangleMesh3D::<Vec3>::from_sdf4d_cross_section(
+            glome,
+            &hyperplane,
+            Vec3::ONE * 3.0,
+            [12, 12, 12],
+        );
+
+        assert!(!got.vertices.is_empty());
+        for vertex in got.vertices.iter() {
+            assert!(approx_equal(vertex.position.length(), 0.8, 0.05));
+        }
+    }
+
+    #[test]
+    fn graph_samples_function() {
+        let f = |p: Vec3| p.x + 2.0 * p.y - p.z;
+
+        let got = TetrahedronMesh4D::<Vec4>::graph(f, Vec3::ONE, [2, 2, 2]);
+
+        assert_eq!(got.vertices.len(), 27);
+        for vertex in got.vertices.iter() {
+            let position = vertex.position;
+            assert!(approx_equal(position.w, f(position.truncate()), 1e-5));
+        }
+    }
+
+    #[test]
+    fn graph_cross_section_is_isosurface() {
+        let f = |p: Vec3| p.z;
+        let mut graph = TetrahedronMesh4D::<Vec4>::graph(f, Vec3::ONE, [3, 3, 3]);
+        let transform = RotateScaleTranslate4::IDENTITY.translated(Vec4::new(0.0, 0.0, 0.0, -0.25));
+
+        let got = graph.apply_transform(&transform).cross_section();
+
+        assert!(!got.simplexes.is_empty());
+        for vertex in got.vertices.iter() {
+            assert!(approx_equal(vertex.position.z, 0.25, 1e-5));
+        }
+        for simplex in got.simplexes.iter() {
+            let [a, b, c] = simplex.map(|i| got.vertices[i].position);
+            assert!((b - a).cross(c - a).z > 0.0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_transform_and_cross_section_match_single_threaded() {
+        // Big enough to be split into several chunks.
+        let f = |p: Vec3| p.length() - 0.3;
+        let mesh = TetrahedronMesh4D::<Vec4>::graph(f, Vec3::ONE, [16, 16, 16]);
+        assert!(mesh.simplexes.len() > 4 * 4096);
+        let transform = RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xw: 0.3,
+                yz: 1.0,
+                ..Bivec4::ZERO
+            }),
+            scale: 1.5,
+            translation: Vec4::new(0.1, 0.0, 0.0, 0.2),
+        };
+        let mut expected = mesh.clone();
+        let mut got = mesh;
+
+        let expected = expected.apply_transform(&transform).cross_section();
+        let got = got.par_apply_transform(&transform).par_cross_section();
+
+        assert_eq!(got.simplexes, expected.simplexes);
+        let positions = |mesh: &TriangleMesh3D<Vec3>| {
+            mesh.vertices.iter().map(|v| v.position).collect::<Vec<_>>()
+        };
+        assert_eq!(positions(&got), positions(&expected));
+    }
+
     #[test]
     fn cube_trimesh_closed() {
         assert!(triangle_mesh_closed(&TriangleMesh3D::<Vec3>::cube(1.0)))
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn tetmesh_serde_round_trip() {
+        let mesh = TetrahedronMesh4D::<Vec4>::tesseract_cube(1.0);
+
+        let json = serde_json::to_string(&mesh).unwrap();
+        let got: TetrahedronMesh4D<Vec4> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(got.simplexes, mesh.simplexes);
+        for (got, expected) in got.vertices.iter().zip(&mesh.vertices) {
+            assert_eq!(got.position, expected.position);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_rejects_wrong_simplex_size() {
+        let json = r#"{"vertices": [{"position": [0.0, 0.0]}], "simplexes": [[0, 0, 0, 0]]}"#;
+
+        let got = serde_json::from_str::<TriangleMesh2D<glam::Vec2>>(json);
+
+        assert!(dbg!(got).is_err());
+    }
+
     #[test]
     fn simplexmesh_join() {
         let mut mesh1 = TriangleMesh2D {
@@ -1,6 +1,10 @@
 use super::ProjectOrthographic;
-use crate::mesh::{TetrahedronMesh, TriangleMesh};
-use crate::transform::traits::InterpolateWith;
+use crate::linear_algebra::{Vector3, Vector4};
+use crate::mesh::{
+    soa::SoaTetrahedronMesh4D, TetrahedronMesh, TriangleMesh, TriangleMesh3D, Vertex3,
+};
+use crate::transform::affine4::Affine4;
+use crate::transform::traits::{InterpolateWith, Transform};
 use std::collections::{hash_map::Entry, HashMap};
 
 /// For a tetrahedron with verts (0,1,2,3), gives the clockwise winding order of each face, assuming (0,1,2) is clockwise facing out from vertex 3.
@@ -10,7 +14,7 @@ const TETRAHEDRON_FACE_WINDING: [[usize; 3]; 4] = [[1, 3, 2], [0, 2, 3], [0, 3,
 const CROSS_SECTION_DEPTH: f32 = 0.0;
 fn project_edge<V: ProjectOrthographic>(vertex1: V, vertex2: V) -> V::Projected
 where
-    V::Projected: InterpolateWith,
+    V::Projected: InterpolateWith<Scalar = f32>,
 {
     let depth1 = vertex1.orthographic_depth();
     let depth2 = vertex2.orthographic_depth();
@@ -29,84 +33,376 @@ pub trait CrossSection {
 
 impl<V: ProjectOrthographic + Copy> CrossSection for TetrahedronMesh<V>
 where
-    V::Projected: InterpolateWith,
+    V::Projected: InterpolateWith<Scalar = f32>,
 {
     type CrossSectioned = TriangleMesh<V::Projected>;
     fn cross_section(&self) -> TriangleMesh<V::Projected> {
-        // Maps edges in the old mesh to projected vertices in the new mesh, takes the edge as a tuple with the lower index first.
-        let mut edge_indices: HashMap<(usize, usize), usize> = HashMap::new();
-        let mut projected_vertices: Vec<V::Projected> = vec![];
-        // Returns the index of the intersection point in the new mesh for the edge between the given vertices in the old mesh.
-        let mut get_intersection = |i: usize, j: usize| {
-            let key = (i.min(j), i.max(j));
-            match edge_indices.entry(key) {
-                Entry::Occupied(projected_index) => *projected_index.get(),
-                Entry::Vacant(slot) => {
-                    let vertex1 = self.vertices[i];
-                    let vertex2 = self.vertices[j];
-                    let projected_vertex = project_edge(vertex1, vertex2);
-                    projected_vertices.push(projected_vertex);
-                    let index = projected_vertices.len() - 1;
-                    slot.insert(index);
-                    index
-                }
-            }
-        };
-        let projected_simplexes = self
+        let SectionChunk { edges, triangles } = section_chunk(
+            |i| self.vertices[i].orthographic_depth() > CROSS_SECTION_DEPTH,
+            &self.simplexes,
+        );
+        TriangleMesh {
+            vertices: edges
+                .into_iter()
+                .map(|(i, j)| project_edge(self.vertices[i], self.vertices[j]))
+                .collect(),
+            simplexes: triangles,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<V: ProjectOrthographic + Copy + Sync> TetrahedronMesh<V>
+where
+    V::Projected: InterpolateWith<Scalar = f32> + Send,
+{
+    /// Same as [CrossSection::cross_section], but sections chunks of tetrahedra in parallel.
+    /// Gives exactly the same mesh as the single-threaded version, whatever the number of threads.
+    pub fn par_cross_section(&self) -> TriangleMesh<V::Projected> {
+        use rayon::prelude::*;
+        let chunks: Vec<_> = self
             .simplexes
+            .par_chunks(PARALLEL_CHUNK_SIZE)
+            .map(|simplexes| {
+                section_chunk(
+                    |i| self.vertices[i].orthographic_depth() > CROSS_SECTION_DEPTH,
+                    simplexes,
+                )
+            })
+            .collect();
+        let (edges, simplexes) = merge_chunks(chunks);
+        TriangleMesh {
+            vertices: edges
+                .into_par_iter()
+                .map(|(i, j)| project_edge(self.vertices[i], self.vertices[j]))
+                .collect(),
+            simplexes,
+        }
+    }
+}
+
+/// Gives the same mesh as [CrossSection::cross_section] on the equivalent [TetrahedronMesh].
+/// Works out the edges of the mesh on every call, use a [SoaCrossSectionSlicer] to cross-section the same mesh repeatedly.
+impl<V: Vector4> CrossSection for SoaTetrahedronMesh4D<V> {
+    type CrossSectioned = TriangleMesh3D<V::Vector3>;
+    fn cross_section(&self) -> TriangleMesh3D<V::Vector3> {
+        SoaCrossSectionSlicer::new(self).cross_section(&Affine4::IDENTITY)
+    }
+}
+
+/// Number of tetrahedra each thread sections at a time.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 4096;
+
+/// Cross-sections the same tetrahedron mesh over and over, e.g. every frame as it moves.
+///
+/// Works out the unique edges of the mesh once, so each cross-section only transforms the vertices and checks which edges cross the hyperplane,
+/// without hashing or cloning the mesh. Gives the same result as transforming the mesh and calling [CrossSection::cross_section].
+#[derive(Clone, Debug)]
+pub struct CrossSectionSlicer<V> {
+    vertices: Vec<V>,
+    edges: SlicerEdges,
+    /// Scratch buffers reused between cross-sections.
+    transformed: Vec<V>,
+    crossing: Vec<(usize, usize)>,
+}
+
+impl<V: ProjectOrthographic + Copy> CrossSectionSlicer<V>
+where
+    V::Projected: InterpolateWith<Scalar = f32>,
+{
+    /// Prepares `mesh` for cross-sectioning.
+    pub fn new(mesh: &TetrahedronMesh<V>) -> Self {
+        Self {
+            vertices: mesh.vertices.clone(),
+            edges: SlicerEdges::new(&mesh.simplexes),
+            transformed: Vec::with_capacity(mesh.vertices.len()),
+            crossing: vec![],
+        }
+    }
+
+    /// Cross-section of the mesh after applying `transform`, written into `output`.
+    /// Reuses the buffers in `output` and in the slicer, so repeated calls don't allocate once they've grown large enough.
+    pub fn cross_section_into<T: Transform<V>>(
+        &mut self,
+        transform: &T,
+        output: &mut TriangleMesh<V::Projected>,
+    ) {
+        self.transformed.clear();
+        self.transformed.extend_from_slice(&self.vertices);
+        transform.transform_each(&mut self.transformed);
+        let transformed = &self.transformed;
+        self.edges.section(
+            |i| transformed[i].orthographic_depth() > CROSS_SECTION_DEPTH,
+            &mut self.crossing,
+            &mut output.simplexes,
+        );
+        output.vertices.clear();
+        output.vertices.extend(
+            self.crossing
+                .iter()
+                .map(|&(i, j)| project_edge(transformed[i], transformed[j])),
+        );
+    }
+
+    /// Like [CrossSectionSlicer::cross_section_into], but returns a new mesh.
+    pub fn cross_section<T: Transform<V>>(&mut self, transform: &T) -> TriangleMesh<V::Projected> {
+        let mut output = TriangleMesh::default();
+        self.cross_section_into(transform, &mut output);
+        output
+    }
+}
+
+/// [CrossSectionSlicer] for a [SoaTetrahedronMesh4D], which transforms, classifies and interpolates over whole arrays of components.
+#[derive(Clone, Debug)]
+pub struct SoaCrossSectionSlicer<V> {
+    vertices: SoaTetrahedronMesh4D<V>,
+    edges: SlicerEdges,
+    /// Scratch buffers reused between cross-sections.
+    transformed: SoaTetrahedronMesh4D<V>,
+    positive_side: Vec<bool>,
+    crossing: Vec<(usize, usize)>,
+    components: [Vec<f32>; 3],
+}
+
+impl<V: Vector4> SoaCrossSectionSlicer<V> {
+    /// Prepares `mesh` for cross-sectioning.
+    pub fn new(mesh: &SoaTetrahedronMesh4D<V>) -> Self {
+        let mut vertices = SoaTetrahedronMesh4D::default();
+        for position in mesh.positions() {
+            vertices.push_vertex(position);
+        }
+        Self {
+            vertices,
+            edges: SlicerEdges::new(&mesh.simplexes),
+            transformed: SoaTetrahedronMesh4D::default(),
+            positive_side: vec![],
+            crossing: vec![],
+            components: [vec![], vec![], vec![]],
+        }
+    }
+
+    /// Cross-section of the mesh after applying `transform`, written into `output`.
+    /// Reuses the buffers in `output` and in the slicer, so repeated calls don't allocate once they've grown large enough.
+    /// Called like [CrossSectionSlicer::cross_section_into], but the transform must convert to an [Affine4].
+    pub fn cross_section_into<T: Copy + Into<Affine4<V>>>(
+        &mut self,
+        transform: &T,
+        output: &mut TriangleMesh3D<V::Vector3>,
+    ) {
+        let transformed = &mut self.transformed;
+        transformed.x.clone_from(&self.vertices.x);
+        transformed.y.clone_from(&self.vertices.y);
+        transformed.z.clone_from(&self.vertices.z);
+        transformed.w.clone_from(&self.vertices.w);
+        transformed.apply_transform(transform);
+        transformed.classify_depths(CROSS_SECTION_DEPTH, &mut self.positive_side);
+        let positive_side = &self.positive_side;
+        self.edges.section(
+            |i| positive_side[i],
+            &mut self.crossing,
+            &mut output.simplexes,
+        );
+        for component in self.components.iter_mut() {
+            component.clear();
+        }
+        transformed.interpolate_edges(&self.crossing, &mut self.components);
+        let [x, y, z] = &self.components;
+        output.vertices.clear();
+        output
+            .vertices
+            .extend(x.iter().zip(y).zip(z).map(|((&x, &y), &z)| Vertex3 {
+                position: V::Vector3::new(x, y, z),
+            }));
+    }
+
+    /// Like [SoaCrossSectionSlicer::cross_section_into], but returns a new mesh.
+    pub fn cross_section<T: Copy + Into<Affine4<V>>>(
+        &mut self,
+        transform: &T,
+    ) -> TriangleMesh3D<V::Vector3> {
+        let mut output = TriangleMesh::default();
+        self.cross_section_into(transform, &mut output);
+        output
+    }
+}
+
+/// Unique edges of a tetrahedron mesh and the ones each tetrahedron uses, shared by the slicers.
+#[derive(Clone, Debug)]
+struct SlicerEdges {
+    /// Unique edges of the mesh, lower index first.
+    edges: Vec<(usize, usize)>,
+    /// Vertices of each tetrahedron, and the indices into `edges` for each pair of them, ordered like [TETRAHEDRON_EDGES].
+    tetrahedra: Vec<([usize; 4], [usize; 6])>,
+    /// Scratch, index of the intersection point for each edge crossed so far.
+    edge_intersections: Vec<Option<usize>>,
+}
+
+/// Every pair of vertices in a tetrahedron, the local edge indices used by [SlicerEdges].
+const TETRAHEDRON_EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+
+/// Index in [TETRAHEDRON_EDGES] of the edge between two different vertices of a tetrahedron.
+fn tetrahedron_edge_index(i: usize, j: usize) -> usize {
+    let (i, j) = (i.min(j), i.max(j));
+    // Edges from vertex i start after the 3 + 2 + ... edges from the vertices before it.
+    i * (5 - i) / 2 + j - 1
+}
+
+impl SlicerEdges {
+    fn new(simplexes: &[[usize; 4]]) -> Self {
+        let mut edge_indices: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut edges = vec![];
+        let tetrahedra = simplexes
             .iter()
-            .flat_map(|simplex| {
-                let vertex_section_side = simplex.map(|vert_index| {
-                    self.vertices[vert_index].orthographic_depth() > CROSS_SECTION_DEPTH
-                });
-                // One vertex on negative side, use face winding order. Takes index of the one negative-depth vertex.
-                let one_negative_case =
-                    |i: usize| vec![TETRAHEDRON_FACE_WINDING[i].map(|j| (i, j))];
-                // One vertex on positive side, use opposite of face winding order. Takes index of the one positive-depth vertex.
-                let three_negative_case = |i: usize| {
-                    let mut winding = TETRAHEDRON_FACE_WINDING[i];
-                    winding.reverse();
-                    vec![winding.map(|j| (i, j))]
-                };
-                // Two vertices on negative side, get a quadrilateral intersection which we map to two triangles.
-                // Pattern comes from drawing things out, enumerating the cases, and reducing.
-                let two_negative_case = |neg1: usize, neg2: usize, pos1: usize, pos2: usize| {
-                    vec![
-                        [(neg1, pos2), (neg1, pos1), (neg2, pos2)],
-                        [(neg1, pos1), (neg2, pos1), (neg2, pos2)],
-                    ]
-                };
-                let faces = match vertex_section_side {
-                    [false, false, false, false] => vec![],
-                    [true, true, true, true] => vec![],
-                    [false, true, true, true] => one_negative_case(0),
-                    [true, false, true, true] => one_negative_case(1),
-                    [true, true, false, true] => one_negative_case(2),
-                    [true, true, true, false] => one_negative_case(3),
-                    [true, false, false, false] => three_negative_case(0),
-                    [false, true, false, false] => three_negative_case(1),
-                    [false, false, true, false] => three_negative_case(2),
-                    [false, false, false, true] => three_negative_case(3),
-                    [false, false, true, true] => two_negative_case(0, 1, 2, 3),
-                    [true, true, false, false] => two_negative_case(3, 2, 1, 0),
-                    [true, false, true, false] => two_negative_case(3, 1, 0, 2),
-                    [false, true, false, true] => two_negative_case(0, 2, 3, 1),
-                    [true, false, false, true] => two_negative_case(2, 1, 3, 0),
-                    [false, true, true, false] => two_negative_case(0, 3, 1, 2),
-                };
-                faces
-                    .into_iter()
-                    .map(|face_edges| {
-                        face_edges.map(|(i, j)| get_intersection(simplex[i], simplex[j]))
+            .map(|&simplex| {
+                let tetrahedron_edges = TETRAHEDRON_EDGES.map(|(i, j)| {
+                    let key = (simplex[i].min(simplex[j]), simplex[i].max(simplex[j]));
+                    *edge_indices.entry(key).or_insert_with(|| {
+                        edges.push(key);
+                        edges.len() - 1
                     })
-                    .collect::<Vec<_>>()
+                });
+                (simplex, tetrahedron_edges)
             })
             .collect();
-        TriangleMesh {
-            vertices: projected_vertices,
-            simplexes: projected_simplexes,
+        Self {
+            edge_intersections: vec![None; edges.len()],
+            edges,
+            tetrahedra,
         }
     }
+
+    /// Writes the triangles where the mesh crosses the hyperplane into `triangles`, as indices into `crossing`,
+    /// which gets the edges that cross in the order they are first used. `positive_side` tells whether the vertex at an index has positive depth.
+    fn section(
+        &mut self,
+        positive_side: impl Fn(usize) -> bool,
+        crossing: &mut Vec<(usize, usize)>,
+        triangles: &mut Vec<[usize; 3]>,
+    ) {
+        self.edge_intersections.fill(None);
+        crossing.clear();
+        triangles.clear();
+        for (simplex, tetrahedron_edges) in &self.tetrahedra {
+            let (faces, face_count) = section_faces(simplex.map(&positive_side));
+            for face_edges in &faces[..face_count] {
+                triangles.push(face_edges.map(|(i, j)| {
+                    let edge = tetrahedron_edges[tetrahedron_edge_index(i, j)];
+                    *self.edge_intersections[edge].get_or_insert_with(|| {
+                        crossing.push(self.edges[edge]);
+                        crossing.len() - 1
+                    })
+                }));
+            }
+        }
+    }
+}
+
+/// Cross-section of a run of tetrahedra, before its intersection points are shared with the rest of the mesh.
+struct SectionChunk {
+    /// Edges of the source mesh that cross the hyperplane, lower index first, in the order they are first used.
+    edges: Vec<(usize, usize)>,
+    /// Triangles of the cross-section, as indices into `edges`.
+    triangles: Vec<[usize; 3]>,
+}
+
+/// `positive_side` tells whether the vertex at an index has positive depth.
+fn section_chunk(positive_side: impl Fn(usize) -> bool, simplexes: &[[usize; 4]]) -> SectionChunk {
+    // Maps edges in the old mesh to their index in `edges`, takes the edge as a tuple with the lower index first.
+    let mut edge_indices: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut edges = vec![];
+    // Returns the index of the intersection point for the edge between the given vertices in the old mesh.
+    let mut get_intersection = |i: usize, j: usize| {
+        let key = (i.min(j), i.max(j));
+        match edge_indices.entry(key) {
+            Entry::Occupied(edge_index) => *edge_index.get(),
+            Entry::Vacant(slot) => {
+                edges.push(key);
+                *slot.insert(edges.len() - 1)
+            }
+        }
+    };
+    let mut triangles = vec![];
+    for simplex in simplexes {
+        let (faces, face_count) = section_faces(simplex.map(&positive_side));
+        for face_edges in &faces[..face_count] {
+            triangles.push(face_edges.map(|(i, j)| get_intersection(simplex[i], simplex[j])));
+        }
+    }
+    SectionChunk { edges, triangles }
+}
+
+/// Triangles where a tetrahedron crosses the hyperplane, given which of its vertices have positive depth.
+/// Each corner of a triangle is on the edge between a pair of the tetrahedron's vertices (0 to 3).
+/// Returns up to two triangles, and how many of them there are.
+fn section_faces(positive_side: [bool; 4]) -> ([[(usize, usize); 3]; 2], usize) {
+    // One vertex on negative side, use face winding order. Takes index of the one negative-depth vertex.
+    let one_negative_case = |i: usize| ([TETRAHEDRON_FACE_WINDING[i].map(|j| (i, j)); 2], 1);
+    // One vertex on positive side, use opposite of face winding order. Takes index of the one positive-depth vertex.
+    let three_negative_case = |i: usize| {
+        let mut winding = TETRAHEDRON_FACE_WINDING[i];
+        winding.reverse();
+        ([winding.map(|j| (i, j)); 2], 1)
+    };
+    // Two vertices on negative side, get a quadrilateral intersection which we map to two triangles.
+    // Pattern comes from drawing things out, enumerating the cases, and reducing.
+    let two_negative_case = |neg1: usize, neg2: usize, pos1: usize, pos2: usize| {
+        (
+            [
+                [(neg1, pos2), (neg1, pos1), (neg2, pos2)],
+                [(neg1, pos1), (neg2, pos1), (neg2, pos2)],
+            ],
+            2,
+        )
+    };
+    match positive_side {
+        [false, false, false, false] | [true, true, true, true] => ([[(0, 0); 3]; 2], 0),
+        [false, true, true, true] => one_negative_case(0),
+        [true, false, true, true] => one_negative_case(1),
+        [true, true, false, true] => one_negative_case(2),
+        [true, true, true, false] => one_negative_case(3),
+        [true, false, false, false] => three_negative_case(0),
+        [false, true, false, false] => three_negative_case(1),
+        [false, false, true, false] => three_negative_case(2),
+        [false, false, false, true] => three_negative_case(3),
+        [false, false, true, true] => two_negative_case(0, 1, 2, 3),
+        [true, true, false, false] => two_negative_case(3, 2, 1, 0),
+        [true, false, true, false] => two_negative_case(3, 1, 0, 2),
+        [false, true, false, true] => two_negative_case(0, 2, 3, 1),
+        [true, false, false, true] => two_negative_case(2, 1, 3, 0),
+        [false, true, true, false] => two_negative_case(0, 3, 1, 2),
+    }
+}
+
+/// Joins chunks in order, sharing the intersection points of edges that appear in more than one chunk.
+/// Returns each unique edge in the order it is first used, and the triangles as indices into those edges.
+/// Edges come out in the same order however the tetrahedra were split into chunks, so the result is deterministic.
+#[cfg(feature = "rayon")]
+fn merge_chunks(
+    chunks: impl IntoIterator<Item = SectionChunk>,
+) -> (Vec<(usize, usize)>, Vec<[usize; 3]>) {
+    let mut edge_indices: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut edges = vec![];
+    let mut triangles = vec![];
+    for chunk in chunks {
+        let merged_indices: Vec<usize> = chunk
+            .edges
+            .into_iter()
+            .map(|edge| {
+                *edge_indices.entry(edge).or_insert_with(|| {
+                    edges.push(edge);
+                    edges.len() - 1
+                })
+            })
+            .collect();
+        triangles.extend(
+            chunk
+                .triangles
+                .into_iter()
+                .map(|triangle| triangle.map(|i| merged_indices[i])),
+        );
+    }
+    (edges, triangles)
 }
 
 #[cfg(test)]
@@ -114,7 +410,9 @@ mod test {
     use proptest::prelude::*;
 
     use crate::mesh::test_util::*;
-    use crate::mesh::{Vertex2, Vertex3};
+    use crate::mesh::{TetrahedronMesh4D, Vertex2, Vertex3};
+    use crate::transform::rotate_scale_translate4::RotateScaleTranslate4;
+    use crate::transform::rotor4::{Bivec4, Rotor4};
     use crate::util::test::proptest::vec3_uniform;
 
     use super::*;
@@ -275,6 +573,119 @@ mod test {
         }
     }
 
+    #[test]
+    fn tetrahedron_edge_index_matches_edges() {
+        for (index, (i, j)) in TETRAHEDRON_EDGES.into_iter().enumerate() {
+            assert_eq!(tetrahedron_edge_index(i, j), index);
+            assert_eq!(tetrahedron_edge_index(j, i), index);
+        }
+    }
+
+    #[test]
+    fn slicer_matches_cross_section_after_transform() {
+        let mesh = TetrahedronMesh4D::<glam::Vec4>::graph(
+            |p| p.length() - 0.3,
+            glam::Vec3::ONE,
+            [4, 4, 4],
+        );
+        let mut slicer = CrossSectionSlicer::new(&mesh);
+        let mut output = TriangleMesh::default();
+
+        for angle in [0.0, 0.4, 1.3] {
+            let transform = RotateScaleTranslate4 {
+                rotation: Rotor4::from_bivec_angles(Bivec4 {
+                    xw: angle,
+                    ..Bivec4::ZERO
+                }),
+                scale: 1.0,
+                translation: glam::vec4(0.0, 0.0, 0.0, 0.1),
+            };
+            let expected = mesh.clone().apply_transform(&transform).cross_section();
+
+            slicer.cross_section_into(&transform, &mut output);
+
+            assert!(!output.simplexes.is_empty());
+            assert_eq!(output.simplexes, expected.simplexes);
+            let positions = |mesh: &TriangleMesh<Vertex3<glam::Vec3>>| {
+                mesh.vertices.iter().map(|v| v.position).collect::<Vec<_>>()
+            };
+            assert_eq!(positions(&output), positions(&expected));
+        }
+    }
+
+    #[test]
+    fn soa_cross_section_matches_cross_section() {
+        let mesh = TetrahedronMesh4D::<glam::Vec4>::graph(
+            |p| p.length() - 0.3,
+            glam::Vec3::ONE,
+            [6, 6, 6],
+        );
+        let soa = SoaTetrahedronMesh4D::from(&mesh);
+
+        let expected = mesh.cross_section();
+        let got = soa.cross_section();
+
+        assert!(!got.simplexes.is_empty());
+        assert_eq!(got.simplexes, expected.simplexes);
+        assert_eq!(got.vertices.len(), expected.vertices.len());
+        for (got, expected) in got.vertices.iter().zip(&expected.vertices) {
+            assert!(got.position.abs_diff_eq(expected.position, 1e-6));
+        }
+    }
+
+    #[test]
+    fn soa_slicer_matches_slicer_after_transform() {
+        let mesh = TetrahedronMesh4D::<glam::Vec4>::graph(
+            |p| p.length() - 0.3,
+            glam::Vec3::ONE,
+            [4, 4, 4],
+        );
+        let mut slicer = CrossSectionSlicer::new(&mesh);
+        let mut soa_slicer = SoaCrossSectionSlicer::new(&SoaTetrahedronMesh4D::from(&mesh));
+        let mut output = TriangleMesh::default();
+
+        for angle in [0.0, 0.4, 1.3] {
+            let transform = RotateScaleTranslate4 {
+                rotation: Rotor4::from_bivec_angles(Bivec4 {
+                    wy: angle,
+                    ..Bivec4::ZERO
+                }),
+                scale: 1.0,
+                translation: glam::vec4(0.0, 0.0, 0.0, -0.1),
+            };
+            let expected = slicer.cross_section(&transform);
+
+            soa_slicer.cross_section_into(&transform, &mut output);
+
+            assert!(!output.simplexes.is_empty());
+            assert_eq!(output.simplexes, expected.simplexes);
+            assert_eq!(output.vertices.len(), expected.vertices.len());
+            for (got, expected) in output.vertices.iter().zip(&expected.vertices) {
+                assert!(got.position.abs_diff_eq(expected.position, 1e-5));
+            }
+        }
+    }
+
+    #[test]
+    fn slicer_reuses_output_buffers() {
+        let mesh = TetrahedronMesh4D::<glam::Vec4>::tesseract_cube(1.0);
+        let transform = Rotor4::from_bivec_angles(Bivec4 {
+            xw: 0.5,
+            yz: 0.2,
+            ..Bivec4::ZERO
+        });
+        let mut slicer = CrossSectionSlicer::new(&mesh);
+        let mut output = slicer.cross_section(&transform);
+        let buffers = (output.vertices.as_ptr(), output.simplexes.as_ptr());
+
+        slicer.cross_section_into(&transform, &mut output);
+
+        assert_eq!(
+            (output.vertices.as_ptr(), output.simplexes.as_ptr()),
+            buffers
+        );
+    }
+
     fn make_vertex_3d(x: f32, y: f32, z: f32) -> Vertex3<glam::Vec3> {
         Vertex3 {
             position: glam::vec3(x, y, z),
@@ -27,18 +27,7 @@ impl<V: LiftOrthographic> Extrude for TriangleMesh<V> {
         let simplexes = self
             .simplexes
             .into_iter()
-            .flat_map(|face| {
-                [
-                    [face[0], face[2], face[1], face[0] + num_verts],
-                    [face[2], face[1], face[0] + num_verts, face[2] + num_verts],
-                    [
-                        face[0] + num_verts,
-                        face[1] + num_verts,
-                        face[2] + num_verts,
-                        face[1],
-                    ],
-                ]
-            })
+            .flat_map(|face| split_prism(face, face.map(|i| i + num_verts)))
             .collect();
 
         TetrahedronMesh {
@@ -48,6 +37,17 @@ impl<V: LiftOrthographic> Extrude for TriangleMesh<V> {
     }
 }
 
+/// Splits a triangular prism into three tetrahedra, `top` and `bottom` give the indices of the triangles at either end of the prism.
+/// Corresponding vertices of `top` and `bottom` are joined by the prism's edges.
+/// If `top` is counter-clockwise when viewed from the side away from `bottom`, the tetrahedra are right-handed.
+pub(crate) fn split_prism(top: [usize; 3], bottom: [usize; 3]) -> [[usize; 4]; 3] {
+    [
+        [top[0], top[2], top[1], bottom[0]],
+        [top[2], top[1], bottom[0], bottom[2]],
+        [bottom[0], bottom[1], bottom[2], top[1]],
+    ]
+}
+
 #[cfg(test)]
 mod test {
     use glam::{vec3, Affine3A, Quat, Vec2, Vec3};
@@ -1,10 +1,13 @@
 mod cross_section;
 mod extrude;
 mod project;
+mod tetrahedralize;
 
-pub use cross_section::CrossSection;
+pub use cross_section::{CrossSection, CrossSectionSlicer, SoaCrossSectionSlicer};
+pub(crate) use extrude::split_prism;
 pub use extrude::Extrude;
 pub use project::{LiftOrthographic, ProjectOrthographic};
+pub use tetrahedralize::{Tetrahedralize, TetrahedralizeError};
 
 // TODO more ops:
 // * Shell: Reduce outer edge of mesh, one rank lower. Cube tet to cube trimesh.
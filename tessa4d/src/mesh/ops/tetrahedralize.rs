@@ -0,0 +1,963 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use thiserror::Error;
+
+use crate::{
+    linear_algebra::Vector3,
+    mesh::{TetrahedronMesh3D, TriangleMesh3D, Vertex3},
+};
+
+pub trait Tetrahedralize {
+    type Tetrahedralized;
+    /// Fills the volume enclosed by a closed surface with simplexes one rank higher,
+    /// e.g. a closed triangle mesh becomes a solid tetrahedron mesh, ready to [Extrude](super::Extrude) or [LiftOrthographic](super::LiftOrthographic) into 4D.
+    fn tetrahedralize(&self) -> Result<Self::Tetrahedralized, TetrahedralizeError>;
+}
+
+/// Problems filling the inside of a surface.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum TetrahedralizeError {
+    #[error("Mesh doesn't enclose any volume")]
+    Empty,
+    #[error("Mesh has a hole, edge {0:?} is only used by one triangle")]
+    NotClosed((usize, usize)),
+    #[error("Triangles on either side of edge {0:?} wind in the same direction")]
+    InconsistentWinding((usize, usize)),
+    #[error("Couldn't fill the mesh, {0} faces were left over, it may intersect itself")]
+    Unfilled(usize),
+}
+
+/// Fills a closed surface with its constrained Delaunay tetrahedralization, built by gift-wrapping inwards from the surface.
+/// Every triangle of the surface is a face of exactly one tetrahedron, and all the tetrahedra are right-handed. The surface can face either way.
+///
+/// Some shapes, e.g. the Schönhardt polyhedron, can't be split into tetrahedra using only their own vertices.
+/// Extra vertices are added inside them where needed, after the existing vertices.
+/// Vertices closer than about a millionth of the size of the mesh to a plane are treated as on it.
+impl<V: Vector3> Tetrahedralize for TriangleMesh3D<V> {
+    type Tetrahedralized = TetrahedronMesh3D<V>;
+    fn tetrahedralize(&self) -> Result<Self::Tetrahedralized, TetrahedralizeError> {
+        check_closed(self)?;
+        let mut wrap = GiftWrap::new(self)?;
+        wrap.fill()?;
+
+        let mut vertices = self.vertices.clone();
+        vertices.extend(
+            wrap.positions[self.vertices.len()..]
+                .iter()
+                .map(|p| Vertex3 {
+                    position: V::new(p[0] as f32, p[1] as f32, p[2] as f32),
+                }),
+        );
+        Ok(TetrahedronMesh3D {
+            vertices,
+            simplexes: wrap.simplexes,
+        })
+    }
+}
+
+/// Checks that every edge is shared by exactly two triangles, which traverse it in opposite directions.
+fn check_closed<V: Vector3>(mesh: &TriangleMesh3D<V>) -> Result<(), TetrahedralizeError> {
+    if mesh.simplexes.is_empty() {
+        return Err(TetrahedralizeError::Empty);
+    }
+    let mut edges = HashSet::new();
+    for &[a, b, c] in &mesh.simplexes {
+        for edge in [(a, b), (b, c), (c, a)] {
+            if !edges.insert(edge) {
+                return Err(TetrahedralizeError::InconsistentWinding(edge));
+            }
+        }
+    }
+    for &(i, j) in &edges {
+        if !edges.contains(&(j, i)) {
+            return Err(TetrahedralizeError::NotClosed((i, j)));
+        }
+    }
+    Ok(())
+}
+
+type Point = [f64; 3];
+
+/// Number of candidate vertices [GiftWrap::best_apex] checks for a face before moving on to other faces.
+const QUICK_TRIES: usize = 16;
+
+/// State for filling a surface one tetrahedron at a time.
+/// Works in double precision, so rounding errors are far below the tolerance for single precision input.
+struct GiftWrap {
+    positions: Vec<Point>,
+    /// Faces between the filled and unfilled parts of the volume, wound counterclockwise seen from the unfilled side.
+    /// Rotated so the smallest index is first, so each face has one representation. Sorted so the output is deterministic.
+    front: BTreeSet<[usize; 3]>,
+    /// Number of front faces using each vertex. Vertices on none of them are surrounded by tetrahedra and can't be used again.
+    front_uses: Vec<usize>,
+    simplexes: Vec<[usize; 4]>,
+    /// Distances smaller than this are treated as zero.
+    eps: f64,
+    /// Largest distance between two vertices along any axis.
+    size: f64,
+    /// Limits the number of extra vertices, so surfaces that intersect themselves can't add them forever.
+    steiner_points_left: usize,
+}
+
+impl GiftWrap {
+    fn new<V: Vector3>(mesh: &TriangleMesh3D<V>) -> Result<Self, TetrahedralizeError> {
+        let positions: Vec<Point> = mesh
+            .vertices
+            .iter()
+            .map(|v| [v.position.x(), v.position.y(), v.position.z()].map(f64::from))
+            .collect();
+        let size = (0..3)
+            .map(|axis| {
+                let coords = || positions.iter().map(|p| p[axis]);
+                coords().fold(f64::MIN, f64::max) - coords().fold(f64::MAX, f64::min)
+            })
+            .fold(0.0, f64::max);
+        let mut wrap = Self {
+            front_uses: vec![0; positions.len()],
+            positions,
+            front: BTreeSet::new(),
+            simplexes: vec![],
+            eps: size * 1e-6,
+            size,
+            steiner_points_left: mesh.simplexes.len(),
+        };
+
+        // Sum over the cones from each triangle to the origin, positive when the triangles are counterclockwise from outside.
+        let volume: f64 = mesh
+            .simplexes
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.map(|i| wrap.positions[i]);
+                dot(a, cross(b, c)) / 6.0
+            })
+            .sum();
+        if volume.abs() <= wrap.eps.powi(3) {
+            return Err(TetrahedralizeError::Empty);
+        }
+        for &[a, b, c] in &mesh.simplexes {
+            wrap.add_front_face(if volume > 0.0 { [b, a, c] } else { [a, b, c] });
+        }
+        Ok(wrap)
+    }
+
+    /// Wraps tetrahedra around the front until it closes up, adding extra vertices when every face is stuck.
+    fn fill(&mut self) -> Result<(), TetrahedralizeError> {
+        // Works outwards from the newest faces, so the filled part grows from one place rather than fronts from all over meeting awkwardly.
+        let mut pending: Vec<_> = self.front.iter().copied().collect();
+        let mut stuck = vec![];
+        // Faces usually join to one of their first few candidates. Only look through all of them when every face is stuck, as it's slow.
+        let mut tries = QUICK_TRIES;
+        loop {
+            let mut progress = false;
+            while let Some(face) = pending.pop() {
+                if !self.front.contains(&face) {
+                    continue;
+                }
+                match self.best_apex(face, tries) {
+                    Some(apex) => {
+                        pending.extend(self.add_tetrahedron(face, apex));
+                        progress = true;
+                    }
+                    None => stuck.push(face),
+                }
+            }
+            if self.front.is_empty() {
+                return Ok(());
+            }
+            if progress {
+                tries = QUICK_TRIES;
+            } else if tries == QUICK_TRIES {
+                tries = usize::MAX;
+            } else {
+                let new_faces = self
+                    .add_steiner_point()
+                    .ok_or(TetrahedralizeError::Unfilled(self.front.len()))?;
+                pending.extend(new_faces);
+                tries = QUICK_TRIES;
+            }
+            // The front has changed since they were stuck, so they may not be any more.
+            pending.append(&mut stuck);
+        }
+    }
+
+    /// Finds the vertex to join to a front face, the one making the smallest circumsphere on the unfilled side of the face
+    /// which doesn't cross the front. Gives up after checking `tries` vertices.
+    fn best_apex(&self, face: [usize; 3], tries: usize) -> Option<usize> {
+        let [a, b, c] = face.map(|i| self.positions[i]);
+        let center = circumcenter(a, b, c);
+        let radius2 = length2(sub(a, center));
+        let mut candidates: Vec<(f64, f64, usize)> = (0..self.positions.len())
+            .filter(|&i| self.front_uses[i] > 0)
+            .filter_map(|i| {
+                let d = self.positions[i];
+                let height = plane_distance(a, b, c, d);
+                // Proportional to how far the center of the sphere through a, b, c and d is in front of the face.
+                (height > self.eps).then(|| (length2(sub(d, center)) - radius2, height, i))
+            })
+            .collect();
+        candidates.sort_by(|x, y| (x.0 / x.1).total_cmp(&(y.0 / y.1)));
+        // Rounding can reorder vertices on the same sphere, which often happens, e.g. on UV spheres and the corners of boxes.
+        // Choosing between them differently from different faces leaves gaps that can't be filled, so they're ordered consistently instead.
+        let tolerance = 4.0 * self.eps * self.size;
+        let mut start = 0;
+        while start < candidates.len() {
+            let mut end = start + 1;
+            while end < candidates.len() && {
+                let [(power, height, _), (next_power, next_height, _)] =
+                    [candidates[end - 1], candidates[end]];
+                (next_power * height - power * next_height).abs()
+                    <= tolerance * (height + next_height)
+            } {
+                end += 1;
+            }
+            if end - start > 1 {
+                self.break_ties(face, &mut candidates[start..end]);
+            }
+            start = end;
+        }
+        candidates
+            .into_iter()
+            .take(tries)
+            .map(|(_, _, i)| i)
+            .find(|&apex| self.can_add(face, apex))
+    }
+
+    /// Orders vertices on the same sphere as a face as if they were moved slightly off it, by less for higher indices.
+    /// That's the same as filling the sphere with tetrahedra joined to its vertex with the lowest index,
+    /// its pulling triangulation, so every face agrees on the choice.
+    fn break_ties(&self, face: [usize; 3], candidates: &mut [(f64, f64, usize)]) {
+        let first = (0..3).min_by_key(|&k| face[k]).unwrap();
+        let [lowest, edge_start, edge_end] = [0, 1, 2].map(|k| face[(first + k) % 3]);
+        // Angle around the opposite edge from the face's lowest vertex, the largest is next to the face on the sphere.
+        let [start, end] = [edge_start, edge_end].map(|i| self.positions[i]);
+        let axis = sub(end, start);
+        let perpendicular = |p: Point| {
+            let offset = sub(p, start);
+            sub(offset, scale(axis, dot(offset, axis) / length2(axis)))
+        };
+        let across = perpendicular(self.positions[lowest]);
+        let up = cross(axis, across);
+        let angle = |i: usize| {
+            let offset = perpendicular(self.positions[i]);
+            dot(offset, up).atan2(dot(offset, across))
+        };
+        candidates.sort_by(|&(_, _, i), &(_, _, j)| {
+            // Vertices with lower indices than the face move the most, and are inside all the other spheres.
+            match (i < lowest, j < lowest) {
+                (true, true) => i.cmp(&j),
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                (false, false) => angle(j).total_cmp(&angle(i)).then(i.cmp(&j)),
+            }
+        });
+    }
+
+    /// Whether the tetrahedron from a front face to `apex` lies in the unfilled part of the volume, without crossing or enclosing any of the front.
+    fn can_add(&self, face: [usize; 3], apex: usize) -> bool {
+        let [a, b, c] = face.map(|i| self.positions[i]);
+        if plane_distance(a, b, c, self.positions[apex]) <= self.eps {
+            return false;
+        }
+        let new_faces = new_front_faces(face, apex);
+        if new_faces
+            .iter()
+            .any(|&new_face| self.front.contains(&canonical(new_face)))
+        {
+            return false;
+        }
+        // Only front faces overlapping the tetrahedron's bounding box can touch it, the box test is much cheaper.
+        let [a, b, c] = face;
+        let (min, max) = bounds([a, b, c, apex].map(|i| self.positions[i]));
+        let near = |i: usize| {
+            let p = self.positions[i];
+            (0..3).all(|axis| p[axis] >= min[axis] - self.eps && p[axis] <= max[axis] + self.eps)
+        };
+        let crosses_front = self.front.iter().any(|&front_face| {
+            front_face != face
+                && {
+                    let (face_min, face_max) = bounds(front_face.map(|i| self.positions[i]));
+                    (0..3).all(|axis| {
+                        face_min[axis] <= max[axis] + self.eps
+                            && face_max[axis] >= min[axis] - self.eps
+                    })
+                }
+                && new_faces
+                    .iter()
+                    .any(|&new_face| self.triangles_intersect(new_face, front_face))
+        });
+        if crosses_front {
+            return false;
+        }
+        let inward_faces = [[a, b, c], [b, a, apex], [a, c, apex], [c, b, apex]]
+            .map(|triangle| triangle.map(|i| self.positions[i]));
+        !(0..self.positions.len()).any(|i| {
+            self.front_uses[i] > 0
+                && near(i)
+                && ![a, b, c, apex].contains(&i)
+                && inward_faces
+                    .iter()
+                    .all(|&[p, q, r]| plane_distance(p, q, r, self.positions[i]) > self.eps)
+        })
+    }
+
+    /// Fills the tetrahedron from a front face to `apex`, returning the faces it adds to the front.
+    fn add_tetrahedron(&mut self, face: [usize; 3], apex: usize) -> Vec<[usize; 3]> {
+        self.front.remove(&face);
+        for i in face {
+            self.front_uses[i] -= 1;
+        }
+        let [a, b, c] = face;
+        self.simplexes.push([a, b, c, apex]);
+        let mut added = vec![];
+        for new_face in new_front_faces(face, apex) {
+            let [a, b, c] = new_face;
+            // The other side of the face is already filled, so it leaves the front.
+            if self.front.remove(&canonical([a, c, b])) {
+                for i in new_face {
+                    self.front_uses[i] -= 1;
+                }
+            } else {
+                self.add_front_face(new_face);
+                added.push(canonical(new_face));
+            }
+        }
+        added
+    }
+
+    fn add_front_face(&mut self, face: [usize; 3]) {
+        self.front.insert(canonical(face));
+        for i in face {
+            self.front_uses[i] += 1;
+        }
+    }
+
+    /// Adds a vertex inside the unfilled part of the volume, for when no existing vertex can be used.
+    /// If every face of a stuck part of the front can see some point inside it, it's filled by joining all of them to a vertex there.
+    /// Otherwise a face is joined to a vertex just in front of it.
+    /// Returns the faces it adds to the front, or None if there's no room for a vertex anywhere.
+    fn add_steiner_point(&mut self) -> Option<Vec<[usize; 3]>> {
+        if self.steiner_points_left == 0 {
+            return None;
+        }
+        self.steiner_points_left -= 1;
+        let apex = self.positions.len();
+        self.front_uses.push(0);
+
+        let mut faces_by_edge: HashMap<[usize; 2], Vec<[usize; 3]>> = HashMap::new();
+        for &face in &self.front {
+            for k in 0..3 {
+                let mut edge = [face[k], face[(k + 1) % 3]];
+                edge.sort();
+                faces_by_edge.entry(edge).or_default().push(face);
+            }
+        }
+        let mut seen = HashSet::new();
+        let faces: Vec<_> = self.front.iter().copied().collect();
+        for &face in &faces {
+            if !seen.insert(face) {
+                continue;
+            }
+            let mut component = vec![face];
+            let mut next = 0;
+            while let Some(&[a, b, c]) = component.get(next) {
+                next += 1;
+                for mut edge in [[a, b], [b, c], [c, a]] {
+                    edge.sort();
+                    let neighbors = &faces_by_edge[&edge];
+                    component.extend(neighbors.iter().filter(|&&other| seen.insert(other)));
+                }
+            }
+            let Some(center) = self.visible_point(&component) else {
+                continue;
+            };
+            // Only join all of the faces at once, joining some of them tends to leave slivers that are even harder to fill.
+            self.positions.push(center);
+            if component.iter().all(|&face| self.can_add(face, apex)) {
+                let mut added = vec![];
+                for &face in &component {
+                    added.extend(self.add_tetrahedron(face, apex));
+                }
+                return Some(added);
+            }
+            self.positions.pop();
+        }
+
+        for face in faces {
+            let [a, b, c] = face.map(|i| self.positions[i]);
+            let normal = cross(sub(b, a), sub(c, a));
+            let normal_length = length2(normal).sqrt();
+            if normal_length == 0.0 {
+                continue;
+            }
+            let centroid = scale(add(add(a, b), c), 1.0 / 3.0);
+            let mut height = [sub(b, a), sub(c, b), sub(a, c)]
+                .map(length2)
+                .into_iter()
+                .fold(0.0, f64::max)
+                .sqrt();
+            while height > self.eps {
+                self.positions
+                    .push(add(centroid, scale(normal, height / normal_length)));
+                if self.can_add(face, apex) {
+                    return Some(self.add_tetrahedron(face, apex));
+                }
+                self.positions.pop();
+                height *= 0.5;
+            }
+        }
+        None
+    }
+
+    /// A point in front of all of some front faces if it can find one, starting from the centroid of the volume they enclose.
+    /// None if they don't enclose any volume.
+    fn visible_point(&self, faces: &[[usize; 3]]) -> Option<Point> {
+        let mut point = self.centroid(faces)?;
+        let (min, max) = faces
+            .iter()
+            .map(|face| bounds(face.map(|i| self.positions[i])))
+            .reduce(|(min, max), (face_min, face_max)| {
+                (
+                    [0, 1, 2].map(|axis| min[axis].min(face_min[axis])),
+                    [0, 1, 2].map(|axis| max[axis].max(face_max[axis])),
+                )
+            })?;
+        let margin = (length2(sub(max, min)).sqrt() * 1e-3).max(self.eps * 2.0);
+        // Repeatedly steps out in front of the face it's furthest behind, which ends up in front of all of them if that's possible.
+        for _ in 0..faces.len() * 8 {
+            let Some((distance, normal)) = faces
+                .iter()
+                .filter_map(|face| {
+                    let [a, b, c] = face.map(|i| self.positions[i]);
+                    let normal = cross(sub(b, a), sub(c, a));
+                    let normal_length = length2(normal).sqrt();
+                    (normal_length > 0.0).then(|| {
+                        (
+                            plane_distance(a, b, c, point),
+                            scale(normal, normal_length.recip()),
+                        )
+                    })
+                })
+                .min_by(|x, y| x.0.total_cmp(&y.0))
+            else {
+                break;
+            };
+            if distance > margin {
+                break;
+            }
+            point = add(point, scale(normal, margin - distance));
+        }
+        Some(point)
+    }
+
+    /// Center of mass of the volume enclosed by some front faces, or None if they don't enclose any.
+    fn centroid(&self, faces: &[[usize; 3]]) -> Option<Point> {
+        // Cones from each face to a nearby vertex rather than the origin, to keep the rounding error down far from the origin.
+        let origin = self.positions[faces.first()?[0]];
+        let (volume, weighted_sum) =
+            faces
+                .iter()
+                .fold((0.0, [0.0; 3]), |(volume, weighted_sum), face| {
+                    let [a, b, c] = face.map(|i| sub(self.positions[i], origin));
+                    let cone_volume = dot(a, cross(b, c)) / 6.0;
+                    let center = scale(add(add(a, b), c), 0.25);
+                    (
+                        volume + cone_volume,
+                        add(weighted_sum, scale(center, cone_volume)),
+                    )
+                });
+        (volume.abs() > self.eps.powi(3)).then(|| add(origin, scale(weighted_sum, volume.recip())))
+    }
+
+    /// Whether two triangles touch anywhere other than along the vertices and edges they share.
+    fn triangles_intersect(&self, p: [usize; 3], q: [usize; 3]) -> bool {
+        let shared: Vec<_> = p.iter().copied().filter(|i| q.contains(i)).collect();
+        match shared[..] {
+            [_, _, _] => false,
+            // Triangles sharing an edge only overlap if they are in the same plane, on the same side of the edge.
+            [u, v] => {
+                let other = |triangle: [usize; 3]| {
+                    let i = triangle.into_iter().find(|&i| i != u && i != v).unwrap();
+                    self.positions[i]
+                };
+                let [u, v, x, y] = [self.positions[u], self.positions[v], other(p), other(q)];
+                plane_distance(u, v, x, y).abs() <= self.eps
+                    && dot(cross(sub(v, u), sub(x, u)), cross(sub(v, u), sub(y, u))) > 0.0
+            }
+            // Otherwise the overlap always reaches an edge of one that doesn't touch the other.
+            _ => {
+                let edge_hits = |edges: [usize; 3], triangle: [usize; 3]| {
+                    (0..3).any(|k| {
+                        let [i, j] = [edges[k], edges[(k + 1) % 3]];
+                        !triangle.contains(&i)
+                            && !triangle.contains(&j)
+                            && segment_hits_triangle(
+                                self.positions[i],
+                                self.positions[j],
+                                triangle.map(|t| self.positions[t]),
+                                self.eps,
+                            )
+                    })
+                };
+                edge_hits(p, q) || edge_hits(q, p)
+            }
+        }
+    }
+}
+
+/// Faces of the tetrahedron from a front face to `apex`, other than the front face, wound counterclockwise seen from outside the tetrahedron.
+fn new_front_faces([a, b, c]: [usize; 3], apex: usize) -> [[usize; 3]; 3] {
+    [[a, b, apex], [b, c, apex], [c, a, apex]]
+}
+
+/// Rotates a face so its smallest index is first, keeping its winding.
+fn canonical(face: [usize; 3]) -> [usize; 3] {
+    let [a, b, c] = face;
+    if a < b && a < c {
+        [a, b, c]
+    } else if b < c {
+        [b, c, a]
+    } else {
+        [c, a, b]
+    }
+}
+
+/// Whether the segment from `p` to `q` touches the triangle, including its edges, within `eps`.
+fn segment_hits_triangle(p: Point, q: Point, [a, b, c]: [Point; 3], eps: f64) -> bool {
+    let [p_height, q_height] = [p, q].map(|x| plane_distance(a, b, c, x));
+    if (p_height > eps && q_height > eps) || (p_height < -eps && q_height < -eps) {
+        return false;
+    }
+    if p_height.abs() <= eps && q_height.abs() <= eps {
+        // In the plane of the triangle, drop the axis it faces most to work in 2D.
+        let normal = cross(sub(b, a), sub(c, a));
+        let axis = (0..3)
+            .max_by(|&i, &j| normal[i].abs().total_cmp(&normal[j].abs()))
+            .unwrap();
+        let [i, j] = match axis {
+            0 => [1, 2],
+            1 => [2, 0],
+            _ => [0, 1],
+        };
+        let [p, q, a, b, c] = [p, q, a, b, c].map(|x| [x[i], x[j]]);
+        return point_in_triangle_2d(p, [a, b, c], eps)
+            || point_in_triangle_2d(q, [a, b, c], eps)
+            || [[a, b], [b, c], [c, a]]
+                .into_iter()
+                .any(|[r, s]| segments_touch_2d([p, q], [r, s], eps));
+    }
+    // The line through p and q passes through the triangle if it goes the same way around all its edges.
+    let sides = [[a, b], [b, c], [c, a]].map(|[r, s]| plane_distance(p, q, r, s));
+    sides.iter().all(|&side| side >= -eps) || sides.iter().all(|&side| side <= eps)
+}
+
+fn point_in_triangle_2d(p: [f64; 2], [a, b, c]: [[f64; 2]; 3], eps: f64) -> bool {
+    let sides = [[a, b], [b, c], [c, a]].map(|[r, s]| line_distance_2d(r, s, p));
+    sides.iter().all(|&side| side >= -eps) || sides.iter().all(|&side| side <= eps)
+}
+
+fn segments_touch_2d([p, q]: [[f64; 2]; 2], [r, s]: [[f64; 2]; 2], eps: f64) -> bool {
+    let [r_side, s_side] = [r, s].map(|x| line_distance_2d(p, q, x));
+    if r_side.abs() <= eps && s_side.abs() <= eps {
+        // Collinear, so they touch if their extents along the line overlap.
+        let direction = [q[0] - p[0], q[1] - p[1]];
+        let length = direction[0].hypot(direction[1]);
+        let along = |x: [f64; 2]| {
+            ((x[0] - p[0]) * direction[0] + (x[1] - p[1]) * direction[1]) / length.max(eps)
+        };
+        let [r_along, s_along] = [along(r), along(s)];
+        return r_along.max(s_along) >= -eps && r_along.min(s_along) <= length + eps;
+    }
+    let [p_side, q_side] = [p, q].map(|x| line_distance_2d(r, s, x));
+    // Neither both clearly on one side, nor both clearly on the other.
+    let straddles = |x: f64, y: f64| x.min(y) <= eps && x.max(y) >= -eps;
+    straddles(r_side, s_side) && straddles(p_side, q_side)
+}
+
+/// Signed distance of `p` from the line through `a` and `b`, positive to the left. Zero if `a` and `b` are the same.
+fn line_distance_2d(a: [f64; 2], b: [f64; 2], p: [f64; 2]) -> f64 {
+    let direction = [b[0] - a[0], b[1] - a[1]];
+    let length = direction[0].hypot(direction[1]);
+    if length == 0.0 {
+        return 0.0;
+    }
+    (direction[0] * (p[1] - a[1]) - direction[1] * (p[0] - a[0])) / length
+}
+
+/// Signed distance of `d` from the plane through `a`, `b` and `c`, positive on the side they are counterclockwise from.
+/// Zero if they are collinear.
+fn plane_distance(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let normal = cross(sub(b, a), sub(c, a));
+    let length = length2(normal).sqrt();
+    if length == 0.0 {
+        return 0.0;
+    }
+    dot(normal, sub(d, a)) / length
+}
+
+/// Corners of the bounding box of some points, with the smallest and largest coordinates.
+fn bounds<const N: usize>(points: [Point; N]) -> (Point, Point) {
+    points
+        .into_iter()
+        .fold(([f64::MAX; 3], [f64::MIN; 3]), |(min, max), point| {
+            (
+                [0, 1, 2].map(|axis| min[axis].min(point[axis])),
+                [0, 1, 2].map(|axis| max[axis].max(point[axis])),
+            )
+        })
+}
+
+/// Center of the circle through three points.
+fn circumcenter(a: Point, b: Point, c: Point) -> Point {
+    let [u, v] = [sub(b, a), sub(c, a)];
+    let normal = cross(u, v);
+    let offset = add(
+        scale(cross(v, normal), length2(u)),
+        scale(cross(normal, u), length2(v)),
+    );
+    add(a, scale(offset, 0.5 / length2(normal)))
+}
+
+fn add(a: Point, b: Point) -> Point {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: Point, factor: f64) -> Point {
+    a.map(|x| x * factor)
+}
+
+fn dot(a: Point, b: Point) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length2(a: Point) -> f64 {
+    dot(a, a)
+}
+
+fn cross(a: Point, b: Point) -> Point {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::{PI, TAU};
+
+    use glam::{Quat, Vec3, Vec4};
+
+    use super::*;
+    use crate::mesh::{
+        ops::LiftOrthographic, test_util::tetrahedron_sign, TetrahedronMesh4D, TriangleMesh,
+    };
+
+    fn mesh_from(positions: &[[f32; 3]], simplexes: Vec<[usize; 3]>) -> TriangleMesh3D<Vec3> {
+        TriangleMesh {
+            vertices: positions
+                .iter()
+                .map(|&p| Vertex3 {
+                    position: Vec3::from(p),
+                })
+                .collect(),
+            simplexes,
+        }
+    }
+
+    /// Surface of a solid tetrahedron mesh, the faces that only one tetrahedron uses, wound counterclockwise from outside.
+    fn boundary(solid: &TetrahedronMesh3D<Vec3>) -> TriangleMesh3D<Vec3> {
+        let faces: Vec<_> = solid
+            .simplexes
+            .iter()
+            .flat_map(|&[a, b, c, d]| [[a, c, b], [a, b, d], [b, c, d], [c, a, d]])
+            .collect();
+        let all: HashSet<_> = faces.iter().map(|&face| canonical(face)).collect();
+        TriangleMesh {
+            vertices: solid.vertices.clone(),
+            simplexes: faces
+                .into_iter()
+                .filter(|&[a, b, c]| !all.contains(&canonical([a, c, b])))
+                .collect(),
+        }
+    }
+
+    /// Checks that `solid` is made of right-handed tetrahedra exactly filling `surface`.
+    fn assert_fills(surface: &TriangleMesh3D<Vec3>, solid: &TetrahedronMesh3D<Vec3>) {
+        for simplex in &solid.simplexes {
+            assert_eq!(
+                tetrahedron_sign(simplex.map(|i| solid.vertices[i].position)),
+                1.0,
+                "{simplex:?}"
+            );
+        }
+        let sorted_faces = |mesh: &TriangleMesh3D<Vec3>| {
+            let mut faces: Vec<_> = mesh
+                .simplexes
+                .iter()
+                .map(|&face| {
+                    let mut face = face;
+                    face.sort();
+                    face
+                })
+                .collect();
+            faces.sort();
+            faces
+        };
+        assert_eq!(sorted_faces(&boundary(solid)), sorted_faces(surface));
+        let enclosed: f32 = surface
+            .simplexes
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.map(|i| surface.vertices[i].position);
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum();
+        let volume = dbg!(solid.volume());
+        assert!((volume - enclosed.abs()).abs() < 1e-4 * volume);
+    }
+
+    /// Triangular prism with the top twisted, and each side folded inwards along a diagonal.
+    fn schonhardt_polyhedron() -> TriangleMesh3D<Vec3> {
+        let corner = |i: usize, z: f32, twist: f32| {
+            let angle = TAU * i as f32 / 3.0 + twist;
+            [angle.cos(), angle.sin(), z]
+        };
+        let positions: Vec<_> = (0..3)
+            .map(|i| corner(i, 0.0, 0.0))
+            .chain((0..3).map(|i| corner(i, 1.0, PI / 6.0)))
+            .collect();
+        let mut simplexes = vec![[0, 2, 1], [3, 4, 5]];
+        for i in 0..3 {
+            let next = (i + 1) % 3;
+            simplexes.push([i, next, next + 3]);
+            simplexes.push([i, next + 3, i + 3]);
+        }
+        mesh_from(&positions, simplexes)
+    }
+
+    /// Square ring 3 wide and 1 high around a square hole, with its sides divided into `cells`, which must be multiples of 3 across.
+    fn ring(cells: [usize; 3]) -> TriangleMesh3D<Vec3> {
+        let mut grid = TetrahedronMesh3D::<Vec3>::grid(Vec3::new(3.0, 3.0, 1.0), cells);
+        let vertices = grid.vertices.clone();
+        grid.simplexes.retain(|simplex| {
+            let center = simplex.iter().map(|&i| vertices[i].position).sum::<Vec3>() / 4.0;
+            center.x.abs() > 0.5 || center.y.abs() > 0.5
+        });
+        boundary(&grid)
+    }
+
+    /// Sphere made like most modelling tools do, with `rings` bands of latitude between the poles, and `segments` around each band.
+    fn uv_sphere(rings: usize, segments: usize) -> TriangleMesh3D<Vec3> {
+        let positions: Vec<_> = std::iter::once([0.0, 0.0, 1.0])
+            .chain((1..rings).flat_map(|ring| {
+                let polar = PI * ring as f32 / rings as f32;
+                (0..segments).map(move |segment| {
+                    let azimuth = TAU * segment as f32 / segments as f32;
+                    [
+                        polar.sin() * azimuth.cos(),
+                        polar.sin() * azimuth.sin(),
+                        polar.cos(),
+                    ]
+                })
+            }))
+            .chain([[0.0, 0.0, -1.0]])
+            .collect();
+        let south = positions.len() - 1;
+        let vertex = |ring: usize, segment: usize| 1 + (ring - 1) * segments + segment % segments;
+        let mut simplexes = vec![];
+        for segment in 0..segments {
+            simplexes.push([0, vertex(1, segment), vertex(1, segment + 1)]);
+            for ring in 1..rings - 1 {
+                let [a, b] = [vertex(ring, segment), vertex(ring, segment + 1)];
+                let [c, d] = [vertex(ring + 1, segment), vertex(ring + 1, segment + 1)];
+                simplexes.push([a, c, d]);
+                simplexes.push([a, d, b]);
+            }
+            simplexes.push([
+                south,
+                vertex(rings - 1, segment + 1),
+                vertex(rings - 1, segment),
+            ]);
+        }
+        mesh_from(&positions, simplexes)
+    }
+
+    #[test]
+    fn gift_wraps_uv_sphere_without_extra_vertices() {
+        // Every vertex is on the same sphere, so all of them tie for every face.
+        for [rings, segments] in [[8, 16], [10, 20]] {
+            let mesh = uv_sphere(rings, segments);
+            let mut wrap = GiftWrap::new(&mesh).unwrap();
+
+            wrap.fill().unwrap();
+
+            assert_eq!(wrap.positions.len(), mesh.vertices.len());
+            let got = TetrahedronMesh3D {
+                vertices: mesh.vertices.clone(),
+                simplexes: wrap.simplexes,
+            };
+            assert_fills(&mesh, &got);
+        }
+    }
+
+    #[test]
+    fn fills_cube_either_way_out() {
+        let cube = TriangleMesh3D::<Vec3>::cube(2.0);
+        let mut inverted = cube.clone();
+        inverted.invert();
+
+        for mesh in [cube, inverted] {
+            let got = mesh.tetrahedralize().unwrap();
+
+            assert_eq!(got.vertices.len(), 8);
+            assert!((got.volume() - 8.0).abs() < 1e-4);
+            assert_fills(&mesh, &got);
+        }
+    }
+
+    #[test]
+    fn fills_off_center_cube() {
+        let mut cube = TriangleMesh3D::<Vec3>::cube(1.0);
+        for vertex in cube.vertices.iter_mut() {
+            vertex.position += Vec3::new(3.0, -1.0, 0.5);
+        }
+
+        let got = cube.tetrahedralize().unwrap();
+
+        assert_fills(&cube, &got);
+    }
+
+    #[test]
+    fn fills_non_convex_surface_without_extra_vertices() {
+        // Triangular prism with the middle of one end pushed in past its centroid.
+        let mesh = mesh_from(
+            &[
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0],
+                [1.0, 0.0, 1.0],
+                [0.0, 1.0, 1.0],
+                [0.3, 0.3, 0.1],
+            ],
+            vec![
+                [0, 2, 1],
+                [0, 1, 4],
+                [0, 4, 3],
+                [1, 2, 5],
+                [1, 5, 4],
+                [2, 0, 3],
+                [2, 3, 5],
+                [3, 4, 6],
+                [4, 5, 6],
+                [5, 3, 6],
+            ],
+        );
+
+        let got = mesh.tetrahedralize().unwrap();
+
+        assert_eq!(got.vertices.len(), 7);
+        assert_fills(&mesh, &got);
+    }
+
+    #[test]
+    fn fills_surface_with_a_hole_through_it() {
+        let mesh = ring([3, 3, 1]);
+
+        let got = mesh.tetrahedralize().unwrap();
+
+        assert_fills(&mesh, &got);
+    }
+
+    #[test]
+    fn adds_vertices_to_fill_schonhardt_polyhedron() {
+        let mesh = schonhardt_polyhedron();
+
+        let got = mesh.tetrahedralize().unwrap();
+
+        assert!(dbg!(got.vertices.len()) > 6);
+        assert_fills(&mesh, &got);
+    }
+
+    #[test]
+    fn rejects_open_mesh() {
+        let mut cube = TriangleMesh3D::<Vec3>::cube(1.0);
+        cube.simplexes.pop();
+
+        let got = dbg!(cube.tetrahedralize());
+
+        assert!(matches!(got, Err(TetrahedralizeError::NotClosed(_))));
+    }
+
+    #[test]
+    fn rejects_flipped_triangle() {
+        let mut cube = TriangleMesh3D::<Vec3>::cube(1.0);
+        cube.simplexes[0].swap(0, 1);
+
+        let got = dbg!(cube.tetrahedralize());
+
+        assert!(matches!(
+            got,
+            Err(TetrahedralizeError::InconsistentWinding(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_empty_mesh() {
+        let got = TriangleMesh3D::<Vec3>::default().tetrahedralize();
+
+        assert_eq!(got.unwrap_err(), TetrahedralizeError::Empty);
+    }
+
+    #[test]
+    fn tetrahedralized_mesh_lifts_into_4d() {
+        let mut cube = TriangleMesh3D::<Vec3>::cube(2.0);
+        cube.invert();
+
+        let solid = cube.tetrahedralize().unwrap();
+        let lifted: TetrahedronMesh4D<Vec4> = solid.lift_orthographic(0.5);
+
+        assert_eq!(lifted.simplexes, solid.simplexes);
+        assert!((lifted.volume() - 8.0).abs() < 1e-4);
+        assert!(lifted.vertices.iter().all(|v| v.position.w == 0.5));
+    }
+
+    #[test]
+    fn fills_rotated_and_scaled_ring() {
+        for (angles, scale) in [
+            ([0.3, -1.2, 2.0], 1.0),
+            ([PI / 4.0, 0.0, PI / 3.0], 0.01),
+            ([-2.5, 0.7, -0.1], 100.0),
+        ] {
+            // Rotating rounds the coordinates, so the flat sides are only nearly flat.
+            let [x, y, z] = angles;
+            let rotation = Quat::from_euler(glam::EulerRot::XYZ, x, y, z);
+            let mut mesh = ring([3, 3, 1]);
+            for vertex in mesh.vertices.iter_mut() {
+                vertex.position = rotation * vertex.position * scale;
+            }
+
+            let got = mesh.tetrahedralize().unwrap();
+
+            assert_fills(&mesh, &got);
+        }
+    }
+
+    #[test]
+    fn fills_finely_divided_ring_with_bumpy_sides() {
+        let mut mesh = ring([6, 6, 3]);
+        // Moves each vertex a little, in a fixed pseudo-random direction, so no four vertices are on a plane or sphere.
+        for (i, vertex) in mesh.vertices.iter_mut().enumerate() {
+            let offset = |axis: usize| ((i * 7919 + axis * 104729) % 1000) as f32 / 1000.0 - 0.5;
+            vertex.position += Vec3::new(offset(0), offset(1), offset(2)) * 0.02;
+        }
+
+        let got = mesh.tetrahedralize().unwrap();
+
+        assert_fills(&mesh, &got);
+    }
+}
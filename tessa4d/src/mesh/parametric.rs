@@ -0,0 +1,336 @@
+//! Builder for meshes from parametric maps, e.g. for hypersurfaces like tori and glomes.
+use super::{ops::split_prism, TetrahedronMesh4D, Vertex4};
+use crate::linear_algebra::Vector3;
+
+/// How the two ends of one axis of the parameter box are joined together.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Seam {
+    /// The ends are left as separate boundaries of the mesh.
+    #[default]
+    Open,
+    /// The end of the axis wraps around to the start, e.g. for the angle around a circle.
+    Periodic,
+    /// Like [Seam::Periodic], but the other axes marked `true` are reversed when wrapping around, e.g. for a Möbius strip or a Klein bottle.
+    Twisted([bool; 3]),
+}
+
+/// Builds a [TetrahedronMesh4D] by sampling a parametric map from a box of 3D parameters to points in 4D.
+///
+/// Samples `map` at the corners of a grid of `cells` boxes covering the parameter box,
+/// and splits each box into tetrahedra so that neighboring tetrahedra always share whole faces.
+/// Tetrahedra are right-handed in parameter space, so the handedness of the mesh follows the orientation of `map`.
+#[derive(Clone, Copy, Debug)]
+pub struct ParametricSurface4D<P, F> {
+    /// Maps from a point in the parameter box to a point in 4D.
+    pub map: F,
+    /// Corner of the parameter box with the lowest values.
+    pub min: P,
+    /// Corner of the parameter box with the highest values.
+    pub max: P,
+    /// Number of grid cells along each axis of the parameter box.
+    /// Open axes get at least 1 cell and joined axes at least 3, whatever is asked for.
+    pub cells: [usize; 3],
+    /// How the ends of each axis of the parameter box are joined together.
+    pub seams: [Seam; 3],
+}
+
+impl<P: Vector3, F: Fn(P) -> P::Vector4> ParametricSurface4D<P, F> {
+    /// Makes a builder for `map` over the parameter box from `min` to `max`, with one open cell along each axis.
+    pub fn new(map: F, min: P, max: P) -> Self {
+        Self {
+            map,
+            min,
+            max,
+            cells: [1, 1, 1],
+            seams: [Seam::Open; 3],
+        }
+    }
+
+    /// Returns a builder that samples the parameter box with `cells` grid cells along each axis.
+    pub fn with_cells(self, cells: [usize; 3]) -> Self {
+        Self { cells, ..self }
+    }
+
+    /// Returns a builder that joins the ends of each axis of the parameter box according to `seams`.
+    pub fn with_seams(self, seams: [Seam; 3]) -> Self {
+        Self { seams, ..self }
+    }
+
+    /// Samples the parametric map into a mesh.
+    pub fn build(&self) -> TetrahedronMesh4D<P::Vector4> {
+        let grid = ParameterGrid::new(self.cells, self.seams);
+        let [min, max] = [self.min, self.max].map(|p| [p.x(), p.y(), p.z()]);
+        let vertices = grid
+            .vertex_coords()
+            .map(|coords| {
+                let [x, y, z] = std::array::from_fn(|axis| {
+                    let fraction = coords[axis] as f32 / grid.cells[axis] as f32;
+                    min[axis] + (max[axis] - min[axis]) * fraction
+                });
+                Vertex4 {
+                    position: (self.map)(P::new(x, y, z)),
+                }
+            })
+            .collect();
+        TetrahedronMesh4D {
+            vertices,
+            simplexes: grid.simplexes(),
+        }
+    }
+}
+
+/// Topology of a grid of boxes split into tetrahedra, with the ends of each axis joined according to a [Seam].
+/// Vertices are indexed with x varying fastest, then y, then z.
+pub(crate) struct ParameterGrid {
+    cells: [usize; 3],
+    seams: [Seam; 3],
+    /// Number of distinct vertices along each axis.
+    verts: [usize; 3],
+}
+
+impl ParameterGrid {
+    pub(crate) fn new(cells: [usize; 3], seams: [Seam; 3]) -> Self {
+        // Joined axes need 3 cells, with fewer the cells on either side of a seam would share all their vertices.
+        let cells = std::array::from_fn(|axis| match seams[axis] {
+            Seam::Open => cells[axis].max(1),
+            Seam::Periodic | Seam::Twisted(_) => cells[axis].max(3),
+        });
+        let verts = std::array::from_fn(|axis| match seams[axis] {
+            Seam::Open => cells[axis] + 1,
+            Seam::Periodic | Seam::Twisted(_) => cells[axis],
+        });
+        Self {
+            cells,
+            seams,
+            verts,
+        }
+    }
+
+    /// Grid coordinates of each distinct vertex, in index order.
+    pub(crate) fn vertex_coords(&self) -> impl Iterator<Item = [usize; 3]> {
+        let [verts_x, verts_y, verts_z] = self.verts;
+        (0..verts_z)
+            .flat_map(move |k| (0..verts_y).flat_map(move |j| (0..verts_x).map(move |i| [i, j, k])))
+    }
+
+    /// Right-handed tetrahedra filling every cell of the grid.
+    pub(crate) fn simplexes(&self) -> Vec<[usize; 4]> {
+        let [cells_x, cells_y, cells_z] = self.cells;
+        (0..cells_z)
+            .flat_map(|k| (0..cells_y).flat_map(move |j| (0..cells_x).map(move |i| [i, j, k])))
+            .flat_map(|[i, j, k]| {
+                // Counter-clockwise triangles covering the bottom of the cell, each one makes a prism through the cell.
+                let corners = [[i, j], [i + 1, j], [i + 1, j + 1], [i, j + 1]];
+                [[0, 1, 2], [0, 2, 3]].map(|face| {
+                    let face = face.map(|corner| corners[corner]);
+                    split_prism_conforming(
+                        face.map(|[x, y]| self.index([x, y, k + 1])),
+                        face.map(|[x, y]| self.index([x, y, k])),
+                    )
+                })
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Index of the vertex at the given grid coordinates, which may be one past the end of a joined axis.
+    fn index(&self, mut coords: [usize; 3]) -> usize {
+        for axis in 0..3 {
+            if coords[axis] < self.verts[axis] {
+                continue;
+            }
+            coords[axis] = 0;
+            if let Seam::Twisted(reversed) = self.seams[axis] {
+                for other in (0..3).filter(|&other| other != axis && reversed[other]) {
+                    coords[other] = (self.cells[other] - coords[other]) % self.verts[other];
+                }
+            }
+        }
+        coords[0] + self.verts[0] * (coords[1] + self.verts[1] * coords[2])
+    }
+}
+
+/// Splits a triangular prism into tetrahedra like [split_prism], but each square side is split along the diagonal through its lowest-indexed vertex.
+/// That way neighboring prisms always agree on how to split their shared sides, without any cracks or overlaps.
+fn split_prism_conforming(top: [usize; 3], bottom: [usize; 3]) -> [[usize; 4]; 3] {
+    let quad_min = |a: usize, b: usize| top[a].min(top[b]).min(bottom[a]).min(bottom[b]);
+    // Diagonals that split_prism uses for each side, as pairs of (top, bottom) corners.
+    const DIAGONALS: [[usize; 2]; 3] = [[1, 0], [1, 2], [2, 0]];
+    // Orders of the triangle corners to try, with whether they flip the winding order.
+    const ORDERS: [([usize; 3], bool); 6] = [
+        ([0, 1, 2], false),
+        ([1, 2, 0], false),
+        ([2, 0, 1], false),
+        ([0, 2, 1], true),
+        ([2, 1, 0], true),
+        ([1, 0, 2], true),
+    ];
+    for (order, reflected) in ORDERS {
+        for swapped in [false, true] {
+            let (upper, lower) = if swapped {
+                (order.map(|i| bottom[i]), order.map(|i| top[i]))
+            } else {
+                (order.map(|i| top[i]), order.map(|i| bottom[i]))
+            };
+            let matches = DIAGONALS.iter().all(|&[upper_corner, lower_corner]| {
+                let lowest = quad_min(order[upper_corner], order[lower_corner]);
+                upper[upper_corner] == lowest || lower[lower_corner] == lowest
+            });
+            if matches {
+                let mut tetrahedra = split_prism(upper, lower);
+                // Swapping the ends and reflecting the triangle both turn the tetrahedra inside-out.
+                if reflected != swapped {
+                    tetrahedra
+                        .iter_mut()
+                        .for_each(|tetrahedron| tetrahedron.swap(0, 1));
+                }
+                return tetrahedra;
+            }
+        }
+    }
+    // Every prism with distinct vertices has a matching split, only reachable for degenerate prisms.
+    split_prism(top, bottom)
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::TAU;
+
+    use glam::{Vec3, Vec4};
+
+    use super::*;
+    use crate::mesh::{ops::CrossSection, test_util::*};
+    use crate::util::approx_equal;
+
+    fn box_map(p: Vec3) -> Vec4 {
+        p.extend(0.0)
+    }
+
+    #[test]
+    fn build_samples_parameter_box() {
+        let mesh = ParametricSurface4D::new(box_map, Vec3::splat(-1.0), Vec3::splat(3.0))
+            .with_cells([2, 2, 2])
+            .build();
+
+        assert!(mesh.vertices[0]
+            .position
+            .abs_diff_eq(Vec4::new(-1.0, -1.0, -1.0, 0.0), 1e-5));
+        assert!(mesh.vertices[1]
+            .position
+            .abs_diff_eq(Vec4::new(1.0, -1.0, -1.0, 0.0), 1e-5));
+        assert!(mesh.vertices[26]
+            .position
+            .abs_diff_eq(Vec4::new(3.0, 3.0, 3.0, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn build_periodic_has_no_boundary() {
+        let mesh = ParametricSurface4D::new(box_map, Vec3::ZERO, Vec3::ONE)
+            .with_cells([3, 4, 5])
+            .with_seams([Seam::Periodic; 3])
+            .build();
+
+        let faces = face_counts(&mesh.simplexes);
+
+        assert_eq!(mesh.vertices.len(), 3 * 4 * 5);
+        assert!(faces.values().all(|count| *count == 2));
+    }
+
+    #[test]
+    fn build_periodic_with_default_cells_has_no_repeated_vertices() {
+        let mesh = ParametricSurface4D::new(box_map, Vec3::ZERO, Vec3::ONE)
+            .with_seams([
+                Seam::Periodic,
+                Seam::Open,
+                Seam::Twisted([false, true, false]),
+            ])
+            .build();
+
+        assert_eq!(mesh.vertices.len(), 3 * 2 * 3);
+        for simplex in &mesh.simplexes {
+            let mut sorted = *simplex;
+            sorted.sort();
+            assert!(
+                sorted.windows(2).all(|pair| pair[0] != pair[1]),
+                "{simplex:?}"
+            );
+        }
+        let faces = face_counts(&mesh.simplexes);
+        assert!(faces.values().all(|count| *count <= 2));
+    }
+
+    #[test]
+    fn build_twisted_has_no_boundary() {
+        let mesh = ParametricSurface4D::new(box_map, Vec3::ZERO, Vec3::ONE)
+            .with_cells([3, 4, 5])
+            .with_seams([
+                Seam::Twisted([false, true, true]),
+                Seam::Periodic,
+                Seam::Periodic,
+            ])
+            .build();
+
+        let faces = face_counts(&mesh.simplexes);
+
+        assert!(faces.values().all(|count| *count == 2));
+    }
+
+    #[test]
+    fn build_twisted_reverses_other_axes() {
+        let grid = ParameterGrid::new(
+            [3, 4, 5],
+            [Seam::Twisted([false, true, false]), Seam::Open, Seam::Open],
+        );
+
+        assert_eq!(grid.index([3, 1, 2]), grid.index([0, 3, 2]));
+    }
+
+    #[test]
+    fn build_duocylinder_cross_section_closed() {
+        // Product of two circles, thickened into a solid torus-like shell along the third parameter.
+        let duocylinder = |p: Vec3| {
+            let radius = 1.0 + 0.25 * p.z.cos();
+            Vec4::new(
+                radius * p.x.cos(),
+                radius * p.x.sin(),
+                (1.0 + 0.25 * p.z.sin()) * p.y.cos(),
+                (1.0 + 0.25 * p.z.sin()) * p.y.sin() + 0.1,
+            )
+        };
+        let mesh = ParametricSurface4D::new(duocylinder, Vec3::ZERO, Vec3::splat(TAU))
+            .with_cells([12, 12, 8])
+            .with_seams([Seam::Periodic; 3])
+            .build();
+
+        let got = mesh.cross_section();
+
+        assert!(!got.simplexes.is_empty());
+        assert!(triangle_mesh_closed(&got));
+    }
+
+    #[test]
+    fn build_glome_vertices_on_glome() {
+        let glome = |p: Vec3| {
+            let (sin_a, cos_a) = p.x.sin_cos();
+            let (sin_b, cos_b) = p.y.sin_cos();
+            let (sin_c, cos_c) = p.z.sin_cos();
+            Vec4::new(
+                cos_a,
+                sin_a * cos_b,
+                sin_a * sin_b * cos_c,
+                sin_a * sin_b * sin_c,
+            )
+        };
+        let mesh =
+            ParametricSurface4D::new(glome, Vec3::ZERO, Vec3::new(TAU / 2.0, TAU / 2.0, TAU))
+                .with_cells([8, 8, 16])
+                .with_seams([Seam::Open, Seam::Open, Seam::Periodic])
+                .build();
+
+        assert_eq!(mesh.vertices.len(), 9 * 9 * 16);
+        for vertex in mesh.vertices.iter() {
+            assert!(approx_equal(vertex.position.length(), 1.0, 1e-5));
+        }
+    }
+}
@@ -0,0 +1,459 @@
+//! Triangulating arbitrary polygons, e.g. outlines of 2D profiles to [Extrude](super::ops::Extrude).
+use thiserror::Error;
+
+use super::{TriangleMesh2D, Vertex2};
+use crate::linear_algebra::Vector2;
+
+/// Problems triangulating a polygon.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum PolygonError {
+    #[error("Outline doesn't enclose any area")]
+    EmptyOutline,
+    #[error("Hole {0} doesn't enclose any area")]
+    EmptyHole(usize),
+    #[error("Hole {0} isn't inside the outline")]
+    HoleOutside(usize),
+    #[error(
+        "Couldn't triangulate the polygon, {0} corners were left over, it may intersect itself"
+    )]
+    Unclipped(usize),
+}
+
+type Point = [f64; 2];
+
+impl<V: Vector2> TriangleMesh2D<V> {
+    /// Triangulates a simple polygon with `holes` cut out of it, by ear clipping, so unlike [Self::circle] it doesn't need to be convex.
+    /// The vertices are the points of `outline` followed by the points of each hole in order, no vertices are added.
+    /// The triangles have the same handedness as [Self::rectangle], whichever way the outline and holes wind.
+    ///
+    /// The holes must be inside the outline, and nothing may cross itself or anything else.
+    pub fn polygon(outline: &[V], holes: &[Vec<V>]) -> Result<Self, PolygonError> {
+        // Works in double precision, so the orientation tests are exact for most single precision input.
+        let positions: Vec<Point> = outline
+            .iter()
+            .chain(holes.iter().flatten())
+            .map(|p| [p.x(), p.y()].map(f64::from))
+            .collect();
+        // The outline goes counterclockwise and the holes clockwise, so the inside of the polygon is always on the left.
+        let mut ring =
+            oriented_loop(&positions, 0..outline.len(), true).ok_or(PolygonError::EmptyOutline)?;
+        let mut start = outline.len();
+        let mut hole_loops = vec![];
+        for (i, hole) in holes.iter().enumerate() {
+            let range = start..start + hole.len();
+            start = range.end;
+            let hole = oriented_loop(&positions, range, false).ok_or(PolygonError::EmptyHole(i))?;
+            hole_loops.push((i, hole));
+        }
+        // Bridges go right from each hole, so joining the holes from right to left means only already joined ones can be in the way.
+        let max_x = |(_, hole): &(usize, Vec<usize>)| {
+            hole.iter()
+                .map(|&i| positions[i][0])
+                .fold(f64::MIN, f64::max)
+        };
+        hole_loops.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+        for (i, hole) in &hole_loops {
+            if !join_hole(&positions, &mut ring, hole) {
+                return Err(PolygonError::HoleOutside(*i));
+            }
+        }
+
+        Ok(Self {
+            vertices: outline
+                .iter()
+                .chain(holes.iter().flatten())
+                .map(|&position| Vertex2 { position })
+                .collect(),
+            simplexes: clip_ears(&positions, ring)?,
+        })
+    }
+}
+
+/// Vertex indices in `range`, reversed if needed so they go counterclockwise if `counterclockwise` or clockwise otherwise.
+/// None if they don't enclose any area.
+fn oriented_loop(
+    positions: &[Point],
+    range: std::ops::Range<usize>,
+    counterclockwise: bool,
+) -> Option<Vec<usize>> {
+    let mut indices: Vec<usize> = range.collect();
+    let area: f64 = (0..indices.len())
+        .map(|k| {
+            let [a, b] = [indices[k], indices[(k + 1) % indices.len()]].map(|i| positions[i]);
+            a[0] * b[1] - a[1] * b[0]
+        })
+        .sum();
+    if area == 0.0 {
+        return None;
+    }
+    if (area > 0.0) != counterclockwise {
+        indices.reverse();
+    }
+    Some(indices)
+}
+
+/// Cuts a clockwise hole into the counterclockwise `ring`, by joining its rightmost vertex to a vertex of the ring it can see.
+/// The ring then goes along the bridge, around the hole and back along the bridge, so it stays a single loop.
+/// Returns false if there's nothing to join it to, as it isn't inside the ring.
+fn join_hole(positions: &[Point], ring: &mut Vec<usize>, hole: &[usize]) -> bool {
+    let start = (0..hole.len())
+        .max_by(|&j, &k| positions[hole[j]][0].total_cmp(&positions[hole[k]][0]))
+        .unwrap();
+    let m = positions[hole[start]];
+    if !inside_ring(positions, ring, m) {
+        return false;
+    }
+    let ring_position = |k: usize| positions[ring[k % ring.len()]];
+
+    // Finds where a ray from the hole to the right first hits the ring. Only upward edges face the inside of the ring to their left.
+    let mut hit: Option<(f64, usize)> = None;
+    for k in 0..ring.len() {
+        let [a, b] = [ring_position(k), ring_position(k + 1)];
+        if !(a[1] <= m[1] && m[1] <= b[1] && a[1] < b[1]) {
+            continue;
+        }
+        let x = a[0] + (m[1] - a[1]) * (b[0] - a[0]) / (b[1] - a[1]);
+        if x >= m[0] && hit.is_none_or(|(best, _)| x < best) {
+            // The end of the edge further right is visible from the hole, unless other vertices are in the way.
+            let end = if m[1] == a[1] || (m[1] != b[1] && a[0] > b[0]) {
+                k
+            } else {
+                (k + 1) % ring.len()
+            };
+            hit = Some((x, end));
+        }
+    }
+    let Some((x, mut bridge)) = hit else {
+        return false;
+    };
+
+    // Anything in the way is inside the triangle between the hole, the hit and the vertex, the best one to bridge to instead
+    // is the one closest in angle to the ray.
+    let hit_point = [x, m[1]];
+    let target = ring_position(bridge);
+    let mut best = (f64::MAX, f64::MAX);
+    for k in 0..ring.len() {
+        let p = ring_position(k);
+        if k == bridge || p[0] < m[0] || !in_triangle([m, hit_point, target], p) {
+            continue;
+        }
+        if !locally_inside(positions, ring, k, m) {
+            continue;
+        }
+        let dx = p[0] - m[0];
+        let key = ((p[1] - m[1]).abs() / dx, dx);
+        if dx > 0.0 && key < best {
+            best = key;
+            bridge = k;
+        }
+    }
+
+    let detour: Vec<usize> = (0..=hole.len())
+        .map(|k| hole[(start + k) % hole.len()])
+        .chain([ring[bridge]])
+        .collect();
+    ring.splice(bridge + 1..bridge + 1, detour);
+    true
+}
+
+/// Whether `p` is inside the ring, by counting how many of its edges a ray from `p` to the right crosses.
+/// Bridges to holes already joined are crossed both ways, so they don't change the answer.
+fn inside_ring(positions: &[Point], ring: &[usize], p: Point) -> bool {
+    let mut inside = false;
+    for k in 0..ring.len() {
+        let [a, b] = [ring[k], ring[(k + 1) % ring.len()]].map(|i| positions[i]);
+        if (a[1] > p[1]) != (b[1] > p[1])
+            && p[0] < a[0] + (p[1] - a[1]) * (b[0] - a[0]) / (b[1] - a[1])
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Whether a point is on the inside of the corner of the ring at position `k`.
+fn locally_inside(positions: &[Point], ring: &[usize], k: usize, p: Point) -> bool {
+    let n = ring.len();
+    let [a, corner, b] = [ring[(k + n - 1) % n], ring[k], ring[(k + 1) % n]].map(|i| positions[i]);
+    let [left_of_in, left_of_out] = [orient(a, corner, p), orient(corner, b, p)];
+    if orient(a, corner, b) >= 0.0 {
+        left_of_in >= 0.0 && left_of_out >= 0.0
+    } else {
+        left_of_in >= 0.0 || left_of_out >= 0.0
+    }
+}
+
+/// Repeatedly cuts off corners of the counterclockwise `ring` that don't have any of it inside them, until only a triangle is left.
+fn clip_ears(positions: &[Point], mut ring: Vec<usize>) -> Result<Vec<[usize; 3]>, PolygonError> {
+    let mut triangles = vec![];
+    let mut k = 0;
+    // Corners checked since the last one was cut off, once it's all of them the ring is stuck.
+    let mut checked = 0;
+    while ring.len() > 3 {
+        let n = ring.len();
+        let corner = [ring[(k + n - 1) % n], ring[k], ring[(k + 1) % n]];
+        if is_ear(positions, &ring, corner) {
+            let [prev, corner, next] = corner;
+            triangles.push([prev, next, corner]);
+            ring.remove(k);
+            k %= ring.len();
+            checked = 0;
+            continue;
+        }
+        k = (k + 1) % n;
+        checked += 1;
+        if checked < n {
+            continue;
+        }
+        // Corners with no area, e.g. points in the middle of a straight edge or along a bridge, can be dropped without leaving a gap.
+        let flat = (0..n).find(|&k| {
+            let [prev, corner, next] =
+                [ring[(k + n - 1) % n], ring[k], ring[(k + 1) % n]].map(|i| positions[i]);
+            orient(prev, corner, next) == 0.0
+        });
+        let Some(flat) = flat else {
+            return Err(PolygonError::Unclipped(n));
+        };
+        ring.remove(flat);
+        k = flat % ring.len();
+        checked = 0;
+    }
+    if let [prev, corner, next] = ring[..] {
+        if orient(positions[prev], positions[corner], positions[next]) > 0.0 {
+            triangles.push([prev, next, corner]);
+        }
+    }
+    Ok(triangles)
+}
+
+/// Whether a counterclockwise corner of the ring can be cut off, it must turn left and have no other vertex of the ring in it.
+fn is_ear(positions: &[Point], ring: &[usize], corner: [usize; 3]) -> bool {
+    let triangle = corner.map(|i| positions[i]);
+    if orient(triangle[0], triangle[1], triangle[2]) <= 0.0 {
+        return false;
+    }
+    // Bridges visit vertices twice, the copies of the corner's own vertices aren't in the way.
+    !ring.iter().any(|&i| {
+        let p = positions[i];
+        !corner.contains(&i) && !triangle.contains(&p) && in_triangle(triangle, p)
+    })
+}
+
+/// Whether `p` is inside a triangle or on its edges, whichever way it winds.
+fn in_triangle([a, b, c]: [Point; 3], p: Point) -> bool {
+    let sides = [orient(a, b, p), orient(b, c, p), orient(c, a, p)];
+    sides.iter().all(|&side| side >= 0.0) || sides.iter().all(|&side| side <= 0.0)
+}
+
+/// Positive if `a`, `b` and `c` go counterclockwise, negative if clockwise and zero if they are on a line.
+fn orient(a: Point, b: Point, c: Point) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::TAU;
+
+    use glam::{vec2, Vec2};
+    use proptest::{collection::vec, prop_assert, prop_assert_eq, proptest};
+
+    use super::*;
+    use crate::mesh::{
+        ops::Extrude,
+        test_util::{tetrahedron_sign, triangle_sign},
+        TetrahedronMesh3D,
+    };
+
+    fn loop_area(points: &[Vec2]) -> f32 {
+        (0..points.len())
+            .map(|k| points[k].perp_dot(points[(k + 1) % points.len()]))
+            .sum::<f32>()
+            .abs()
+            / 2.0
+    }
+
+    /// Checks every triangle has the same handedness as a rectangle, and together they cover the polygon exactly once.
+    fn assert_covers(mesh: &TriangleMesh2D<Vec2>, outline: &[Vec2], holes: &[Vec<Vec2>]) {
+        for simplex in &mesh.simplexes {
+            assert_eq!(
+                triangle_sign(simplex.map(|i| mesh.vertices[i].position)),
+                1.0,
+                "{simplex:?}"
+            );
+        }
+        let expected = loop_area(outline) - holes.iter().map(|hole| loop_area(hole)).sum::<f32>();
+        assert!((dbg!(mesh.area()) - expected).abs() < 1e-3 * expected);
+    }
+
+    fn square(center: Vec2, size: f32) -> Vec<Vec2> {
+        [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .map(|(x, y)| center + vec2(x, y) * size / 2.0)
+            .to_vec()
+    }
+
+    /// Star with `points` points, alternating between the inner and outer radius.
+    fn star(points: usize, inner: f32, outer: f32) -> Vec<Vec2> {
+        (0..points * 2)
+            .map(|k| {
+                let radius = if k % 2 == 0 { outer } else { inner };
+                Vec2::from_angle(TAU * k as f32 / (points * 2) as f32) * radius
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rectangle_outline_matches_rectangle() {
+        let outline: Vec<_> = TriangleMesh2D::<Vec2>::rectangle(vec2(2.0, 3.0))
+            .vertices
+            .iter()
+            .map(|v| v.position)
+            .collect();
+
+        let got = TriangleMesh2D::polygon(&outline, &[]).unwrap();
+
+        assert_eq!(got.simplexes.len(), 2);
+        assert_covers(&got, &outline, &[]);
+    }
+
+    #[test]
+    fn fills_concave_star_either_way_round() {
+        let mut outline = star(5, 0.4, 1.0);
+
+        for _ in 0..2 {
+            let got = TriangleMesh2D::polygon(&outline, &[]).unwrap();
+
+            assert_eq!(got.simplexes.len(), outline.len() - 2);
+            assert_covers(&got, &outline, &[]);
+            outline.reverse();
+        }
+    }
+
+    #[test]
+    fn cuts_out_holes() {
+        let outline = square(Vec2::ZERO, 10.0);
+        let mut holes = vec![
+            square(vec2(-3.0, 0.0), 1.0),
+            square(vec2(0.0, 0.0), 1.0),
+            star(4, 0.5, 1.5),
+        ];
+        holes[2].iter_mut().for_each(|p| *p += vec2(3.0, 0.0));
+        holes[0].reverse();
+
+        let got = TriangleMesh2D::polygon(&outline, &holes).unwrap();
+
+        let vertex_count = outline.len() + holes.iter().map(Vec::len).sum::<usize>();
+        assert_eq!(got.vertices.len(), vertex_count);
+        // Each hole adds a bridge, which is two more edges around the ring.
+        assert_eq!(got.simplexes.len(), vertex_count + 2 * holes.len() - 2);
+        assert_covers(&got, &outline, &holes);
+    }
+
+    #[test]
+    fn bridges_hole_past_notch_in_outline() {
+        let outline = [
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 4.0),
+            (4.0, 5.2),
+            (10.0, 6.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+        ]
+        .map(|(x, y)| vec2(x, y));
+        let holes = [square(vec2(2.0, 5.0), 1.0)];
+
+        let got = TriangleMesh2D::polygon(&outline, &holes).unwrap();
+
+        assert_covers(&got, &outline, &holes);
+    }
+
+    #[test]
+    fn fills_outline_with_points_along_its_edges() {
+        let outline: Vec<_> = [
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (2.0, 2.0),
+            (1.0, 1.0),
+            (0.0, 2.0),
+            (0.0, 1.0),
+        ]
+        .map(|(x, y)| vec2(x, y))
+        .to_vec();
+
+        let got = TriangleMesh2D::polygon(&outline, &[]).unwrap();
+
+        assert_covers(&got, &outline, &[]);
+    }
+
+    #[test]
+    fn rejects_empty_outline_and_hole() {
+        let line = vec![Vec2::ZERO, Vec2::X, Vec2::X * 2.0];
+
+        assert!(matches!(
+            TriangleMesh2D::polygon(&line, &[]),
+            Err(PolygonError::EmptyOutline)
+        ));
+        assert!(matches!(
+            TriangleMesh2D::polygon(&square(Vec2::ZERO, 4.0), &[square(Vec2::ZERO, 1.0), line]),
+            Err(PolygonError::EmptyHole(1))
+        ));
+    }
+
+    #[test]
+    fn rejects_hole_outside_outline() {
+        // A ray to the right from the hole on the left still hits the outline.
+        for x in [5.0, -5.0] {
+            let holes = [square(Vec2::ZERO, 1.0), square(vec2(x, 0.0), 1.0)];
+
+            let got = dbg!(TriangleMesh2D::polygon(&square(Vec2::ZERO, 4.0), &holes));
+
+            assert!(matches!(got, Err(PolygonError::HoleOutside(1))));
+        }
+    }
+
+    #[test]
+    fn extrudes_like_rectangle() {
+        let outline = star(6, 1.0, 2.0);
+        let holes = [square(Vec2::ZERO, 0.5)];
+        let area = TriangleMesh2D::polygon(&outline, &holes).unwrap().area();
+
+        let got: TetrahedronMesh3D<glam::Vec3> = TriangleMesh2D::polygon(&outline, &holes)
+            .unwrap()
+            .extrude(3.0);
+
+        assert!((got.volume() - area * 3.0).abs() < 1e-3);
+        let rectangle_sign = {
+            let prism = TetrahedronMesh3D::<glam::Vec3>::rectangular_prism(glam::Vec3::ONE);
+            tetrahedron_sign(prism.simplexes[0].map(|i| prism.vertices[i].position))
+        };
+        for simplex in &got.simplexes {
+            assert_eq!(
+                tetrahedron_sign(simplex.map(|i| got.vertices[i].position)),
+                rectangle_sign
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn fills_star_shaped_outline_around_hole(radii in vec(1.5f32..3.0, 4..40), hole_size in 0.1f32..1.0) {
+            let outline: Vec<_> = radii
+                .iter()
+                .enumerate()
+                .map(|(k, radius)| Vec2::from_angle(TAU * k as f32 / radii.len() as f32) * *radius)
+                .collect();
+            let holes = [square(Vec2::ZERO, hole_size)];
+
+            let got = TriangleMesh2D::polygon(&outline, &holes).unwrap();
+
+            prop_assert_eq!(got.simplexes.len(), outline.len() + 4);
+            let expected = loop_area(&outline) - hole_size * hole_size;
+            prop_assert!((got.area() - expected).abs() < 1e-3 * expected);
+            for simplex in &got.simplexes {
+                prop_assert_eq!(triangle_sign(simplex.map(|i| got.vertices[i].position)), 1.0);
+            }
+        }
+    }
+}
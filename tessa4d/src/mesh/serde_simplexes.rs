@@ -0,0 +1,69 @@
+//! Serde `with` module for lists of simplexes, serde only implements arrays up to a fixed size rather than for any `N`.
+//! Each simplex is a sequence of exactly `N` vertex indices.
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+pub fn serialize<S: Serializer, const N: usize>(
+    simplexes: &[[usize; N]],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(simplexes.len()))?;
+    for simplex in simplexes {
+        seq.serialize_element(&Simplex(*simplex))?;
+    }
+    seq.end()
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<Vec<[usize; N]>, D::Error> {
+    let simplexes = Vec::<Simplex<N>>::deserialize(deserializer)?;
+    Ok(simplexes.into_iter().map(|simplex| simplex.0).collect())
+}
+
+struct Simplex<const N: usize>([usize; N]);
+
+impl<const N: usize> Serialize for Simplex<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(N))?;
+        for index in &self.0 {
+            seq.serialize_element(index)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Simplex<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(SimplexVisitor(PhantomData))
+    }
+}
+
+struct SimplexVisitor<const N: usize>(PhantomData<[usize; N]>);
+
+impl<'de, const N: usize> Visitor<'de> for SimplexVisitor<N> {
+    type Value = Simplex<N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a simplex with {N} vertex indices")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut simplex = [0; N];
+        for (i, index) in simplex.iter_mut().enumerate() {
+            *index = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+        Ok(Simplex(simplex))
+    }
+}
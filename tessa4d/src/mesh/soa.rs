@@ -0,0 +1,254 @@
+//! Structure-of-arrays storage for 4D meshes.
+//!
+//! [SimplexMesh] keeps each vertex as a struct, which is convenient but interleaves the x, y, z and w components in memory.
+//! [SoaMesh4D] keeps each component in its own contiguous array instead, so the hot loops of transforming and cross-sectioning
+//! large meshes run over plain `f32` slices. These are ordinary scalar loops, written so the compiler can auto-vectorize them.
+//! Use a [SoaCrossSectionSlicer](super::ops::SoaCrossSectionSlicer) to cross-section the same mesh repeatedly.
+
+use std::marker::PhantomData;
+
+use super::{SimplexMesh, Vertex4};
+use crate::{
+    linear_algebra::{Matrix4, Vector4},
+    transform::affine4::Affine4,
+};
+
+/// Mesh of 4D vertices made of N-simplexes, storing the vertex positions as separate arrays of x, y, z and w components.
+/// Convert to and from [SimplexMesh] with `From`, the vertices and simplexes keep their order.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoaMesh4D<V, const N: usize> {
+    pub x: Vec<f32>,
+    pub y: Vec<f32>,
+    pub z: Vec<f32>,
+    pub w: Vec<f32>,
+    /// Indices into the component arrays representing the vertices of each N-simplex in the mesh.
+    #[cfg_attr(feature = "serde", serde(with = "super::serde_simplexes"))]
+    pub simplexes: Vec<[usize; N]>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    vector: PhantomData<V>,
+}
+
+pub type SoaTriangleMesh4D<V> = SoaMesh4D<V, 3>;
+pub type SoaTetrahedronMesh4D<V> = SoaMesh4D<V, 4>;
+
+impl<V, const N: usize> Default for SoaMesh4D<V, N> {
+    fn default() -> Self {
+        Self {
+            x: vec![],
+            y: vec![],
+            z: vec![],
+            w: vec![],
+            simplexes: vec![],
+            vector: PhantomData,
+        }
+    }
+}
+
+impl<V: Vector4, const N: usize> SoaMesh4D<V, N> {
+    /// Number of vertices in the mesh.
+    pub fn vertex_count(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Position of the vertex at `index`.
+    pub fn position(&self, index: usize) -> V {
+        V::new(self.x[index], self.y[index], self.z[index], self.w[index])
+    }
+
+    /// Iterates over the positions of all vertices in order.
+    pub fn positions(&self) -> impl ExactSizeIterator<Item = V> + '_ {
+        (0..self.vertex_count()).map(|i| self.position(i))
+    }
+
+    /// Adds a vertex to the end of the component arrays.
+    pub fn push_vertex(&mut self, position: V) {
+        self.x.push(position.x());
+        self.y.push(position.y());
+        self.z.push(position.z());
+        self.w.push(position.w());
+    }
+
+    /// Applies an affine transform to all vertices in the mesh in place.
+    /// Takes anything that converts to an [Affine4], e.g. a [Rotor4](crate::transform::rotor4::Rotor4) or [RotateScaleTranslate4](crate::transform::rotate_scale_translate4::RotateScaleTranslate4).
+    pub fn apply_transform<T: Copy + Into<Affine4<V>>>(&mut self, transform: &T) -> &mut Self {
+        let transform: Affine4<V> = (*transform).into();
+        let [mx, my, mz, mw] = transform.linear.to_cols_array();
+        let translation = transform.translation;
+        let t = [
+            translation.x(),
+            translation.y(),
+            translation.z(),
+            translation.w(),
+        ];
+        let xs = self.x.iter_mut();
+        let ys = self.y.iter_mut();
+        let zs = self.z.iter_mut();
+        let ws = self.w.iter_mut();
+        for (((x, y), z), w) in xs.zip(ys).zip(zs).zip(ws) {
+            let (px, py, pz, pw) = (*x, *y, *z, *w);
+            // Columns of the linear part scale each input component.
+            *x = mx[0] * px + my[0] * py + mz[0] * pz + mw[0] * pw + t[0];
+            *y = mx[1] * px + my[1] * py + mz[1] * pz + mw[1] * pw + t[1];
+            *z = mx[2] * px + my[2] * py + mz[2] * pz + mw[2] * pw + t[2];
+            *w = mx[3] * px + my[3] * py + mz[3] * pz + mw[3] * pw + t[3];
+        }
+        self
+    }
+
+    /// Writes whether each vertex has a w component above `depth` into `output`, replacing its contents.
+    pub fn classify_depths(&self, depth: f32, output: &mut Vec<bool>) {
+        output.clear();
+        output.extend(self.w.iter().map(|&w| w > depth));
+    }
+
+    /// Points where each edge crosses the hyperplane `w = 0`, projected into 3D, appended to `output` as separate x, y and z arrays.
+    /// Each edge must have one end on either side of the hyperplane, otherwise the result is meaningless.
+    pub fn interpolate_edges(&self, edges: &[(usize, usize)], output: &mut [Vec<f32>; 3]) {
+        const LANES: usize = 8;
+        for chunk in edges.chunks(LANES) {
+            // Gather each chunk into fixed size arrays, so the loops below have a constant length the compiler can unroll and auto-vectorize.
+            let mut fraction = [0.0; LANES];
+            for (lane, &(i, j)) in chunk.iter().enumerate() {
+                fraction[lane] = self.w[i] / (self.w[i] - self.w[j]);
+            }
+            for (component, output) in [&self.x, &self.y, &self.z]
+                .into_iter()
+                .zip(output.iter_mut())
+            {
+                let mut start = [0.0; LANES];
+                let mut end = [0.0; LANES];
+                for (lane, &(i, j)) in chunk.iter().enumerate() {
+                    start[lane] = component[i];
+                    end[lane] = component[j];
+                }
+                let mut interpolated = [0.0; LANES];
+                for lane in 0..LANES {
+                    interpolated[lane] =
+                        start[lane] * (1.0 - fraction[lane]) + end[lane] * fraction[lane];
+                }
+                output.extend_from_slice(&interpolated[..chunk.len()]);
+            }
+        }
+    }
+}
+
+impl<V: Vector4, const N: usize> From<&SimplexMesh<Vertex4<V>, N>> for SoaMesh4D<V, N> {
+    fn from(mesh: &SimplexMesh<Vertex4<V>, N>) -> Self {
+        let mut soa = Self {
+            simplexes: mesh.simplexes.clone(),
+            ..Default::default()
+        };
+        for vertex in &mesh.vertices {
+            soa.push_vertex(vertex.position);
+        }
+        soa
+    }
+}
+
+impl<V: Vector4, const N: usize> From<SimplexMesh<Vertex4<V>, N>> for SoaMesh4D<V, N> {
+    fn from(mesh: SimplexMesh<Vertex4<V>, N>) -> Self {
+        Self::from(&mesh)
+    }
+}
+
+impl<V: Vector4, const N: usize> From<SoaMesh4D<V, N>> for SimplexMesh<Vertex4<V>, N> {
+    fn from(mesh: SoaMesh4D<V, N>) -> Self {
+        Self {
+            vertices: mesh
+                .positions()
+                .map(|position| Vertex4 { position })
+                .collect(),
+            simplexes: mesh.simplexes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec4;
+
+    use super::*;
+    use crate::{
+        mesh::TetrahedronMesh4D,
+        transform::{
+            rotate_scale_translate4::RotateScaleTranslate4,
+            rotor4::{Bivec4, Rotor4},
+            traits::Transform,
+        },
+    };
+
+    const EPS: f32 = 1e-5;
+
+    #[test]
+    fn round_trip_keeps_vertices_and_simplexes() {
+        let mesh = TetrahedronMesh4D::<Vec4>::tesseract_cube(2.0);
+
+        let got = TetrahedronMesh4D::from(SoaTetrahedronMesh4D::from(&mesh));
+
+        assert_eq!(got.simplexes, mesh.simplexes);
+        for (got, expected) in got.vertices.iter().zip(&mesh.vertices) {
+            assert_eq!(got.position, expected.position);
+        }
+    }
+
+    #[test]
+    fn apply_transform_matches_array_of_structs() {
+        let transform = RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xy: 0.4,
+                xw: -0.9,
+                zw: 1.3,
+                ..Bivec4::ZERO
+            }),
+            scale: 1.5,
+            translation: Vec4::new(1.0, -2.0, 3.0, 0.5),
+        };
+        let mut mesh = TetrahedronMesh4D::<Vec4>::tesseract_cube(1.0);
+        let mut soa = SoaTetrahedronMesh4D::from(&mesh);
+
+        mesh.apply_transform(&transform);
+        soa.apply_transform(&transform);
+
+        for (i, vertex) in mesh.vertices.iter().enumerate() {
+            assert!(dbg!(soa.position(i)).abs_diff_eq(dbg!(vertex.position), EPS));
+        }
+    }
+
+    #[test]
+    fn apply_rotor_matches_transform() {
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xz: 0.7,
+            wy: -0.2,
+            ..Bivec4::ZERO
+        });
+        let mesh = TetrahedronMesh4D::<Vec4>::tesseract_cube(1.0);
+        let mut soa = SoaTetrahedronMesh4D::from(&mesh);
+
+        soa.apply_transform(&rotor);
+
+        for (i, vertex) in mesh.vertices.iter().enumerate() {
+            let expected = rotor.transform(vertex.position);
+            assert!(soa.position(i).abs_diff_eq(expected, EPS));
+        }
+    }
+
+    #[test]
+    fn interpolate_edges_finds_crossing_points() {
+        let mut soa = SoaTriangleMesh4D::<Vec4>::default();
+        soa.push_vertex(Vec4::new(0.0, 0.0, 0.0, -1.0));
+        soa.push_vertex(Vec4::new(2.0, 4.0, -2.0, 1.0));
+        soa.push_vertex(Vec4::new(1.0, 1.0, 1.0, 3.0));
+        // More edges than lanes, to cover the partial last chunk.
+        let edges = [(0, 1), (0, 2), (1, 0)].repeat(4);
+        let mut output = [vec![], vec![], vec![]];
+
+        soa.interpolate_edges(&edges, &mut output);
+
+        let got = |i: usize| [output[0][i], output[1][i], output[2][i]];
+        assert_eq!(output[0].len(), edges.len());
+        assert_eq!(got(0), [1.0, 2.0, -1.0]);
+        assert_eq!(got(1), [0.25, 0.25, 0.25]);
+        assert_eq!(got(11), [1.0, 2.0, -1.0]);
+    }
+}
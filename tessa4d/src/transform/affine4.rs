@@ -0,0 +1,459 @@
+use crate::{
+    linear_algebra::{Matrix4, Vector4},
+    util::lerp,
+};
+
+use super::{
+    rotate_scale_translate4::RotateScaleTranslate4,
+    rotor4::Rotor4,
+    scale_rotate_translate4::ScaleRotateTranslate4,
+    traits::{Compose, InterpolateWith, Transform, TryInverse},
+};
+
+/// General affine transform, a linear map followed by a translation.
+/// Unlike [RotateScaleTranslate4] it can stretch along arbitrary axes and shear, but it is only invertible when the linear part is.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Affine4<V: Vector4> {
+    /// Linear part of the transform.
+    pub linear: V::Matrix4,
+    pub translation: V,
+}
+
+impl<V: Vector4> Affine4<V> {
+    pub const IDENTITY: Self = Self {
+        linear: V::Matrix4::IDENTITY,
+        translation: V::ZERO,
+    };
+
+    /// Makes a transform that scales each axis by the matching component of `scale`.
+    pub fn from_scale(scale: V) -> Self {
+        let mut linear = V::Matrix4::IDENTITY.to_cols_array();
+        linear[0][0] = scale.x();
+        linear[1][1] = scale.y();
+        linear[2][2] = scale.z();
+        linear[3][3] = scale.w();
+        Self {
+            linear: V::Matrix4::from_cols_array(linear),
+            translation: V::ZERO,
+        }
+    }
+
+    /// Returns a matrix that represents the linear part of this transform.
+    pub fn get_linear_matrix(&self) -> V::Matrix4 {
+        self.linear
+    }
+
+    /// Same transform as a 5x5 matrix acting on homogeneous coordinates `(x, y, z, w, 1)`, in column-major order.
+    /// The last column is the translation, and the last row is always `(0, 0, 0, 0, 1)`.
+    pub fn to_homogeneous_array(&self) -> [[f32; 5]; 5] {
+        let mut arr = [[0.0; 5]; 5];
+        for (col, linear_col) in arr.iter_mut().zip(self.linear.to_cols_array()) {
+            col[..4].copy_from_slice(&linear_col);
+        }
+        let translation = self.translation;
+        arr[4][..4].copy_from_slice(&[
+            translation.x(),
+            translation.y(),
+            translation.z(),
+            translation.w(),
+        ]);
+        arr[4][4] = 1.0;
+        arr
+    }
+
+    /// Inverse of [Affine4::to_homogeneous_array], takes a column-major 5x5 matrix.
+    /// Returns None if the last row isn't `(0, 0, 0, 0, 1)`, i.e. the matrix is a projection rather than an affine transform.
+    pub fn from_homogeneous_array(arr: [[f32; 5]; 5]) -> Option<Self> {
+        let last_row_affine = arr
+            .iter()
+            .enumerate()
+            .all(|(col, column)| column[4] == if col == 4 { 1.0 } else { 0.0 });
+        if !last_row_affine {
+            return None;
+        }
+        Some(Self {
+            linear: V::Matrix4::from_cols_array(std::array::from_fn(|col| {
+                std::array::from_fn(|row| arr[col][row])
+            })),
+            translation: V::new(arr[4][0], arr[4][1], arr[4][2], arr[4][3]),
+        })
+    }
+
+    /// Determinant of the linear part, the factor this transform scales hypervolumes by.
+    /// Negative if the transform is a reflection.
+    pub fn determinant(&self) -> f32 {
+        self.linear.determinant()
+    }
+
+    /// Splits this transform back into rotation, uniform scale and translation.
+    /// Returns None if the linear part is not a rotation times a positive uniform scale (e.g. it shears, stretches or reflects).
+    pub fn try_decompose(&self) -> Option<RotateScaleTranslate4<V>> {
+        RotateScaleTranslate4::from_rotate_scale_array(
+            self.linear.to_cols_array(),
+            self.translation,
+        )
+        .ok()
+    }
+
+    /// Closest [ScaleRotateTranslate4] to this transform, exact unless the linear part shears.
+    /// Takes the rotation from the polar decomposition and the scale from the diagonal of the remaining stretch.
+    /// Reflections come out as a negative x scale. Returns None if the linear part is singular.
+    pub fn approx_scale_rotate_translate(&self) -> Option<ScaleRotateTranslate4<V>> {
+        let reflected = self.determinant() < 0.0;
+        let linear = if reflected {
+            self.linear * Self::from_scale(V::new(-1.0, 1.0, 1.0, 1.0)).linear
+        } else {
+            self.linear
+        };
+        let (rotation, stretch) = polar_decompose(linear)?;
+        let stretch = stretch.to_cols_array();
+        let x_scale = if reflected {
+            -stretch[0][0]
+        } else {
+            stretch[0][0]
+        };
+        Some(ScaleRotateTranslate4 {
+            scale: V::new(x_scale, stretch[1][1], stretch[2][2], stretch[3][3]),
+            rotation,
+            translation: self.translation,
+        })
+    }
+}
+
+impl<V: Vector4> Default for Affine4<V> {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl<V: Vector4> From<Rotor4> for Affine4<V> {
+    fn from(value: Rotor4) -> Self {
+        Self {
+            linear: value.into_mat4(),
+            translation: V::ZERO,
+        }
+    }
+}
+
+impl<V: Vector4> From<RotateScaleTranslate4<V>> for Affine4<V> {
+    fn from(value: RotateScaleTranslate4<V>) -> Self {
+        Self {
+            linear: value.get_rotate_scale_matrix(),
+            translation: value.translation,
+        }
+    }
+}
+
+impl<V: Vector4> Transform<V> for Affine4<V> {
+    fn transform(&self, operand: V) -> V {
+        self.linear * operand + self.translation
+    }
+}
+
+impl<V: Vector4> Compose<Affine4<V>> for Affine4<V> {
+    type Composed = Affine4<V>;
+    fn compose(&self, other: Affine4<V>) -> Self::Composed {
+        Self {
+            linear: other.linear * self.linear,
+            translation: other.transform(self.translation),
+        }
+    }
+}
+
+impl<V: Vector4> Compose<RotateScaleTranslate4<V>> for Affine4<V> {
+    type Composed = Affine4<V>;
+    fn compose(&self, other: RotateScaleTranslate4<V>) -> Self::Composed {
+        self.compose(Affine4::from(other))
+    }
+}
+
+impl<V: Vector4> Compose<Affine4<V>> for RotateScaleTranslate4<V> {
+    type Composed = Affine4<V>;
+    fn compose(&self, other: Affine4<V>) -> Self::Composed {
+        Affine4::from(*self).compose(other)
+    }
+}
+
+impl<V: Vector4> TryInverse for Affine4<V> {
+    type Inverted = Self;
+    fn try_inverse(&self) -> Option<Self::Inverted> {
+        let linear = self.linear.try_inverse()?;
+        Some(Self {
+            linear,
+            translation: linear * self.translation * -1.0,
+        })
+    }
+}
+
+impl<V: Vector4> InterpolateWith for Affine4<V> {
+    type Scalar = f32;
+    /// Interpolates rotation and stretch separately (polar decomposition) so rotating transforms don't collapse halfway.
+    /// Falls back to interpolating the matrices directly if either transform reflects or is singular.
+    fn interpolate_with(&self, other: &Self, fraction: f32) -> Self {
+        let translation = lerp(self.translation, other.translation, fraction);
+        let linear = match (polar_decompose(self.linear), polar_decompose(other.linear)) {
+            (Some((rotation1, stretch1)), Some((rotation2, stretch2))) => {
+                let rotation: V::Matrix4 =
+                    rotation1.interpolate_with(&rotation2, fraction).into_mat4();
+                rotation * lerp(stretch1, stretch2, fraction)
+            }
+            _ => lerp(self.linear, other.linear, fraction),
+        };
+        Self {
+            linear,
+            translation,
+        }
+    }
+}
+
+/// Splits `m` into a rotation and a symmetric stretch with m = rotation * stretch.
+/// Returns None if `m` is singular or a reflection, where the rotation would not be a proper one.
+fn polar_decompose<M: Matrix4>(m: M) -> Option<(Rotor4, M)> {
+    const MAX_ITERATIONS: usize = 32;
+    if m.determinant() <= 0.0 {
+        return None;
+    }
+    // Newton iteration R <- (R + R^-T) / 2 converges quadratically to the rotation.
+    let mut rotation = m;
+    for _ in 0..MAX_ITERATIONS {
+        let next = lerp(rotation, rotation.try_inverse()?.transpose(), 0.5);
+        let change: f32 = next
+            .to_cols_array()
+            .iter()
+            .flatten()
+            .zip(rotation.to_cols_array().iter().flatten())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        rotation = next;
+        if change < 1e-6 {
+            break;
+        }
+    }
+    let stretch = rotation.transpose() * m;
+    Some((
+        Rotor4::from_mat4_array(rotation.to_cols_array()).ok()?,
+        stretch,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::proptest;
+    use std::f32::consts::PI;
+
+    use crate::{
+        mesh::TetrahedronMesh4D,
+        transform::rotor4::{
+            test_util::{arbitrary_rotor4, rotor_approx_equal},
+            Bivec4,
+        },
+        util::{approx_equal, test::proptest::vec4_uniform},
+    };
+
+    const EPS: f32 = 1e-3;
+
+    fn shear() -> Affine4<glam::Vec4> {
+        Affine4 {
+            linear: glam::Mat4::from_cols_array_2d(&[
+                [1.0, 0.0, 0.0, 0.0],
+                [0.5, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 2.0, 0.0],
+                [0.0, 0.0, 1.0, 1.0],
+            ]),
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        }
+    }
+
+    #[test]
+    fn transform_matches_matrix() {
+        let transform = shear();
+        let vector = glam::vec4(5.0, 6.0, 7.0, 8.0);
+        let expected = transform.get_linear_matrix() * vector + transform.translation;
+        dbg!(expected);
+
+        let got = dbg!(transform.transform(vector));
+
+        assert!(got.abs_diff_eq(expected, EPS));
+        assert!(got.abs_diff_eq(glam::vec4(9.0, 8.0, 25.0, 12.0), EPS));
+    }
+
+    #[test]
+    fn homogeneous_array_round_trips() {
+        let transform = shear();
+
+        let arr = dbg!(transform.to_homogeneous_array());
+        let got = Affine4::<glam::Vec4>::from_homogeneous_array(arr).unwrap();
+
+        assert_eq!(arr[4], [1.0, 2.0, 3.0, 4.0, 1.0]);
+        assert_eq!(got.linear, transform.linear);
+        assert_eq!(got.translation, transform.translation);
+    }
+
+    #[test]
+    fn from_homogeneous_array_rejects_projections() {
+        let mut arr = shear().to_homogeneous_array();
+        arr[3][4] = 1.0;
+
+        assert!(Affine4::<glam::Vec4>::from_homogeneous_array(arr).is_none());
+    }
+
+    #[test]
+    fn from_scale_stretches_axes() {
+        let transform = Affine4::from_scale(glam::vec4(1.0, 2.0, 3.0, 4.0));
+        let vector = glam::vec4(1.0, 1.0, -1.0, 0.5);
+        let expected = glam::vec4(1.0, 2.0, -3.0, 2.0);
+
+        let got = dbg!(transform.transform(vector));
+
+        assert!(got.abs_diff_eq(expected, EPS));
+        assert!(approx_equal(transform.determinant(), 24.0, EPS));
+    }
+
+    #[test]
+    fn composed_composes() {
+        let transform1 = shear();
+        let transform2 = Affine4 {
+            linear: Rotor4::from_bivec_angles(Bivec4 {
+                xw: PI / 3.0,
+                ..Bivec4::ZERO
+            })
+            .into_mat4(),
+            translation: glam::vec4(-1.0, 0.0, 1.0, 0.0),
+        };
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+        let expected = transform2.transform(transform1.transform(vector));
+        dbg!(expected);
+
+        let got = dbg!(transform1.compose(transform2).transform(vector));
+
+        assert!(got.abs_diff_eq(expected, EPS));
+    }
+
+    #[test]
+    fn composed_with_rotate_scale_translate_matches_both_orders() {
+        let affine = shear();
+        let rst = RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                yz: PI / 2.0,
+                ..Bivec4::ZERO
+            }),
+            scale: 2.0,
+            translation: glam::vec4(0.0, 1.0, 0.0, -1.0),
+        };
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+
+        let got_after = dbg!(affine.compose(rst).transform(vector));
+        let got_before = dbg!(rst.compose(affine).transform(vector));
+
+        assert!(got_after.abs_diff_eq(rst.transform(affine.transform(vector)), EPS));
+        assert!(got_before.abs_diff_eq(affine.transform(rst.transform(vector)), EPS));
+    }
+
+    #[test]
+    fn try_inverse_undoes_transform() {
+        let transform = shear();
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+
+        let transformed = dbg!(transform.transform(vector));
+        let untransformed = dbg!(transform.try_inverse().unwrap().transform(transformed));
+
+        assert!(untransformed.abs_diff_eq(vector, EPS));
+        assert!(!transformed.abs_diff_eq(vector, EPS));
+    }
+
+    #[test]
+    fn try_inverse_singular_is_none() {
+        let transform = Affine4::from_scale(glam::vec4(1.0, 0.0, 1.0, 1.0));
+
+        assert!(transform.try_inverse().is_none());
+    }
+
+    #[test]
+    fn try_decompose_rejects_stretch_and_reflection() {
+        let stretched = Affine4::from_scale(glam::vec4(1.0, 2.0, 1.0, 1.0));
+        let reflected = Affine4::from_scale(glam::vec4(-1.0, 1.0, 1.0, 1.0));
+
+        assert!(stretched.try_decompose().is_none());
+        assert!(reflected.try_decompose().is_none());
+        assert!(shear().try_decompose().is_none());
+    }
+
+    #[test]
+    fn interpolate_with_keeps_rotation_rigid() {
+        let transform1 = Affine4::<glam::Vec4>::IDENTITY;
+        let transform2 = Affine4 {
+            linear: Rotor4::from_bivec_angles(Bivec4 {
+                xy: PI * 0.9,
+                ..Bivec4::ZERO
+            })
+            .into_mat4(),
+            translation: glam::vec4(2.0, 0.0, 0.0, 0.0),
+        };
+        let expected = Affine4::from(RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xy: PI * 0.45,
+                ..Bivec4::ZERO
+            }),
+            scale: 1.0,
+            translation: glam::vec4(1.0, 0.0, 0.0, 0.0),
+        });
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+
+        let got = dbg!(transform1.interpolate_with(&transform2, 0.5));
+
+        assert!(got
+            .transform(vector)
+            .abs_diff_eq(expected.transform(vector), EPS));
+    }
+
+    #[test]
+    fn interpolate_with_interpolates_stretch() {
+        let transform1 = Affine4::<glam::Vec4>::IDENTITY;
+        let transform2 = Affine4::from_scale(glam::vec4(3.0, 1.0, 1.0, 5.0));
+        let expected = glam::vec4(2.0, 1.0, 1.0, 3.0);
+
+        let got = dbg!(transform1.interpolate_with(&transform2, 0.5));
+
+        assert!(got.transform(glam::Vec4::ONE).abs_diff_eq(expected, EPS));
+    }
+
+    #[test]
+    fn stretched_tesseract_hypervolume_scales_by_determinant() {
+        let transform = shear().compose(Affine4::from_scale(glam::vec4(1.0, 2.0, 3.0, 0.5)));
+        let mut mesh = TetrahedronMesh4D::tesseract(glam::Vec4::ONE);
+
+        mesh.apply_transform(&transform);
+
+        assert!(approx_equal(transform.determinant(), 6.0, EPS));
+        assert!(approx_equal(mesh.hypervolume(), 6.0, EPS));
+    }
+
+    proptest! {
+        #[test]
+        fn decompose_round_trips_rotate_scale_translate(rotation in arbitrary_rotor4(), scale in 0.1f32..10.0, translation in vec4_uniform(10.0), vector in vec4_uniform(4.0)) {
+            let rst = RotateScaleTranslate4 { rotation, scale, translation };
+            let affine = Affine4::from(rst);
+
+            let got = dbg!(affine.try_decompose().unwrap());
+
+            assert!(approx_equal(got.scale, scale, EPS * scale));
+            assert!(got.translation.abs_diff_eq(translation, EPS));
+            assert!(got.transform(vector).abs_diff_eq(rst.transform(vector), EPS * scale.max(1.0) * 4.0));
+            assert!(affine.transform(vector).abs_diff_eq(rst.transform(vector), EPS * scale.max(1.0) * 4.0));
+        }
+
+        #[test]
+        fn decompose_recovers_rotor(rotation in arbitrary_rotor4()) {
+            let affine = Affine4::<glam::Vec4>::from(RotateScaleTranslate4 { rotation, ..RotateScaleTranslate4::IDENTITY });
+
+            let got = dbg!(affine.try_decompose().unwrap().rotation);
+
+            let got_matrix: glam::Mat4 = got.into_mat4();
+            let expected_matrix: glam::Mat4 = rotation.into_mat4();
+            assert!(rotor_approx_equal(got, rotation) || got_matrix.abs_diff_eq(expected_matrix, EPS));
+        }
+    }
+}
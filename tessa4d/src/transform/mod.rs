@@ -1,3 +1,7 @@
+pub mod affine4;
+pub mod projective4;
 pub mod rotate_scale_translate4;
 pub mod rotor4;
+pub mod scale_rotate_translate4;
+pub mod spline;
 pub mod traits;
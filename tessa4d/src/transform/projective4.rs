@@ -0,0 +1,297 @@
+use crate::{linear_algebra::Vector4, util::inverse};
+
+use super::{
+    affine4::Affine4,
+    rotate_scale_translate4::RotateScaleTranslate4,
+    traits::{Compose, Transform, TryInverse},
+};
+
+/// Projective transform in 4D, represented by a 5x5 matrix acting on homogeneous coordinates `(x, y, z, w, 1)`.
+/// Transforming a point divides by the last homogeneous coordinate, which is what makes perspective possible.
+///
+/// Projections follow the same conventions as `glam`'s left-handed ones, except depth is along `w` instead of `z`:
+/// the camera looks along +w, visible points end up with x, y and z in [-1, 1] and w in [0, 1] from the near to the far plane.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Projective4 {
+    /// Homogeneous matrix in column-major order, the last column is the translation.
+    pub matrix: [[f32; 5]; 5],
+}
+
+impl Projective4 {
+    pub const IDENTITY: Self = Self {
+        matrix: [
+            [1.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    /// Perspective projection for a camera at the origin looking along +w.
+    /// `fov_y_radians` is the full vertical field of view, the x and z extents of the view are `aspect_ratio_x` and `aspect_ratio_z` times the y extent.
+    /// Points at `w_near` and `w_far` are mapped to w=0 and w=1.
+    pub fn perspective(
+        fov_y_radians: f32,
+        aspect_ratio_x: f32,
+        aspect_ratio_z: f32,
+        w_near: f32,
+        w_far: f32,
+    ) -> Self {
+        let focal_length = 1.0 / (0.5 * fov_y_radians).tan();
+        let depth_scale = w_far / (w_far - w_near);
+        let mut matrix = [[0.0; 5]; 5];
+        matrix[0][0] = focal_length / aspect_ratio_x;
+        matrix[1][1] = focal_length;
+        matrix[2][2] = focal_length / aspect_ratio_z;
+        matrix[3][3] = depth_scale;
+        // Copies w into the homogeneous coordinate, so everything gets divided by depth.
+        matrix[3][4] = 1.0;
+        matrix[4][3] = -w_near * depth_scale;
+        Self { matrix }
+    }
+
+    /// Orthographic projection that maps the box between `min` and `max` to x, y, z in [-1, 1] and w in [0, 1].
+    /// `min.w()` and `max.w()` are the near and far planes.
+    pub fn orthographic<V: Vector4>(min: V, max: V) -> Self {
+        let mut matrix = Self::IDENTITY.matrix;
+        let (min, max) = (to_array(min), to_array(max));
+        for axis in 0..3 {
+            let size = max[axis] - min[axis];
+            matrix[axis][axis] = 2.0 / size;
+            matrix[4][axis] = -(max[axis] + min[axis]) / size;
+        }
+        let depth = max[3] - min[3];
+        matrix[3][3] = 1.0 / depth;
+        matrix[4][3] = -min[3] / depth;
+        Self { matrix }
+    }
+
+    /// Transforms a point without the perspective divide, returns all 5 homogeneous coordinates.
+    /// The last one is positive for points in front of a perspective camera, zero on the camera plane, and negative behind it.
+    pub fn transform_homogeneous<V: Vector4>(&self, operand: V) -> [f32; 5] {
+        let vector = to_array(operand);
+        std::array::from_fn(|row| {
+            (0..4)
+                .map(|col| self.matrix[col][row] * vector[col])
+                .sum::<f32>()
+                + self.matrix[4][row]
+        })
+    }
+}
+
+impl Default for Projective4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl<V: Vector4> From<Affine4<V>> for Projective4 {
+    fn from(value: Affine4<V>) -> Self {
+        Self {
+            matrix: value.to_homogeneous_array(),
+        }
+    }
+}
+
+impl<V: Vector4> From<RotateScaleTranslate4<V>> for Projective4 {
+    fn from(value: RotateScaleTranslate4<V>) -> Self {
+        Affine4::from(value).into()
+    }
+}
+
+/// Points on the camera plane (last homogeneous coordinate zero) have no projection, they come out infinite or NaN.
+impl<V: Vector4> Transform<V> for Projective4 {
+    fn transform(&self, operand: V) -> V {
+        let [x, y, z, w, h] = self.transform_homogeneous(operand);
+        V::new(x / h, y / h, z / h, w / h)
+    }
+}
+
+impl Compose<Projective4> for Projective4 {
+    type Composed = Projective4;
+    fn compose(&self, other: Projective4) -> Self::Composed {
+        Self {
+            matrix: std::array::from_fn(|col| {
+                std::array::from_fn(|row| {
+                    (0..5)
+                        .map(|k| other.matrix[k][row] * self.matrix[col][k])
+                        .sum()
+                })
+            }),
+        }
+    }
+}
+
+/// Applies an affine transform before projecting, e.g. the inverse of a camera's transform.
+impl<V: Vector4> Compose<Projective4> for Affine4<V> {
+    type Composed = Projective4;
+    fn compose(&self, other: Projective4) -> Self::Composed {
+        Projective4::from(*self).compose(other)
+    }
+}
+
+impl<V: Vector4> Compose<Projective4> for RotateScaleTranslate4<V> {
+    type Composed = Projective4;
+    fn compose(&self, other: Projective4) -> Self::Composed {
+        Projective4::from(*self).compose(other)
+    }
+}
+
+/// The inverse of a projection turns points in clip space back into points in view space, e.g. to cast rays from a camera.
+impl TryInverse for Projective4 {
+    type Inverted = Projective4;
+    fn try_inverse(&self) -> Option<Self::Inverted> {
+        Some(Self {
+            matrix: inverse(self.matrix)?,
+        })
+    }
+}
+
+fn to_array<V: Vector4>(v: V) -> [f32; 4] {
+    [v.x(), v.y(), v.z(), v.w()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use glam::Vec4;
+    use proptest::proptest;
+    use std::f32::consts::PI;
+
+    use crate::{
+        mesh::TetrahedronMesh4D,
+        transform::{
+            rotor4::{test_util::arbitrary_rotor4, Bivec4, Rotor4},
+            traits::Inverse,
+        },
+        util::{approx_equal, test::proptest::vec4_uniform},
+    };
+
+    const EPS: f32 = 1e-3;
+
+    #[test]
+    fn perspective_maps_near_and_far_to_depth_range() {
+        let projection = Projective4::perspective(PI / 2.0, 2.0, 1.0, 1.0, 10.0);
+
+        let near = dbg!(projection.transform(glam::vec4(0.0, 0.0, 0.0, 1.0)));
+        let far = dbg!(projection.transform(glam::vec4(0.0, 0.0, 0.0, 10.0)));
+
+        assert!(near.abs_diff_eq(Vec4::ZERO, EPS));
+        assert!(far.abs_diff_eq(glam::vec4(0.0, 0.0, 0.0, 1.0), EPS));
+    }
+
+    #[test]
+    fn perspective_maps_frustum_edges_to_unit_box() {
+        // 90 degree field of view, so the frustum edges at depth d are at y = +-d.
+        let projection = Projective4::perspective(PI / 2.0, 2.0, 0.5, 1.0, 10.0);
+        let depth = 4.0;
+        let corner = glam::vec4(2.0 * depth, depth, -0.5 * depth, depth);
+
+        let got = dbg!(projection.transform(corner));
+
+        assert!(got.truncate().abs_diff_eq(glam::vec3(1.0, 1.0, -1.0), EPS));
+    }
+
+    #[test]
+    fn perspective_divides_by_depth() {
+        let projection = Projective4::perspective(PI / 2.0, 1.0, 1.0, 1.0, 10.0);
+        let point = glam::vec4(1.0, 1.0, 1.0, 2.0);
+        let further_point = point * 3.0;
+
+        let got = dbg!(projection.transform(point));
+        let got_further = dbg!(projection.transform(further_point));
+
+        assert!(got.truncate().abs_diff_eq(glam::Vec3::splat(0.5), EPS));
+        assert!(got_further.truncate().abs_diff_eq(got.truncate(), EPS));
+        assert!(got_further.w > got.w);
+        assert!(projection.transform_homogeneous(point * -1.0)[4] < 0.0);
+    }
+
+    #[test]
+    fn orthographic_maps_box_to_unit_box() {
+        let min = glam::vec4(-1.0, 2.0, 3.0, 1.0);
+        let max = glam::vec4(3.0, 4.0, 7.0, 5.0);
+        let projection = Projective4::orthographic(min, max);
+
+        let got_min = dbg!(projection.transform(min));
+        let got_max = dbg!(projection.transform(max));
+
+        assert!(got_min.abs_diff_eq(glam::vec4(-1.0, -1.0, -1.0, 0.0), EPS));
+        assert!(got_max.abs_diff_eq(Vec4::ONE, EPS));
+    }
+
+    #[test]
+    fn projecting_mesh_matches_projecting_points() {
+        let projection = Projective4::perspective(PI / 3.0, 1.0, 1.0, 0.1, 100.0);
+        let camera = RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xw: PI / 6.0,
+                ..Bivec4::ZERO
+            }),
+            scale: 1.0,
+            translation: glam::vec4(0.0, 0.0, 0.0, -5.0),
+        };
+        let view_projection = camera.inverse().compose(projection);
+        let mut mesh = TetrahedronMesh4D::tesseract(Vec4::ONE);
+        let expected: Vec<_> = mesh
+            .vertices
+            .iter()
+            .map(|v| projection.transform(camera.inverse().transform(v.position)))
+            .collect();
+
+        mesh.apply_transform(&view_projection);
+
+        for (got, expected) in mesh.vertices.iter().zip(expected) {
+            assert!(got.position.abs_diff_eq(expected, EPS));
+        }
+    }
+
+    #[test]
+    fn try_inverse_unprojects() {
+        let projection = Projective4::perspective(PI / 2.0, 1.5, 0.5, 0.5, 20.0);
+        let point = glam::vec4(1.0, -2.0, 0.5, 3.0);
+
+        let projected = dbg!(projection.transform(point));
+        let got = dbg!(projection.try_inverse().unwrap().transform(projected));
+
+        assert!(got.abs_diff_eq(point, EPS));
+    }
+
+    #[test]
+    fn from_affine_is_same_transform() {
+        let affine = Affine4 {
+            linear: glam::Mat4::from_cols_array_2d(&[
+                [1.0, 0.0, 0.0, 0.0],
+                [0.5, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 2.0, 0.0],
+                [0.0, 0.0, 1.0, 1.0],
+            ]),
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let vector = glam::vec4(5.0, 6.0, 7.0, 8.0);
+
+        let got = dbg!(Projective4::from(affine).transform(vector));
+
+        assert!(got.abs_diff_eq(affine.transform(vector), EPS));
+        assert!(approx_equal(
+            Projective4::from(affine).transform_homogeneous(vector)[4],
+            1.0,
+            EPS
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn compose_same_as_one_then_other(rotation in arbitrary_rotor4(), translation in vec4_uniform(4.0), vector in vec4_uniform(1.0)) {
+            let view = RotateScaleTranslate4 { rotation, scale: 1.0, translation: translation + glam::vec4(0.0, 0.0, 0.0, 10.0) };
+            let projection = Projective4::perspective(PI / 2.0, 1.0, 1.0, 0.1, 100.0);
+
+            let got = dbg!(view.compose(projection).transform(vector));
+
+            assert!(got.abs_diff_eq(projection.transform(view.transform(vector)), EPS));
+        }
+    }
+}
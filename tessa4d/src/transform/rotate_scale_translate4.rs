@@ -1,27 +1,41 @@
 use crate::{
-    linear_algebra::{Matrix4, Vector4},
-    util::lerp,
+    linear_algebra::{Matrix4, Scalar, Vector4},
+    util::{cubic_hermite, determinant, lerp},
 };
 
 use super::{
-    rotor4::Rotor4,
-    traits::{Compose, InterpolateWith, Inverse, Transform},
+    rotor4::{GenericRotor4, RotorError},
+    traits::{Compose, InterpolateSpline, InterpolateWith, Inverse, Transform},
 };
 
+/// Single precision [GenericRotateScaleTranslate4].
+pub type RotateScaleTranslate4<V> = GenericRotateScaleTranslate4<f32, V>;
+/// Double precision [GenericRotateScaleTranslate4], e.g. with `glam::DVec4`.
+pub type DRotateScaleTranslate4<V> = GenericRotateScaleTranslate4<f64, V>;
+
 /// Transform with rotation, uniform scale, and translation.
 /// Applies rotation, then scale, then translation.
+/// Generic over the precision of the rotation and scale, use the [RotateScaleTranslate4] and [DRotateScaleTranslate4] aliases.
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
-pub struct RotateScaleTranslate4<V> {
-    pub rotation: Rotor4,
-    pub scale: f32,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "S: serde::Serialize, V: serde::Serialize",
+        deserialize = "S: Scalar + serde::Deserialize<'de>, V: serde::Deserialize<'de>"
+    ))
+)]
+pub struct GenericRotateScaleTranslate4<S, V> {
+    pub rotation: GenericRotor4<S>,
+    pub scale: S,
     pub translation: V,
 }
 
-impl<V: Vector4> RotateScaleTranslate4<V> {
+impl<S: Scalar, V: Vector4<S>> GenericRotateScaleTranslate4<S, V> {
     pub const IDENTITY: Self = Self {
-        rotation: Rotor4::IDENTITY,
-        scale: 1.0,
+        rotation: GenericRotor4::IDENTITY,
+        scale: S::ONE,
         translation: V::ZERO,
     };
 
@@ -36,8 +50,28 @@ impl<V: Vector4> RotateScaleTranslate4<V> {
         V::Matrix4::from_cols_array(arr)
     }
 
+    /// Inverse of [RotateScaleTranslate4::get_rotate_scale_matrix], takes a column-major matrix and the translation to apply after it.
+    /// Returns [RotorError::NotRotation] if the matrix isn't a rotation times a positive uniform scale, e.g. if it shears or reflects.
+    pub fn from_rotate_scale_array(
+        arr: [[S; 4]; 4],
+        translation: V,
+    ) -> Result<Self, RotorError<S>> {
+        let determinant = determinant(arr);
+        if determinant <= S::ZERO {
+            return Err(RotorError::NotRotation(arr));
+        }
+        let scale = determinant.sqrt().sqrt();
+        let rotation = GenericRotor4::from_mat4_array(arr.map(|col| col.map(|x| x / scale)))
+            .map_err(|_| RotorError::NotRotation(arr))?;
+        Ok(Self {
+            rotation,
+            scale,
+            translation,
+        })
+    }
+
     /// Returns a transform that applies this transform, and then the given rotation.
-    pub fn rotated(&self, rotation: Rotor4) -> Self {
+    pub fn rotated(&self, rotation: GenericRotor4<S>) -> Self {
         Self {
             rotation: self.rotation.compose(rotation),
             scale: self.scale,
@@ -46,7 +80,7 @@ impl<V: Vector4> RotateScaleTranslate4<V> {
     }
 
     /// Returns a transform that applies this transform, and then the given scale.
-    pub fn scaled(&self, scale: f32) -> Self {
+    pub fn scaled(&self, scale: S) -> Self {
         Self {
             rotation: self.rotation,
             scale: self.scale * scale,
@@ -64,29 +98,44 @@ impl<V: Vector4> RotateScaleTranslate4<V> {
     }
 }
 
-impl<V: Vector4> Default for RotateScaleTranslate4<V> {
+impl<S: Scalar, V: Vector4<S>> Default for GenericRotateScaleTranslate4<S, V> {
     fn default() -> Self {
         Self::IDENTITY
     }
 }
 
-impl<V: Vector4> Compose<RotateScaleTranslate4<V>> for RotateScaleTranslate4<V> {
-    type Composed = RotateScaleTranslate4<V>;
-    fn compose(&self, other: RotateScaleTranslate4<V>) -> Self::Composed {
+impl<S: Scalar, V: Vector4<S>> Compose<GenericRotateScaleTranslate4<S, V>>
+    for GenericRotateScaleTranslate4<S, V>
+{
+    type Composed = GenericRotateScaleTranslate4<S, V>;
+    fn compose(&self, other: GenericRotateScaleTranslate4<S, V>) -> Self::Composed {
         self.rotated(other.rotation)
             .scaled(other.scale)
             .translated(other.translation)
     }
 }
 
-impl<V: Vector4> Transform<V> for RotateScaleTranslate4<V> {
+impl<S: Scalar, V: Vector4<S>> Transform<V> for GenericRotateScaleTranslate4<S, V> {
     fn transform(&self, operand: V) -> V {
         self.rotation.transform(operand) * self.scale + self.translation
     }
+
+    /// Builds the rotate-scale matrix once and applies it to every operand.
+    fn transform_each<'a, I>(&self, operands: I)
+    where
+        I: IntoIterator<Item = &'a mut V>,
+        V: 'a,
+    {
+        let matrix = self.get_rotate_scale_matrix();
+        for operand in operands {
+            *operand = matrix * *operand + self.translation;
+        }
+    }
 }
 
-impl<V: Vector4> InterpolateWith for RotateScaleTranslate4<V> {
-    fn interpolate_with(&self, other: &Self, fraction: f32) -> Self {
+impl<S: Scalar, V: Vector4<S>> InterpolateWith for GenericRotateScaleTranslate4<S, V> {
+    type Scalar = S;
+    fn interpolate_with(&self, other: &Self, fraction: S) -> Self {
         Self {
             rotation: self.rotation.interpolate_with(&other.rotation, fraction),
             scale: lerp(self.scale, other.scale, fraction),
@@ -95,11 +144,78 @@ impl<V: Vector4> InterpolateWith for RotateScaleTranslate4<V> {
     }
 }
 
-impl<V: Vector4> Inverse for RotateScaleTranslate4<V> {
+/// Per-keyframe data for splines through [GenericRotateScaleTranslate4]s.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "S: serde::Serialize, V: serde::Serialize",
+        deserialize = "S: Scalar + serde::Deserialize<'de>, V: serde::Deserialize<'de>"
+    ))
+)]
+pub struct RotateScaleTranslateControl4<S, V> {
+    /// Squad control point, see the [InterpolateSpline] impl for [GenericRotor4].
+    pub rotation: GenericRotor4<S>,
+    pub scale_tangent: S,
+    pub translation_tangent: V,
+}
+
+/// Squad for the rotation, and Catmull-Rom curves for the scale and translation.
+impl<S: Scalar, V: Vector4<S>> InterpolateSpline for GenericRotateScaleTranslate4<S, V> {
+    type Scalar = S;
+    type Control = RotateScaleTranslateControl4<S, V>;
+    fn spline_control(previous: &Self, current: &Self, next: &Self) -> Self::Control {
+        // Catmull-Rom tangents are the average of the velocities on either side of the keyframe.
+        let half = S::from_f32(0.5);
+        RotateScaleTranslateControl4 {
+            rotation: GenericRotor4::spline_control(
+                &previous.rotation,
+                &current.rotation,
+                &next.rotation,
+            ),
+            scale_tangent: (next.scale - previous.scale) * half,
+            translation_tangent: (next.translation + previous.translation * -S::ONE) * half,
+        }
+    }
+
+    fn interpolate_spline(
+        &self,
+        control: &Self::Control,
+        other: &Self,
+        other_control: &Self::Control,
+        fraction: S,
+    ) -> Self {
+        Self {
+            rotation: self.rotation.interpolate_spline(
+                &control.rotation,
+                &other.rotation,
+                &other_control.rotation,
+                fraction,
+            ),
+            scale: cubic_hermite(
+                self.scale,
+                control.scale_tangent,
+                other.scale,
+                other_control.scale_tangent,
+                fraction,
+            ),
+            translation: cubic_hermite(
+                self.translation,
+                control.translation_tangent,
+                other.translation,
+                other_control.translation_tangent,
+                fraction,
+            ),
+        }
+    }
+}
+
+impl<S: Scalar, V: Vector4<S>> Inverse for GenericRotateScaleTranslate4<S, V> {
     type Inverted = Self;
     fn inverse(&self) -> Self::Inverted {
         let inverse_rotation = self.rotation.inverse();
-        let inverse_scale = 1.0 / self.scale;
+        let inverse_scale = self.scale.recip();
         Self {
             rotation: inverse_rotation,
             scale: inverse_scale,
@@ -114,7 +230,7 @@ mod test {
     use std::f32::consts::PI;
 
     use crate::{
-        transform::rotor4::{test_util::rotor_approx_equal, Bivec4},
+        transform::rotor4::{test_util::rotor_approx_equal, Bivec4, DBivec4, DRotor4, Rotor4},
         util::approx_equal,
     };
 
@@ -148,6 +264,43 @@ mod test {
         assert!(got_matrix.abs_diff_eq(expected_matrix, EPS));
     }
 
+    #[test]
+    fn from_rotate_scale_array_undoes_get_rotate_scale_matrix() {
+        let transform = RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xz: 0.7,
+                wy: -2.0,
+                ..Bivec4::ZERO
+            }),
+            scale: 3.0,
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let matrix: glam::Mat4 = transform.get_rotate_scale_matrix();
+
+        let got = dbg!(RotateScaleTranslate4::from_rotate_scale_array(
+            matrix.to_cols_array_2d(),
+            transform.translation
+        )
+        .unwrap());
+
+        assert!(approx_equal(got.scale, transform.scale, EPS));
+        // The rotor is only unique up to sign, compare the rotations they apply.
+        let got_matrix: glam::Mat4 = got.get_rotate_scale_matrix();
+        assert!(got_matrix.abs_diff_eq(matrix, EPS));
+    }
+
+    #[test]
+    fn from_rotate_scale_array_rejects_stretch() {
+        let matrix = glam::Mat4::from_diagonal(glam::vec4(1.0, 2.0, 1.0, 1.0));
+
+        let got = RotateScaleTranslate4::from_rotate_scale_array(
+            matrix.to_cols_array_2d(),
+            glam::Vec4::ZERO,
+        );
+
+        assert!(matches!(got, Err(RotorError::NotRotation(_))));
+    }
+
     #[test]
     fn rotated_same_as_rotating_after() {
         let transform = RotateScaleTranslate4 {
@@ -306,4 +459,63 @@ mod test {
         assert!(untransformed.abs_diff_eq(vector, EPS));
         assert!(!transformed.abs_diff_eq(vector, EPS));
     }
+
+    #[test]
+    fn transform_each_matches_transform() {
+        let transform = RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xz: 0.4,
+                wy: 1.1,
+                ..Bivec4::ZERO
+            }),
+            scale: 2.0,
+            translation: glam::vec4(3.0, 4.0, 5.0, 6.0),
+        };
+        let mut vectors = [
+            glam::vec4(1.0, 2.0, 3.0, 4.0),
+            glam::vec4(-1.0, 0.0, 0.5, 2.0),
+        ];
+        let expected = vectors.map(|v| transform.transform(v));
+
+        transform.transform_each(&mut vectors);
+
+        for (got, expected) in vectors.into_iter().zip(expected) {
+            assert!(dbg!(got).abs_diff_eq(expected, EPS));
+        }
+    }
+
+    #[test]
+    fn transforms_through_trait_object() {
+        let transform = RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xy: 0.7,
+                ..Bivec4::ZERO
+            }),
+            scale: 0.5,
+            translation: glam::vec4(1.0, 0.0, -1.0, 2.0),
+        };
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+        let object: &dyn Transform<glam::Vec4> = &transform;
+
+        assert!(dbg!(object.transform(vector)).abs_diff_eq(transform.transform(vector), EPS));
+    }
+
+    #[test]
+    fn double_precision_inverse_undoes_transform() {
+        let transform = DRotateScaleTranslate4 {
+            rotation: DRotor4::from_bivec_angles(DBivec4 {
+                xy: 0.3,
+                zw: -1.2,
+                ..DBivec4::ZERO
+            }),
+            scale: 1e-3,
+            translation: glam::dvec4(1e6, -2e6, 3e6, 4e6),
+        };
+        let vector = glam::dvec4(1.0, 2.0, 3.0, 4.0);
+
+        let transformed = dbg!(transform.transform(vector));
+        let untransformed = dbg!(transform.inverse().transform(transformed));
+
+        assert!(untransformed.abs_diff_eq(vector, 1e-6));
+    }
 }
@@ -1,168 +1,200 @@
-use std::{
-    f32::consts::{FRAC_PI_2, PI},
-    ops::{Add, Mul, Neg, Sub},
-};
+use std::ops::{Add, Mul, Neg, Sub};
 use thiserror::Error;
 
-use super::traits::{Compose, InterpolateWith, Inverse, Transform};
-use crate::linear_algebra::{Matrix4, Vector4};
+use super::traits::{Compose, InterpolateSpline, InterpolateWith, Inverse, Transform};
+use crate::{
+    linear_algebra::{Matrix4, Quaternion, Scalar, Vector4},
+    util::determinant,
+};
 
-const EPSILON: f32 = 1e-3;
+/// Single precision rotor, see [GenericRotor4].
+pub type Rotor4 = GenericRotor4<f32>;
+/// Double precision rotor, see [GenericRotor4].
+pub type DRotor4 = GenericRotor4<f64>;
+/// Single precision bivector, see [GenericBivec4].
+pub type Bivec4 = GenericBivec4<f32>;
+/// Double precision bivector, see [GenericBivec4].
+pub type DBivec4 = GenericBivec4<f64>;
+/// Single precision simple bivector, see [GenericSimpleBivec4].
+pub type SimpleBivec4 = GenericSimpleBivec4<f32>;
+/// Double precision simple bivector, see [GenericSimpleBivec4].
+pub type DSimpleBivec4 = GenericSimpleBivec4<f64>;
+/// Single precision rotor logarithm, see [GenericRotorLog4].
+pub type RotorLog4 = GenericRotorLog4<f32>;
+/// Double precision rotor logarithm, see [GenericRotorLog4].
+pub type DRotorLog4 = GenericRotorLog4<f64>;
 
 /// Represents rotations in four dimensions. Immutable and no direct constructor because the constraints are tricky.
+/// Generic over the precision of its components, use the [Rotor4] and [DRotor4] aliases.
 #[derive(Clone, Copy, Debug)]
-pub struct Rotor4 {
-    c: f32,
-    bivec: Bivec4,
-    xyzw: f32,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "RotorComponents<S>",
+        bound(deserialize = "S: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub struct GenericRotor4<S> {
+    c: S,
+    bivec: GenericBivec4<S>,
+    xyzw: S,
 }
 
-impl Rotor4 {
-    pub const IDENTITY: Rotor4 = Rotor4 {
-        c: 1.0,
-        bivec: Bivec4::ZERO,
-        xyzw: 0.0,
+impl<S: Scalar> GenericRotor4<S> {
+    pub const IDENTITY: Self = Self {
+        c: S::ONE,
+        bivec: GenericBivec4::ZERO,
+        xyzw: S::ZERO,
     };
 
     /// Makes a new normalized Rotor with the given components.
     /// Returns `Rotor4::IDENTITY` if all components are zero.
-    pub fn new(c: f32, bivec: Bivec4, xyzw: f32) -> Self {
+    pub fn new(c: S, bivec: GenericBivec4<S>, xyzw: S) -> Self {
         Self { c, bivec, xyzw }.normalized()
     }
 
     /// Makes a rotor that rotates in the plane of `from` and `to` by the twice angle between them.
-    pub fn between<V: Vector4>(from: V, to: V) -> Self {
+    pub fn between<V: Vector4<S>>(from: V, to: V) -> Self {
         let from = from.normalized();
         let to = to.normalized();
         Self {
             c: from.dot(to),
             bivec: from.wedge(to),
-            xyzw: 0.0,
+            xyzw: S::ZERO,
         }
         .normalized()
     }
 
+    /// Makes a rotor that rotates the x, y, z, and w axes onto the given vectors, i.e. the columns of its rotation matrix.
+    /// Returns [RotorError::NotRotation] if the frame isn't orthonormal and right-handed, use [Rotor4::look_to] to build one from arbitrary directions.
+    pub fn from_orthonormal_frame<V: Vector4<S>>(
+        x: V,
+        y: V,
+        z: V,
+        w: V,
+    ) -> Result<Self, RotorError<S>> {
+        Self::from_mat4_array([x, y, z, w].map(|v| [v.x(), v.y(), v.z(), v.w()]))
+    }
+
+    /// Makes a rotor that turns +w to face along `forward`, and then turns +y as close to `up` and +z as close to `over` as possible.
+    /// This takes 3 directions where 3D needs 2, as there's one more degree of freedom to pin down. +x ends up wherever keeps the frame right-handed.
+    /// Returns [RotorError::NotRotation] if `forward`, `up`, and `over` aren't linearly independent.
+    pub fn look_to<V: Vector4<S>>(forward: V, up: V, over: V) -> Result<Self, RotorError<S>> {
+        // Gram-Schmidt, remove the parts of each direction along the ones before it.
+        let reject = |v: V, from: V| v + from * -v.dot(from);
+        let w = forward.normalized();
+        let y = reject(up, w).normalized();
+        let z = reject(reject(over, w), y).normalized();
+        // The remaining axis is perpendicular to all 3, with the sign that makes the determinant positive.
+        let columns = [y, z, w].map(|v| [v.x(), v.y(), v.z(), v.w()]);
+        let [x0, x1, x2, x3] = [0, 1, 2, 3].map(|i| {
+            let mut axis = [S::ZERO; 4];
+            axis[i] = S::ONE;
+            determinant([axis, columns[0], columns[1], columns[2]])
+        });
+        Self::from_orthonormal_frame(V::new(x0, x1, x2, x3), y, z, w)
+    }
+
+    /// Like [Rotor4::look_to], but facing from `eye` towards `target`.
+    pub fn look_at<V: Vector4<S>>(
+        eye: V,
+        target: V,
+        up: V,
+        over: V,
+    ) -> Result<Self, RotorError<S>> {
+        Self::look_to(target + eye * -S::ONE, up, over)
+    }
+
     /// Makes a rotor that rotates by the angles specified in the components of the input.
-    pub fn from_bivec_angles(bivec: Bivec4) -> Self {
+    pub fn from_bivec_angles(bivec: GenericBivec4<S>) -> Self {
         // Rotor rotates by twice the angle, scale by half to compensate.
-        bivec.scaled(0.5).exp().normalized()
+        bivec.scaled(S::from_f32(0.5)).exp().normalized()
     }
 
-    pub fn into_bivec_angles(&self) -> Bivec4 {
-        self.log().scaled(2.0).into()
+    pub fn into_bivec_angles(&self) -> GenericBivec4<S> {
+        self.log().scaled(S::from_f32(2.0)).into()
     }
 
     /// Getter for the scalar term of the rotor.
-    pub fn c(&self) -> f32 {
+    pub fn c(&self) -> S {
         self.c
     }
 
     /// Getter for the bivector components of the rotor.
-    pub fn bivec(&self) -> Bivec4 {
+    pub fn bivec(&self) -> GenericBivec4<S> {
         self.bivec
     }
 
     /// Getter for the quadvector component of the rotor.
-    pub fn xyzw(&self) -> f32 {
+    pub fn xyzw(&self) -> S {
         self.xyzw
     }
 
     /// Inverse of a bivector exponential. Returns a "polar" representation of the Rotor.
-    pub fn log(&self) -> RotorLog4 {
-        let bivec_simple = SimpleBivec4::try_from(self.bivec);
-        match bivec_simple {
-            Ok(bivec) if approx_equal(self.xyzw, 0.0) => {
-                let abs_angle = (bivec.magnitude() / self.c.abs()).atan();
-                let angle = if self.c > 0.0 {
-                    abs_angle
-                } else {
-                    PI - abs_angle
-                };
-                RotorLog4::Simple {
-                    bivec: bivec.normalized(),
-                    angle,
-                }
+    pub fn log(&self) -> GenericRotorLog4<S> {
+        // The self-dual and anti-self-dual halves of the rotor each rotate isoclinically, and commute,
+        // so the log of each can be taken separately like a quaternion's. Unlike factoring the bivector into planes first,
+        // that stays accurate near isoclinic rotations and half turns, where the planes aren't well defined.
+        let [(self_dual, self_dual_magnitude), (anti_self_dual, anti_self_dual_magnitude)] =
+            self.bivec.split_self_dual();
+        let sqrt_2 = S::from_f32(2.0).sqrt();
+        let self_dual_angle = (sqrt_2 * self_dual_magnitude).atan2(self.c - self.xyzw);
+        let anti_self_dual_angle = (sqrt_2 * anti_self_dual_magnitude).atan2(self.c + self.xyzw);
+
+        let bivec1 = (self_dual + anti_self_dual)
+            .scaled(sqrt_2.recip())
+            .force_simple();
+        let bivec2 = (self_dual - anti_self_dual)
+            .scaled(sqrt_2.recip())
+            .force_simple();
+        let half = S::from_f32(0.5);
+        let angle1 = (self_dual_angle + anti_self_dual_angle) * half;
+        let angle2 = (self_dual_angle - anti_self_dual_angle) * half;
+        // Both halves turning by the same angle is a rotation in the plane of bivec1 only.
+        if angle2 == S::ZERO {
+            GenericRotorLog4::Simple {
+                bivec: bivec1,
+                angle: angle1,
             }
-            Ok(bivec) if approx_equal(self.c, 0.0) => {
-                let mut bivec2 = bivec;
-                let bivec2_magnitude = bivec2.magnitude();
-                // If the bivector component is zero, have an isoclinic rotation and any simple bivector works.
-                if approx_equal(bivec2_magnitude, 0.0) {
-                    bivec2 = SimpleBivec4 {
-                        bivec: Bivec4 {
-                            xy: 1.0,
-                            ..Bivec4::ZERO
-                        },
-                    }
-                }
-                let angle1 = self.xyzw.atan2(bivec2_magnitude);
-                let angle2 = FRAC_PI_2;
-                let bivec2 = bivec2.normalized();
-                let bivec1 = SimpleBivec4 {
-                    bivec: Bivec4 {
-                        xy: bivec2.bivec.zw,
-                        xz: bivec2.bivec.wy,
-                        xw: bivec2.bivec.yz,
-                        yz: bivec2.bivec.xw,
-                        wy: bivec2.bivec.xz,
-                        zw: bivec2.bivec.xy,
-                    },
-                };
-                RotorLog4::DoubleRotation {
-                    bivec1,
-                    angle1,
-                    bivec2,
-                    angle2,
-                }
-            }
-            _ => {
-                let (bivec1, bivec2) = self.bivec.factor_into_simple_orthogonal();
-                // Because bivec magnitude is always positive, essentially have x and |y| which breaks atan2
-                // Need to figure out quadrant based on signs of other terms.
-                // Also, can calculate from either self.c or self.xyzw, use the bigger one for precision.
-                let mag1 = bivec1.magnitude();
-                let mag2 = bivec2.magnitude();
-                let (abs_angle1, abs_angle2) = if self.c.abs() > self.xyzw.abs() {
-                    ((mag1 / self.c.abs()).atan(), (mag2 / self.c.abs()).atan())
-                } else {
-                    (
-                        (self.xyzw.abs() / mag2).atan(),
-                        (self.xyzw.abs() / mag1).atan(),
-                    )
-                };
-                let bivec1 = bivec1.normalized();
-                let mut bivec2 = bivec2.normalized();
-
-                let sign_c = self.c > 0.0;
-                let sign_xyzw = self.xyzw > 0.0;
-                let (angle1, angle2) = match (sign_c, sign_xyzw) {
-                    (true, true) => (abs_angle1, abs_angle2),
-                    (true, false) => (abs_angle1, -abs_angle2),
-                    (false, true) => (-abs_angle1 + PI, abs_angle2),
-                    (false, false) => (-abs_angle1 + PI, -abs_angle2),
-                };
-                // If the coefficient for B2 is negative, need to flip it so
-                // the bivector components still sum to the right value.
-                if angle1.cos() * angle2.sin() < 0.0 {
-                    bivec2 = bivec2.scaled(-1.0);
-                }
-
-                RotorLog4::DoubleRotation {
-                    bivec1: bivec1.normalized(),
-                    angle1,
-                    bivec2: bivec2.normalized(),
-                    angle2,
-                }
+        } else {
+            GenericRotorLog4::DoubleRotation {
+                bivec1,
+                angle1,
+                bivec2,
+                angle2,
             }
         }
     }
 
     /// Computes R^exponent as exp(exponent * log(R)).
-    pub fn pow(&self, exponent: f32) -> Rotor4 {
+    pub fn pow(&self, exponent: S) -> Self {
         self.log().scaled(exponent).exp()
     }
 
-    pub fn into_mat4_array(&self) -> [[f32; 4]; 4] {
+    /// Steps the rotor by a constant angular velocity `omega` for time `dt`, with angles in the same units as [Rotor4::from_bivec_angles].
+    /// `omega` is in world space, i.e. the step is applied after this rotor. Exact for double rotations as well, not just a first order step,
+    /// and the result is renormalized so error doesn't build up over many steps.
+    pub fn integrate(&self, omega: GenericBivec4<S>, dt: S) -> Self {
+        self.compose(Self::from_bivec_angles(omega.scaled(dt)))
+    }
+
+    /// Constant angular velocity that takes this rotor to `other` in time `dt`, the inverse of [Rotor4::integrate].
+    /// Always turns the short way, by at most half a turn in each plane.
+    pub fn angular_velocity_to(&self, other: &Self, dt: S) -> GenericBivec4<S> {
+        let mut delta = self.inverse().compose(*other);
+        // R and -R are the same rotation, but the log of a rotor with negative scalar part goes the long way around.
+        if delta.c < S::ZERO {
+            delta = Self {
+                c: -delta.c,
+                bivec: -delta.bivec,
+                xyzw: -delta.xyzw,
+            };
+        }
+        delta.into_bivec_angles().scaled(dt.recip())
+    }
+
+    pub fn into_mat4_array(&self) -> [[S; 4]; 4] {
         macro_rules! get {
             [c] => {
                 self.c
@@ -180,54 +212,166 @@ impl Rotor4 {
                 get![$a] * get![$b]
             };
         }
+        let half = S::from_f32(0.5);
         // This took like 2 days of algebra to derive, basically just do RxR^-1 and simplify but there's hundreds of terms.
         // Don't worry about duplicate products, complier optimization handles it.
         let mut arr = [
             [
-                0.5 - p!(xy, xy) - p!(xz, xz) - p!(xw, xw) - p!(xyzw, xyzw),
+                half - p!(xy, xy) - p!(xz, xz) - p!(xw, xw) - p!(xyzw, xyzw),
                 p!(c, xy) - p!(xz, yz) + p!(xw, wy) + p!(zw, xyzw),
                 p!(c, xz) + p!(xy, yz) - p!(xw, zw) + p!(wy, xyzw),
                 p!(c, xw) - p!(xy, wy) + p!(xz, zw) + p!(yz, xyzw),
             ],
             [
                 -p!(c, xy) - p!(xz, yz) + p!(xw, wy) - p!(zw, xyzw),
-                0.5 - p!(xy, xy) - p!(yz, yz) - p!(wy, wy) - p!(xyzw, xyzw),
+                half - p!(xy, xy) - p!(yz, yz) - p!(wy, wy) - p!(xyzw, xyzw),
                 p!(c, yz) - p!(xy, xz) + p!(wy, zw) + p!(xw, xyzw),
                 -p!(c, wy) - p!(xw, xy) + p!(yz, zw) - p!(xz, xyzw),
             ],
             [
                 -p!(c, xz) + p!(xy, yz) - p!(xw, zw) - p!(wy, xyzw),
                 -p!(c, yz) - p!(xy, xz) + p!(wy, zw) - p!(xw, xyzw),
-                0.5 - p!(xz, xz) - p!(yz, yz) - p!(zw, zw) - p!(xyzw, xyzw),
+                half - p!(xz, xz) - p!(yz, yz) - p!(zw, zw) - p!(xyzw, xyzw),
                 p!(c, zw) - p!(xz, xw) + p!(yz, wy) + p!(xy, xyzw),
             ],
             [
                 -p!(c, xw) - p!(xy, wy) + p!(xz, zw) - p!(yz, xyzw),
                 p!(c, wy) - p!(xy, xw) + p!(yz, zw) + p!(xz, xyzw),
                 -p!(c, zw) - p!(xz, xw) + p!(yz, wy) - p!(xy, xyzw),
-                0.5 - p!(xw, xw) - p!(wy, wy) - p!(zw, zw) - p!(xyzw, xyzw),
+                half - p!(xw, xw) - p!(wy, wy) - p!(zw, zw) - p!(xyzw, xyzw),
             ],
         ];
         for row in arr.iter_mut() {
             for item in row.iter_mut() {
-                *item *= 2.0;
+                *item *= S::from_f32(2.0);
             }
         }
         arr
     }
 
     /// Creates a 4x4 rotation matrix that applies the same rotation as this rotor.
-    pub fn into_mat4<M: Matrix4>(&self) -> M {
+    pub fn into_mat4<M: Matrix4<S>>(&self) -> M {
         M::from_cols_array(self.into_mat4_array())
     }
 
+    /// Splits the rotor into its left and right isoclinic rotations, as a pair of unit quaternions.
+    /// Treating a vector as the quaternion `xi + yj + zk + w`, this rotor transforms `v` to `left * v * right`.
+    /// Like the rotor, the pair is only unique up to negating both.
+    pub fn to_quaternion_pair<Q: Quaternion<S>>(&self) -> (Q, Q) {
+        let ([lx, ly, lz, lw], [rx, ry, rz, rw]) = self.quaternion_pair_arrays();
+        (Q::from_xyzw(lx, ly, lz, lw), Q::from_xyzw(rx, ry, rz, rw))
+    }
+
+    /// Same as [Rotor4::to_quaternion_pair], with each quaternion as an `[x, y, z, w]` array.
+    fn quaternion_pair_arrays(&self) -> ([S; 4], [S; 4]) {
+        let GenericBivec4 {
+            xy,
+            xz,
+            xw,
+            yz,
+            wy,
+            zw,
+        } = self.bivec;
+        let left = [yz - xw, wy - xz, xy - zw, self.c + self.xyzw];
+        let right = [-xw - yz, xz + wy, -xy - zw, self.c - self.xyzw];
+        (left, right)
+    }
+
+    /// Inverse of [Rotor4::to_quaternion_pair], makes the rotor that transforms `v` to `left * v * right`.
+    /// The quaternions are normalized, so they only need to be roughly unit length.
+    pub fn from_quaternion_pair<Q: Quaternion<S>>(left: Q, right: Q) -> Self {
+        Self::from_isoclinic_halves(
+            [left.w(), left.z(), -left.y(), -left.x()],
+            [right.w(), -right.z(), right.y(), -right.x()],
+        )
+    }
+
+    /// Inverse of [Rotor4::into_mat4_array], makes a rotor from a column-major rotation matrix.
+    /// The rotor is only unique up to sign, R and -R give the same matrix.
+    /// Returns [RotorError::NotRotation] if the matrix isn't orthonormal with determinant 1, e.g. if it scales or reflects.
+    pub fn from_mat4_array(arr: [[S; 4]; 4]) -> Result<Self, RotorError<S>> {
+        let orthonormal = (0..4).all(|i| {
+            (0..4).all(|j| {
+                let dot: S = (0..4).map(|k| arr[i][k] * arr[j][k]).sum();
+                approx_equal(dot, if i == j { S::ONE } else { S::ZERO })
+            })
+        });
+        if !orthonormal || determinant(arr) < S::ZERO {
+            return Err(RotorError::NotRotation(arr));
+        }
+        Ok(Self::from_rotation_mat4_array(arr))
+    }
+
+    /// Unchecked version of [Rotor4::from_mat4_array], takes a column-major rotation matrix.
+    /// Input must be orthonormal with determinant 1, anything else gives a meaningless rotor.
+    fn from_rotation_mat4_array(m: [[S; 4]; 4]) -> Self {
+        // Every 4D rotation factors into a left and a right isoclinic rotation, each described by a unit 4-vector
+        // `a` and `b` (the self-dual and anti-self-dual halves of the rotor). The matrix entries are bilinear in
+        // those, so these sums of entries recover the outer product a_i * b_j (times 4).
+        let outer = [
+            [
+                m[0][0] + m[1][1] + m[2][2] + m[3][3],
+                m[0][1] - m[1][0] + m[2][3] - m[3][2],
+                m[0][2] - m[1][3] - m[2][0] + m[3][1],
+                m[0][3] + m[1][2] - m[2][1] - m[3][0],
+            ],
+            [
+                m[0][1] - m[1][0] - m[2][3] + m[3][2],
+                -m[0][0] - m[1][1] + m[2][2] + m[3][3],
+                -m[0][3] - m[1][2] - m[2][1] - m[3][0],
+                m[0][2] - m[1][3] + m[2][0] - m[3][1],
+            ],
+            [
+                m[0][2] + m[1][3] - m[2][0] - m[3][1],
+                m[0][3] - m[1][2] - m[2][1] + m[3][0],
+                -m[0][0] + m[1][1] - m[2][2] + m[3][3],
+                -m[0][1] - m[1][0] - m[2][3] - m[3][2],
+            ],
+            [
+                m[0][3] - m[1][2] + m[2][1] - m[3][0],
+                -m[0][2] - m[1][3] - m[2][0] - m[3][1],
+                m[0][1] + m[1][0] - m[2][3] - m[3][2],
+                -m[0][0] + m[1][1] + m[2][2] - m[3][3],
+            ],
+        ];
+        // Read a and b off the row and column through the largest entry, the rest may be all but zero.
+        let (row, col) = (0..16)
+            .map(|i| (i / 4, i % 4))
+            .max_by(|&(r1, c1), &(r2, c2)| outer[r1][c1].abs().total_cmp(&outer[r2][c2].abs()))
+            .unwrap();
+        let sign = outer[row][col].signum();
+        Self::from_isoclinic_halves(outer.map(|r| r[col]), outer[row].map(|x| x * sign))
+    }
+
+    /// Makes a rotor from its self-dual and anti-self-dual halves `a` and `b`, normalizing both.
+    /// These are `(c + xyzw, xy - zw, xz - wy, xw - yz)` and `(c - xyzw, xy + zw, xz + wy, xw + yz)`.
+    fn from_isoclinic_halves(a: [S; 4], b: [S; 4]) -> Self {
+        let a_len = a.iter().map(|&x| x * x).sum::<S>().sqrt();
+        let b_len = b.iter().map(|&x| x * x).sum::<S>().sqrt();
+        let a = a.map(|x| x / a_len);
+        let b = b.map(|x| x / b_len);
+        let half = S::from_f32(0.5);
+        Self::new(
+            half * (a[0] + b[0]),
+            GenericBivec4 {
+                xy: half * (a[1] + b[1]),
+                xz: half * (a[2] + b[2]),
+                xw: half * (a[3] + b[3]),
+                yz: half * (b[3] - a[3]),
+                wy: half * (b[2] - a[2]),
+                zw: half * (b[1] - a[1]),
+            },
+            half * (a[0] - b[0]),
+        )
+    }
+
     /// Computes RR^-1, should be (1, 0) if the rotor is properly normalized.
-    fn normalization_error(self) -> ScalarPlusQuadvec4 {
+    fn normalization_error(self) -> ScalarPlusQuadvec4<S> {
         let bivec_squared = self.bivec.square();
         // Should be 1
         let magnitude = self.c * self.c + self.xyzw * self.xyzw - bivec_squared.c;
         // Should be 0
-        let xyzw_err = 2.0 * self.c * self.xyzw - bivec_squared.xyzw;
+        let xyzw_err = S::from_f32(2.0) * self.c * self.xyzw - bivec_squared.xyzw;
         ScalarPlusQuadvec4 {
             c: magnitude,
             xyzw: xyzw_err,
@@ -236,39 +380,61 @@ impl Rotor4 {
 
     /// Internal, users should not have to call this, implementation must guarantee that the rotor stays normalized.
     fn normalized(mut self) -> Self {
-        if !approx_equal(self.c, 0.0) {
-            self.xyzw = self.bivec.square().xyzw / (2.0 * self.c);
+        // Fixes up whichever of c and xyzw is smaller to satisfy 2 * c * xyzw = B ^ B, dividing by the bigger one for precision.
+        let bivec_wedge = self.bivec.square().xyzw;
+        if self.c.abs() >= self.xyzw.abs() {
+            if self.c != S::ZERO {
+                self.xyzw = bivec_wedge / (S::from_f32(2.0) * self.c);
+            }
+        } else {
+            self.c = bivec_wedge / (S::from_f32(2.0) * self.xyzw);
         }
 
         let error = self.normalization_error();
         let magnitude = error.c.sqrt();
-        if approx_equal(magnitude, 0.0) {
+        if approx_equal(magnitude, S::ZERO) {
             return Self::IDENTITY;
         }
         self.c /= magnitude;
-        self.bivec = self.bivec.scaled(1.0 / magnitude);
+        self.bivec = self.bivec.scaled(magnitude.recip());
         self.xyzw /= magnitude;
 
         self
     }
 }
 
-impl Default for Rotor4 {
+impl<S: Scalar> Default for GenericRotor4<S> {
     fn default() -> Self {
         Self::IDENTITY
     }
 }
 
-impl<V: Vector4> Transform<V> for Rotor4 {
+/// Applies the rotor as `left * v * right` with its quaternion pair, which is cheaper than building the rotation matrix
+/// for a single vector. [Transform::transform_each] builds the matrix once instead, which is faster for many vectors.
+impl<S: Scalar, V: Vector4<S>> Transform<V> for GenericRotor4<S> {
     fn transform(&self, operand: V) -> V {
+        let (left, right) = self.quaternion_pair_arrays();
+        let vector = [operand.x(), operand.y(), operand.z(), operand.w()];
+        let [x, y, z, w] = quaternion_product(quaternion_product(left, vector), right);
+        V::new(x, y, z, w)
+    }
+
+    /// Builds the rotation matrix once and applies it to every operand.
+    fn transform_each<'a, I>(&self, operands: I)
+    where
+        I: IntoIterator<Item = &'a mut V>,
+        V: 'a,
+    {
         let matrix: V::Matrix4 = self.into_mat4();
-        matrix * operand
+        for operand in operands {
+            *operand = matrix * *operand;
+        }
     }
 }
 
-impl Compose<Rotor4> for Rotor4 {
-    type Composed = Rotor4;
-    fn compose(&self, other: Rotor4) -> Self::Composed {
+impl<S: Scalar> Compose<GenericRotor4<S>> for GenericRotor4<S> {
+    type Composed = GenericRotor4<S>;
+    fn compose(&self, other: GenericRotor4<S>) -> Self::Composed {
         macro_rules! get {
             ($x:ident, c) => {
                 $x.c
@@ -295,7 +461,7 @@ impl Compose<Rotor4> for Rotor4 {
             c: other.c,
             xyzw: other.xyzw,
         } * self.bivec;
-        Rotor4 {
+        GenericRotor4 {
             c: p!(c, c)
                 - p!(xy, xy)
                 - p!(xz, xz)
@@ -306,7 +472,7 @@ impl Compose<Rotor4> for Rotor4 {
                 + p!(xyzw, xyzw),
             bivec: a_scalarquadvec_b_bivec
                 + b_scalarquadvec_a_bivec
-                + Bivec4 {
+                + GenericBivec4 {
                     xy: -p!(xz, yz) + p!(xw, wy) + p!(yz, xz) - p!(wy, xw),
                     xz: p!(xy, yz) - p!(xw, zw) - p!(yz, xy) + p!(zw, xw),
                     xw: -p!(xy, wy) + p!(xz, zw) + p!(wy, xy) - p!(zw, xz),
@@ -327,8 +493,8 @@ impl Compose<Rotor4> for Rotor4 {
     }
 }
 
-impl Inverse for Rotor4 {
-    type Inverted = Rotor4;
+impl<S: Scalar> Inverse for GenericRotor4<S> {
+    type Inverted = GenericRotor4<S>;
     fn inverse(&self) -> Self::Inverted {
         Self {
             c: self.c,
@@ -338,35 +504,73 @@ impl Inverse for Rotor4 {
     }
 }
 
-impl InterpolateWith for Rotor4 {
-    fn interpolate_with(&self, other: &Self, fraction: f32) -> Self {
+impl<S: Scalar> InterpolateWith for GenericRotor4<S> {
+    type Scalar = S;
+    fn interpolate_with(&self, other: &Self, fraction: S) -> Self {
         self.compose(self.inverse().compose(*other).pow(fraction))
     }
 }
 
+/// Spherical quadrangle interpolation (squad), same as for quaternions.
+/// Works because the log of a rotor splits into two commuting quaternion logs, see [Rotor4::to_quaternion_pair].
+impl<S: Scalar> InterpolateSpline for GenericRotor4<S> {
+    /// Inner control point, chosen so the curve's angular velocity is continuous through each keyframe.
+    type Scalar = S;
+    type Control = GenericRotor4<S>;
+    fn spline_control(previous: &Self, current: &Self, next: &Self) -> Self::Control {
+        let inverse = current.inverse();
+        let to_next = GenericBivec4::from(inverse.compose(*next).log());
+        let to_previous = GenericBivec4::from(inverse.compose(*previous).log());
+        current.compose((to_next + to_previous).scaled(S::from_f32(-0.25)).exp())
+    }
+
+    fn interpolate_spline(
+        &self,
+        control: &Self::Control,
+        other: &Self,
+        other_control: &Self::Control,
+        fraction: S,
+    ) -> Self {
+        let keyframes = self.interpolate_with(other, fraction);
+        let controls = control.interpolate_with(other_control, fraction);
+        keyframes.interpolate_with(&controls, S::from_f32(2.0) * fraction * (S::ONE - fraction))
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "S: Scalar + serde::Serialize",
+        deserialize = "S: Scalar + serde::Deserialize<'de>"
+    ))
+)]
 /// Result of [Rotor4::log()], all bivectors are normalized.
-pub enum RotorLog4 {
+pub enum GenericRotorLog4<S> {
     /// A simple rotation in the plane of a bivector, R = exp(angle * bivec)
-    Simple { bivec: SimpleBivec4, angle: f32 },
+    Simple {
+        bivec: GenericSimpleBivec4<S>,
+        angle: S,
+    },
     /// A double rotation, two independent rotations at the same time.
     /// R = exp(angle1 * bivec1 + angle2 * bivec2) = exp(angle1 * bivec1) * exp(angle2 * bivec2)
     /// Also, bivec1 commutes with bivec2, they are orthogonal.
     DoubleRotation {
-        bivec1: SimpleBivec4,
-        angle1: f32,
-        bivec2: SimpleBivec4,
-        angle2: f32,
+        bivec1: GenericSimpleBivec4<S>,
+        angle1: S,
+        bivec2: GenericSimpleBivec4<S>,
+        angle2: S,
     },
 }
 
-impl RotorLog4 {
-    pub fn exp(&self) -> Rotor4 {
-        match self {
-            Self::Simple { bivec, angle } => Rotor4 {
+impl<S: Scalar> GenericRotorLog4<S> {
+    pub fn exp(&self) -> GenericRotor4<S> {
+        match *self {
+            Self::Simple { bivec, angle } => GenericRotor4 {
                 c: angle.cos(),
                 bivec: bivec.scaled(angle.sin()).bivec,
-                xyzw: 0.0,
+                xyzw: S::ZERO,
             },
             Self::DoubleRotation {
                 bivec1,
@@ -376,7 +580,7 @@ impl RotorLog4 {
             } => {
                 let (sin_angle1, cos_angle1) = angle1.sin_cos();
                 let (sin_angle2, cos_angle2) = angle2.sin_cos();
-                Rotor4 {
+                GenericRotor4 {
                     c: cos_angle1 * cos_angle2,
                     bivec: bivec1.scaled(sin_angle1 * cos_angle2)
                         + bivec2.scaled(cos_angle1 * sin_angle2),
@@ -387,10 +591,10 @@ impl RotorLog4 {
         .normalized()
     }
 
-    pub fn scaled(&self, scale: f32) -> Self {
-        match self {
+    pub fn scaled(&self, scale: S) -> Self {
+        match *self {
             Self::Simple { bivec, angle } => Self::Simple {
-                bivec: *bivec,
+                bivec,
                 angle: angle * scale,
             },
             Self::DoubleRotation {
@@ -399,20 +603,20 @@ impl RotorLog4 {
                 bivec2,
                 angle2,
             } => Self::DoubleRotation {
-                bivec1: *bivec1,
+                bivec1,
                 angle1: scale * angle1,
-                bivec2: *bivec2,
+                bivec2,
                 angle2: scale * angle2,
             },
         }
     }
 }
 
-impl From<RotorLog4> for Bivec4 {
-    fn from(value: RotorLog4) -> Bivec4 {
+impl<S: Scalar> From<GenericRotorLog4<S>> for GenericBivec4<S> {
+    fn from(value: GenericRotorLog4<S>) -> GenericBivec4<S> {
         match value {
-            RotorLog4::Simple { bivec, angle } => bivec.bivec.scaled(angle),
-            RotorLog4::DoubleRotation {
+            GenericRotorLog4::Simple { bivec, angle } => bivec.bivec.scaled(angle),
+            GenericRotorLog4::DoubleRotation {
                 bivec1,
                 angle1,
                 bivec2,
@@ -423,61 +627,63 @@ impl From<RotorLog4> for Bivec4 {
 }
 
 /// 4D bivector with components for each of the six basis planes in 4D.
+/// Generic over the precision of its components, use the [Bivec4] and [DBivec4] aliases.
 #[derive(Clone, Copy, Debug)]
-pub struct Bivec4 {
-    pub xy: f32,
-    pub xz: f32,
-    pub xw: f32,
-    pub yz: f32,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericBivec4<S> {
+    pub xy: S,
+    pub xz: S,
+    pub xw: S,
+    pub yz: S,
     /// Note wy is flipped from what you might expect, this makes the multiplication tables for rotors nicer.
-    pub wy: f32,
-    pub zw: f32,
+    pub wy: S,
+    pub zw: S,
 }
 
-impl Bivec4 {
+impl<S: Scalar> GenericBivec4<S> {
     pub const ZERO: Self = Self {
-        xy: 0.0,
-        xz: 0.0,
-        xw: 0.0,
-        yz: 0.0,
-        wy: 0.0,
-        zw: 0.0,
+        xy: S::ZERO,
+        xz: S::ZERO,
+        xw: S::ZERO,
+        yz: S::ZERO,
+        wy: S::ZERO,
+        zw: S::ZERO,
     };
     pub const ONE: Self = Self {
-        xy: 1.0,
-        xz: 1.0,
-        xw: 1.0,
-        yz: 1.0,
-        wy: 1.0,
-        zw: 1.0,
+        xy: S::ONE,
+        xz: S::ONE,
+        xw: S::ONE,
+        yz: S::ONE,
+        wy: S::ONE,
+        zw: S::ONE,
     };
     pub const XY: Self = Self {
-        xy: 1.0,
+        xy: S::ONE,
         ..Self::ZERO
     };
     pub const YZ: Self = Self {
-        yz: 1.0,
+        yz: S::ONE,
         ..Self::ZERO
     };
     pub const XZ: Self = Self {
-        xz: 1.0,
+        xz: S::ONE,
         ..Self::ZERO
     };
     pub const XW: Self = Self {
-        xw: 1.0,
+        xw: S::ONE,
         ..Self::ZERO
     };
     pub const WY: Self = Self {
-        wy: 1.0,
+        wy: S::ONE,
         ..Self::ZERO
     };
     pub const ZW: Self = Self {
-        zw: 1.0,
+        zw: S::ONE,
         ..Self::ZERO
     };
 
     /// Scales the bivector by a scalar.
-    pub fn scaled(&self, scale: f32) -> Self {
+    pub fn scaled(&self, scale: S) -> Self {
         Self {
             xy: self.xy * scale,
             xz: self.xz * scale,
@@ -489,7 +695,7 @@ impl Bivec4 {
     }
 
     /// Bivector exponential, essentially maps from a polar representation, angle * Bivector, to a Rotor that transforms by that angle.
-    pub fn exp(&self) -> Rotor4 {
+    pub fn exp(&self) -> GenericRotor4<S> {
         let (b1, b2) = self.factor_into_simple_orthogonal();
         let angle1 = b1.magnitude();
         let angle2 = b2.magnitude();
@@ -498,7 +704,7 @@ impl Bivec4 {
         let wedge = b1.bivec.wedge(b2.bivec);
         let (angle1_sin, angle1_cos) = angle1.sin_cos();
         let (angle2_sin, angle2_cos) = angle2.sin_cos();
-        Rotor4 {
+        GenericRotor4 {
             c: angle1_cos * angle2_cos,
             bivec: b1.scaled(angle1_sin * angle2_cos) + b2.scaled(angle1_cos * angle2_sin),
             xyzw: angle1_sin * angle2_sin * wedge,
@@ -506,7 +712,7 @@ impl Bivec4 {
     }
 
     /// Returns the scalar component of the dot product of self and other.
-    pub fn dot(&self, other: Bivec4) -> f32 {
+    pub fn dot(&self, other: Self) -> S {
         -(self.xy * other.xy
             + self.xz * other.xz
             + self.xw * other.xw
@@ -516,7 +722,7 @@ impl Bivec4 {
     }
 
     /// Returns the quadvector component of the wedge product of self and other.
-    pub fn wedge(&self, other: Bivec4) -> f32 {
+    pub fn wedge(&self, other: Self) -> S {
         self.xy * other.zw
             + self.xz * other.wy
             + self.xw * other.yz
@@ -526,73 +732,78 @@ impl Bivec4 {
     }
 
     /// Factors this bivector B into two the sum of *simple*, *orthogonal* bivectors. That is, B = B1 + B2, B1 * B2 = B2 * B1, and B1^2, B2^2 are scalars.
-    fn factor_into_simple_orthogonal(&self) -> (SimpleBivec4, SimpleBivec4) {
-        let mag = self.max_component_magnitude();
-        // Use some extra precision before defaulting to zero to help to/from_bivec_angles with precision.
-        if mag.abs() < 1e-4 {
-            let zero = Bivec4::ZERO.force_simple();
-            return (zero, zero);
-        }
-        let bivec = self.scaled(1.0 / mag);
-        let squared = bivec.square();
-        let det = ((squared.c + squared.xyzw) * (squared.c - squared.xyzw)).sqrt();
-        if approx_equal(squared.c.abs(), squared.xyzw.abs()) {
-            return (
-                Bivec4 {
-                    xy: self.xy,
-                    xz: self.xz,
-                    xw: self.xw,
-                    ..Self::ZERO
-                }
-                .force_simple(),
-                Bivec4 {
-                    yz: self.yz,
-                    wy: self.wy,
-                    zw: self.zw,
-                    ..Self::ZERO
-                }
-                .force_simple(),
-            );
-        }
-
-        let factor1 = ScalarPlusQuadvec4 {
-            c: (-squared.c + det),
-            xyzw: squared.xyzw,
-        };
-        let factor2 = ScalarPlusQuadvec4 {
-            c: (squared.c + det),
-            xyzw: -squared.xyzw,
-        };
-        let scale = 1.0 / (2.0 * det);
-        let factor1 = (bivec * factor1).scaled(scale);
-        let factor2 = (bivec * factor2).scaled(scale);
+    fn factor_into_simple_orthogonal(&self) -> (GenericSimpleBivec4<S>, GenericSimpleBivec4<S>) {
+        let [(self_dual, self_dual_magnitude), (anti_self_dual, anti_self_dual_magnitude)] =
+            self.split_self_dual();
+        let frac_1_sqrt_2 = S::from_f32(2.0).sqrt().recip();
+        // The sum and difference of unit self-dual and anti-self-dual bivectors are orthogonal planes.
+        let bivec1 = (self_dual + anti_self_dual)
+            .scaled(frac_1_sqrt_2)
+            .force_simple();
+        let bivec2 = (self_dual - anti_self_dual)
+            .scaled(frac_1_sqrt_2)
+            .force_simple();
         (
-            factor1.force_simple().scaled(mag),
-            factor2.force_simple().scaled(mag),
+            bivec1.scaled((self_dual_magnitude + anti_self_dual_magnitude) * frac_1_sqrt_2),
+            bivec2.scaled((self_dual_magnitude - anti_self_dual_magnitude) * frac_1_sqrt_2),
         )
     }
 
+    /// Splits the bivector into its self-dual and anti-self-dual parts, along planes like xy + zw and xy - zw, which commute.
+    /// Returns the unit direction and magnitude of each, with an arbitrary direction for a part that's zero.
+    /// Unlike solving for the orthogonal planes directly, this stays accurate close to isoclinic bivectors.
+    fn split_self_dual(&self) -> [(Self, S); 2] {
+        let frac_1_sqrt_2 = S::from_f32(2.0).sqrt().recip();
+        let dual = self.dual();
+        let part = |bivec: Self, default: Self| {
+            let bivec = bivec.scaled(S::from_f32(0.5));
+            let magnitude = bivec.dot(bivec).abs().sqrt();
+            if magnitude == S::ZERO {
+                (default.scaled(frac_1_sqrt_2), magnitude)
+            } else {
+                (bivec.scaled(magnitude.recip()), magnitude)
+            }
+        };
+        [
+            part(*self + dual, Self::XY + Self::ZW),
+            part(*self - dual, Self::XY - Self::ZW),
+        ]
+    }
+
     /// For vectors that are mathematically guranteed to be simple, but might not be due to float precision.
     /// Always returns a SimpleBivec4, panics in tests.
     /// Consequences of vector not being simple when expected are incorrect results, shouldn't be NaNs or anything catastrophic.
-    fn force_simple(self) -> SimpleBivec4 {
+    fn force_simple(self) -> GenericSimpleBivec4<S> {
         #[cfg(test)]
         {
-            let simple = SimpleBivec4::try_from(self);
+            let simple = GenericSimpleBivec4::try_from(self);
             simple.expect("bivector should be simple");
         }
-        SimpleBivec4 { bivec: self }
+        GenericSimpleBivec4 { bivec: self }
+    }
+
+    /// Hodge dual of the bivector, the orthogonal plane with the orientation that makes their wedge product positive.
+    fn dual(&self) -> Self {
+        Self {
+            xy: self.zw,
+            xz: self.wy,
+            xw: self.yz,
+            yz: self.xw,
+            wy: self.xz,
+            zw: self.xy,
+        }
     }
 
     /// Returns the square of the bivector, as a [ScalarPlusQuadvec4].
-    fn square(&self) -> ScalarPlusQuadvec4 {
+    fn square(&self) -> ScalarPlusQuadvec4<S> {
         ScalarPlusQuadvec4 {
             c: self.dot(*self),
-            xyzw: 2.0 * (self.xy * self.zw + self.xz * self.wy + self.xw * self.yz),
+            xyzw: S::from_f32(2.0) * (self.xy * self.zw + self.xz * self.wy + self.xw * self.yz),
         }
     }
 
-    fn max_component_magnitude(&self) -> f32 {
+    #[cfg(test)]
+    fn max_component_magnitude(&self) -> S {
         self.xy
             .abs()
             .max(self.xz.abs())
@@ -603,10 +814,10 @@ impl Bivec4 {
     }
 }
 
-impl Neg for Bivec4 {
-    type Output = Bivec4;
+impl<S: Scalar> Neg for GenericBivec4<S> {
+    type Output = GenericBivec4<S>;
     fn neg(self) -> Self::Output {
-        Bivec4 {
+        GenericBivec4 {
             xy: -self.xy,
             xz: -self.xz,
             xw: -self.xw,
@@ -617,10 +828,10 @@ impl Neg for Bivec4 {
     }
 }
 
-impl Add for Bivec4 {
-    type Output = Bivec4;
+impl<S: Scalar> Add for GenericBivec4<S> {
+    type Output = GenericBivec4<S>;
     fn add(self, rhs: Self) -> Self::Output {
-        Bivec4 {
+        GenericBivec4 {
             xy: self.xy + rhs.xy,
             xz: self.xz + rhs.xz,
             xw: self.xw + rhs.xw,
@@ -631,10 +842,10 @@ impl Add for Bivec4 {
     }
 }
 
-impl Sub for Bivec4 {
-    type Output = Bivec4;
+impl<S: Scalar> Sub for GenericBivec4<S> {
+    type Output = GenericBivec4<S>;
     fn sub(self, rhs: Self) -> Self::Output {
-        Bivec4 {
+        GenericBivec4 {
             xy: self.xy - rhs.xy,
             xz: self.xz - rhs.xz,
             xw: self.xw - rhs.xw,
@@ -646,104 +857,164 @@ impl Sub for Bivec4 {
 }
 
 /// Special case of [Bivec4], a 4D bivector which squares to a scalar. Immutable.
+/// Generic over the precision of its components, use the [SimpleBivec4] and [DSimpleBivec4] aliases.
 #[derive(Clone, Copy, Debug)]
-pub struct SimpleBivec4 {
-    bivec: Bivec4,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        into = "GenericBivec4<S>",
+        try_from = "GenericBivec4<S>",
+        bound(
+            serialize = "S: Scalar + serde::Serialize",
+            deserialize = "S: Scalar + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct GenericSimpleBivec4<S> {
+    bivec: GenericBivec4<S>,
 }
 
-impl SimpleBivec4 {
-    pub fn bivec(&self) -> Bivec4 {
+impl<S: Scalar> GenericSimpleBivec4<S> {
+    pub fn bivec(&self) -> GenericBivec4<S> {
         self.bivec
     }
 
     /// Multiplies this bivector by a positive scalar so that it squares to -1. If 0, returns 0.
     pub fn normalized(&self) -> Self {
         let magnitude = self.magnitude();
-        let bivec = if magnitude == 0.0 {
-            Bivec4::ZERO
+        let bivec = if magnitude == S::ZERO {
+            GenericBivec4::ZERO
         } else {
             self.bivec.scaled(magnitude.recip())
         };
         Self { bivec }
     }
 
-    pub fn scaled(&self, scale: f32) -> Self {
+    pub fn scaled(&self, scale: S) -> Self {
         Self {
             bivec: self.bivec.scaled(scale),
         }
     }
 
-    pub fn square(&self) -> f32 {
+    pub fn square(&self) -> S {
         self.bivec.square().c
     }
 
-    pub fn magnitude(&self) -> f32 {
+    pub fn magnitude(&self) -> S {
         self.square().abs().sqrt()
     }
 
     /// Bivector exponential, essentially maps from a polar representation, angle * Bivector, to a Rotor that transforms by that angle.
-    pub fn exp(&self) -> Rotor4 {
+    pub fn exp(&self) -> GenericRotor4<S> {
         // Special case of bivector exponential for *simple* bivectors, e^{theta * B} = cos(theta) + sin(theta) B, iff B^2 = -1.
         // Same proof as e^{i*pi} = -1
         let theta = self.magnitude();
         let normalized = self.normalized();
-        Rotor4 {
+        GenericRotor4 {
             c: theta.cos(),
             bivec: normalized.bivec.scaled(theta.sin()),
-            xyzw: 0.0,
+            xyzw: S::ZERO,
         }
     }
 }
 
+/// `S` is the precision of the rotor the error came from.
 #[derive(Clone, Copy, Debug, Error)]
-pub enum RotorError {
+pub enum RotorError<S: Scalar = f32> {
     #[error("Bivector {0:?} was not simple, had square with quadvec component {1:?}")]
-    NotSimple(Bivec4, f32),
+    NotSimple(GenericBivec4<S>, S),
+    #[error("Matrix {0:?} was not a rotation, must be orthonormal with determinant 1")]
+    NotRotation([[S; 4]; 4]),
+    #[error("Transform has translation {0:?}, a 4x4 matrix can only hold rotation and scale")]
+    Translated([S; 4]),
 }
-impl TryFrom<Bivec4> for SimpleBivec4 {
-    type Error = RotorError;
-    fn try_from(value: Bivec4) -> Result<Self, Self::Error> {
+impl<S: Scalar> TryFrom<GenericBivec4<S>> for GenericSimpleBivec4<S> {
+    type Error = RotorError<S>;
+    fn try_from(value: GenericBivec4<S>) -> Result<Self, Self::Error> {
         let square = value.square();
-        if approx_equal(square.xyzw, 0.0) {
-            Ok(SimpleBivec4 { bivec: value })
+        if approx_equal(square.xyzw, S::ZERO) {
+            Ok(GenericSimpleBivec4 { bivec: value })
         } else {
             Err(RotorError::NotSimple(value, square.xyzw))
         }
     }
 }
-impl From<SimpleBivec4> for Bivec4 {
-    fn from(value: SimpleBivec4) -> Self {
+impl<S: Scalar> TryFrom<[[S; 4]; 4]> for GenericRotor4<S> {
+    type Error = RotorError<S>;
+    /// Same as [Rotor4::from_mat4_array], takes the matrix in column-major order.
+    fn try_from(value: [[S; 4]; 4]) -> Result<Self, Self::Error> {
+        Self::from_mat4_array(value)
+    }
+}
+/// Components in the order `[xy, xz, xw, yz, wy, zw]`, for handing bivectors to other libraries as 6-vectors.
+impl<S: Scalar> From<GenericBivec4<S>> for [S; 6] {
+    fn from(value: GenericBivec4<S>) -> Self {
+        [value.xy, value.xz, value.xw, value.yz, value.wy, value.zw]
+    }
+}
+/// Takes components in the order `[xy, xz, xw, yz, wy, zw]`.
+impl<S: Scalar> From<[S; 6]> for GenericBivec4<S> {
+    fn from([xy, xz, xw, yz, wy, zw]: [S; 6]) -> Self {
+        Self {
+            xy,
+            xz,
+            xw,
+            yz,
+            wy,
+            zw,
+        }
+    }
+}
+impl<S: Scalar> From<GenericSimpleBivec4<S>> for GenericBivec4<S> {
+    fn from(value: GenericSimpleBivec4<S>) -> Self {
         value.bivec
     }
 }
 
 /// Addition for *simple* bivectors, the sum of simple bivectors (in 4D)
 /// is not necessarily simple so this returns a [Bivec4].
-impl Add for SimpleBivec4 {
-    type Output = Bivec4;
+impl<S: Scalar> Add for GenericSimpleBivec4<S> {
+    type Output = GenericBivec4<S>;
     fn add(self, rhs: Self) -> Self::Output {
         self.bivec + rhs.bivec
     }
 }
 
-impl Neg for SimpleBivec4 {
+impl<S: Scalar> Neg for GenericSimpleBivec4<S> {
     type Output = Self;
     fn neg(self) -> Self::Output {
         Self { bivec: -self.bivec }
     }
 }
 
+/// Same fields as [GenericRotor4], deserialized first so that rotors always go through [Rotor4::new] and come out normalized.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RotorComponents<S> {
+    c: S,
+    bivec: GenericBivec4<S>,
+    xyzw: S,
+}
+
+#[cfg(feature = "serde")]
+impl<S: Scalar> From<RotorComponents<S>> for GenericRotor4<S> {
+    fn from(RotorComponents { c, bivec, xyzw }: RotorComponents<S>) -> Self {
+        Self::new(c, bivec, xyzw)
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 /// A scalar added to a 4D quadvector, used by several operations on [Rotor4] and [Bivec4].
-struct ScalarPlusQuadvec4 {
-    c: f32,
-    xyzw: f32,
+struct ScalarPlusQuadvec4<S> {
+    c: S,
+    xyzw: S,
 }
 
-impl Mul<Bivec4> for ScalarPlusQuadvec4 {
-    type Output = Bivec4;
-    fn mul(self, rhs: Bivec4) -> Self::Output {
-        Bivec4 {
+impl<S: Scalar> Mul<GenericBivec4<S>> for ScalarPlusQuadvec4<S> {
+    type Output = GenericBivec4<S>;
+    fn mul(self, rhs: GenericBivec4<S>) -> Self::Output {
+        GenericBivec4 {
             xy: self.c * rhs.xy - self.xyzw * rhs.zw,
             xz: self.c * rhs.xz - self.xyzw * rhs.wy,
             xw: self.c * rhs.xw - self.xyzw * rhs.yz,
@@ -753,21 +1024,33 @@ impl Mul<Bivec4> for ScalarPlusQuadvec4 {
         }
     }
 }
-impl Mul<ScalarPlusQuadvec4> for Bivec4 {
-    type Output = Bivec4;
-    fn mul(self, rhs: ScalarPlusQuadvec4) -> Self::Output {
+impl<S: Scalar> Mul<ScalarPlusQuadvec4<S>> for GenericBivec4<S> {
+    type Output = GenericBivec4<S>;
+    fn mul(self, rhs: ScalarPlusQuadvec4<S>) -> Self::Output {
         rhs * self
     }
 }
 
-fn approx_equal(a: f32, b: f32) -> bool {
-    crate::util::approx_equal(a, b, EPSILON)
+/// Hamilton product of two quaternions stored as `[x, y, z, w]`.
+fn quaternion_product<S: Scalar>(a: [S; 4], b: [S; 4]) -> [S; 4] {
+    let [ax, ay, az, aw] = a;
+    let [bx, by, bz, bw] = b;
+    [
+        aw * bx + ax * bw + ay * bz - az * by,
+        aw * by - ax * bz + ay * bw + az * bx,
+        aw * bz + ax * by - ay * bx + az * bw,
+        aw * bw - ax * bx - ay * by - az * bz,
+    ]
+}
+
+fn approx_equal<S: Scalar>(a: S, b: S) -> bool {
+    crate::util::approx_equal(a, b, S::EPSILON)
 }
 
 #[cfg(test)]
 mod test {
     //! Why so many tests? Because this module is loaded with arcane bullshit and I'll be damned if I'm figuring it all out again.
-    use std::f32::consts::{FRAC_PI_3, FRAC_PI_4, FRAC_PI_6, PI, SQRT_2};
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, FRAC_PI_6, PI, SQRT_2, TAU};
 
     use proptest::proptest;
 
@@ -776,6 +1059,19 @@ mod test {
     use super::test_util::*;
     use super::*;
 
+    const EPSILON: f32 = <f32 as Scalar>::EPSILON;
+
+    fn to_dbivec(bivec: Bivec4) -> DBivec4 {
+        DBivec4 {
+            xy: bivec.xy as f64,
+            xz: bivec.xz as f64,
+            xw: bivec.xw as f64,
+            yz: bivec.yz as f64,
+            wy: bivec.wy as f64,
+            zw: bivec.zw as f64,
+        }
+    }
+
     #[test]
     fn test_rotor_new_zero() {
         let rotor = dbg!(Rotor4::new(0.0, Bivec4::ZERO, 0.0));
@@ -808,6 +1104,88 @@ mod test {
         assert!(rotor_approx_equal(got, expected));
     }
 
+    #[test]
+    fn test_rotor_from_orthonormal_frame() {
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xy: FRAC_PI_3,
+            zw: FRAC_PI_6,
+            xw: FRAC_PI_4,
+            ..Bivec4::ZERO
+        });
+        let matrix: glam::Mat4 = rotor.into_mat4();
+        dbg!(rotor);
+
+        let got = dbg!(Rotor4::from_orthonormal_frame(
+            matrix.x_axis,
+            matrix.y_axis,
+            matrix.z_axis,
+            matrix.w_axis
+        )
+        .unwrap());
+
+        let got_matrix: glam::Mat4 = got.into_mat4();
+        assert!(got_matrix.abs_diff_eq(matrix, EPSILON));
+    }
+
+    #[test]
+    fn test_rotor_from_orthonormal_frame_rejects_reflection() {
+        let got = Rotor4::from_orthonormal_frame(
+            glam::Vec4::X,
+            glam::Vec4::Y,
+            glam::Vec4::Z,
+            -glam::Vec4::W,
+        );
+
+        assert!(matches!(got, Err(RotorError::NotRotation(_))));
+    }
+
+    #[test]
+    fn test_rotor_look_to_rotates_axes_onto_directions() {
+        let forward = glam::vec4(1.0, 1.0, 0.0, 0.0);
+        // Not orthogonal to forward, only the perpendicular part counts.
+        let up = glam::vec4(0.0, 1.0, 0.0, 3.0);
+        let over = glam::vec4(0.0, 0.0, -2.0, 1.0);
+
+        let rotor = dbg!(Rotor4::look_to(forward, up, over).unwrap());
+        let got_forward = dbg!(rotor.transform(glam::Vec4::W));
+        let got_up = dbg!(rotor.transform(glam::Vec4::Y));
+        let got_over = dbg!(rotor.transform(glam::Vec4::Z));
+        let got_x = dbg!(rotor.transform(glam::Vec4::X));
+
+        assert!(vector_approx_equal(got_forward, forward.normalize()));
+        assert!(approx_equal(got_up.dot(forward), 0.0));
+        assert!(got_up.dot(up) > 0.0);
+        assert!(approx_equal(got_over.dot(forward), 0.0));
+        assert!(approx_equal(got_over.dot(up), 0.0));
+        assert!(got_over.dot(over) > 0.0);
+        let handedness = glam::Mat4::from_cols(got_x, got_up, got_over, got_forward).determinant();
+        assert!(approx_equal(handedness, 1.0));
+    }
+
+    #[test]
+    fn test_rotor_look_to_rejects_dependent_directions() {
+        let forward = glam::vec4(1.0, 1.0, 0.0, 0.0);
+
+        let got = Rotor4::look_to(forward, forward * 2.0, glam::Vec4::Z);
+
+        assert!(matches!(got, Err(RotorError::NotRotation(_))));
+    }
+
+    #[test]
+    fn test_rotor_look_at_faces_target() {
+        let eye = glam::vec4(1.0, 2.0, 3.0, 4.0);
+        let target = glam::vec4(1.0, 2.0, 3.0, -4.0);
+
+        let rotor = dbg!(Rotor4::look_at(eye, target, glam::Vec4::Y, glam::Vec4::Z).unwrap());
+        let got = dbg!(rotor.transform(glam::Vec4::W));
+
+        assert!(vector_approx_equal(got, glam::vec4(0.0, 0.0, 0.0, -1.0)));
+        assert!(vector_approx_equal(
+            rotor.transform(glam::Vec4::Y),
+            glam::Vec4::Y
+        ));
+    }
+
     #[test]
     fn test_rotor_between_transform_simple_180() {
         let rotor = Rotor4::between(
@@ -1251,6 +1629,129 @@ mod test {
         assert!(rotor_approx_equal(got, expected));
     }
 
+    #[test]
+    fn test_rotor_integrate_double_rotation() {
+        let omega = Bivec4 {
+            xy: FRAC_PI_2,
+            zw: PI,
+            ..Bivec4::ZERO
+        };
+        let dt = 0.5;
+        let vector = glam::Vec4::new(1.0, 0.0, 1.0, 0.0);
+        // Quarter turn in xy, half turn in zw.
+        let expected = glam::Vec4::new(
+            FRAC_PI_4.cos(),
+            FRAC_PI_4.sin(),
+            FRAC_PI_2.cos(),
+            FRAC_PI_2.sin(),
+        );
+
+        let got = dbg!(Rotor4::IDENTITY.integrate(omega, dt).transform(vector));
+
+        assert!(vector_approx_equal(got, expected));
+    }
+
+    #[test]
+    fn test_rotor_integrate_many_steps_stays_normalized() {
+        let omega = Bivec4 {
+            xy: 1.0,
+            xz: -2.0,
+            xw: 0.5,
+            yz: 3.0,
+            wy: -1.5,
+            zw: 0.25,
+        };
+        let dt = 1.0 / 60.0;
+        let mut rotor = Rotor4::IDENTITY;
+        for _ in 0..10_000 {
+            rotor = rotor.integrate(omega, dt);
+        }
+
+        let error = dbg!(rotor.normalization_error());
+
+        assert!(approx_equal(error.c, 1.0));
+        assert!(approx_equal(error.xyzw, 0.0));
+    }
+
+    #[test]
+    fn test_drotor_same_transform_as_rotor() {
+        let angles = Bivec4 {
+            xy: 0.5,
+            xz: -1.0,
+            xw: 2.0,
+            yz: 0.25,
+            wy: -0.75,
+            zw: 1.5,
+        };
+        let rotor = Rotor4::from_bivec_angles(angles);
+        let drotor = DRotor4::from_bivec_angles(to_dbivec(angles));
+        let vector = glam::Vec4::new(1.0, 2.0, 3.0, 4.0);
+
+        let got = dbg!(drotor.transform(vector.as_dvec4()));
+        let expected = dbg!(rotor.transform(vector));
+
+        assert!(got.as_vec4().abs_diff_eq(expected, EPSILON));
+    }
+
+    #[test]
+    fn test_drotor_many_small_steps_is_precise() {
+        let angles = DBivec4 {
+            xy: 1.0,
+            xz: 0.5,
+            xw: -0.25,
+            yz: 0.75,
+            wy: 2.0,
+            zw: -1.0,
+        };
+        const STEPS: usize = 10_000;
+        let step = DRotor4::from_bivec_angles(angles.scaled(1.0 / STEPS as f64));
+        let mut rotor = DRotor4::IDENTITY;
+        for _ in 0..STEPS {
+            rotor = rotor.compose(step);
+        }
+        let vector = glam::DVec4::new(1.0, 2.0, 3.0, 4.0);
+
+        let got = dbg!(rotor.transform(vector));
+        let expected = dbg!(DRotor4::from_bivec_angles(angles).transform(vector));
+
+        assert!(got.abs_diff_eq(expected, 1e-9));
+    }
+
+    #[test]
+    fn test_rotor_angular_velocity_to_goes_short_way() {
+        let from = Rotor4::IDENTITY;
+        let to = Rotor4::from_bivec_angles(Bivec4 {
+            xy: 3.0 * FRAC_PI_2,
+            ..Bivec4::ZERO
+        });
+        let expected = Bivec4 {
+            xy: -FRAC_PI_4,
+            ..Bivec4::ZERO
+        };
+
+        let got = dbg!(from.angular_velocity_to(&to, 2.0));
+
+        assert!(bivec_approx_equal(got, expected));
+    }
+
+    #[test]
+    fn test_rotor_integrate_angular_velocity_to_near_double_half_turn() {
+        let from = Rotor4::IDENTITY;
+        let to = Rotor4::from_bivec_angles(Bivec4 {
+            xy: 3.1,
+            zw: 3.1,
+            ..Bivec4::ZERO
+        });
+        let vector = glam::Vec4::new(1.0, 2.0, 3.0, 4.0);
+
+        let got = dbg!(from.integrate(from.angular_velocity_to(&to, 0.5), 0.5));
+
+        assert!(vector_approx_equal(
+            got.transform(vector),
+            to.transform(vector)
+        ));
+    }
+
     #[test]
     fn test_bivec_neg() {
         let val = Bivec4 {
@@ -1717,7 +2218,192 @@ mod test {
         assert!(bivec_approx_equal(result2, expected));
     }
 
+    #[test]
+    fn test_rotor_from_quaternion_pair_3d_rotation() {
+        // Conjugating by a quaternion is the usual 3D rotation, leaving w alone.
+        let quat = glam::Quat::from_rotation_z(FRAC_PI_3);
+        let expected = Rotor4::from_bivec_angles(Bivec4 {
+            xy: FRAC_PI_3,
+            ..Bivec4::ZERO
+        });
+        dbg!(expected);
+
+        let got = dbg!(Rotor4::from_quaternion_pair(quat, quat.inverse()));
+
+        assert!(rotor_approx_equal(got, expected));
+        assert!(vector_approx_equal(
+            got.transform(glam::Vec4::X),
+            glam::vec4(0.5, 0.75f32.sqrt(), 0.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn test_rotor_from_quaternion_pair_left_isoclinic() {
+        // A left multiplication alone rotates two orthogonal planes by the same angle.
+        let left = glam::Quat::from_rotation_z(FRAC_PI_3);
+        let expected_matrix = glam::Mat4::from_cols(
+            glam::vec4(0.0, 1.0, 0.0, 0.0) * FRAC_PI_6.sin() + glam::Vec4::X * FRAC_PI_6.cos(),
+            glam::vec4(-1.0, 0.0, 0.0, 0.0) * FRAC_PI_6.sin() + glam::Vec4::Y * FRAC_PI_6.cos(),
+            glam::vec4(0.0, 0.0, 0.0, -1.0) * FRAC_PI_6.sin() + glam::Vec4::Z * FRAC_PI_6.cos(),
+            glam::vec4(0.0, 0.0, 1.0, 0.0) * FRAC_PI_6.sin() + glam::Vec4::W * FRAC_PI_6.cos(),
+        );
+        dbg!(expected_matrix);
+
+        let got = dbg!(Rotor4::from_quaternion_pair(left, glam::Quat::IDENTITY));
+
+        let got_matrix: glam::Mat4 = dbg!(got.into_mat4());
+        assert!(got_matrix.abs_diff_eq(expected_matrix, EPSILON));
+    }
+
+    #[test]
+    fn test_bivec_array_round_trip() {
+        let bivec = Bivec4 {
+            xy: 1.0,
+            xz: 2.0,
+            xw: 3.0,
+            yz: 4.0,
+            wy: 5.0,
+            zw: 6.0,
+        };
+
+        let arr: [f32; 6] = bivec.into();
+
+        assert_eq!(arr, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert!(bivec_approx_equal(Bivec4::from(arr), bivec));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_rotor_serde_round_trip() {
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xy: 0.3,
+            zw: -1.1,
+            ..Bivec4::ZERO
+        });
+
+        let json = serde_json::to_string(&rotor).unwrap();
+        let got: Rotor4 = dbg!(serde_json::from_str(&json).unwrap());
+
+        assert!(rotor_approx_equal(got, rotor));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_rotor_deserialize_normalizes() {
+        let json = r#"{"c": 3.0, "bivec": {"xy": 4.0, "xz": 0.0, "xw": 0.0, "yz": 0.0, "wy": 0.0, "zw": 0.0}, "xyzw": 0.0}"#;
+
+        let got: Rotor4 = dbg!(serde_json::from_str(json).unwrap());
+
+        let expected = Rotor4::new(
+            0.6,
+            Bivec4 {
+                xy: 0.8,
+                ..Bivec4::ZERO
+            },
+            0.0,
+        );
+        assert!(rotor_approx_equal(got, expected));
+        assert!(approx_equal(got.c(), 0.6));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_simple_bivec_deserialize_rejects_non_simple() {
+        let json = r#"{"xy": 1.0, "xz": 0.0, "xw": 0.0, "yz": 0.0, "wy": 0.0, "zw": 1.0}"#;
+
+        let got = serde_json::from_str::<SimpleBivec4>(json);
+
+        assert!(dbg!(got).is_err());
+    }
+
+    #[test]
+    fn test_rotor_from_mat4_array_rejects_non_rotations() {
+        let scaled = glam::Mat4::from_diagonal(glam::vec4(2.0, 2.0, 2.0, 2.0));
+        let reflected = glam::Mat4::from_diagonal(glam::vec4(1.0, -1.0, 1.0, 1.0));
+        let sheared = glam::Mat4::from_cols(
+            glam::Vec4::X,
+            glam::vec4(0.5, 1.0, 0.0, 0.0),
+            glam::Vec4::Z,
+            glam::Vec4::W,
+        );
+
+        for matrix in [scaled, reflected, sheared] {
+            let got = dbg!(Rotor4::try_from(matrix.to_cols_array_2d()));
+            assert!(matches!(got, Err(RotorError::NotRotation(_))));
+        }
+    }
+
+    #[test]
+    fn test_rotor_from_mat4_array_accepts_nearly_orthonormal() {
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xy: FRAC_PI_3,
+            zw: FRAC_PI_4,
+            ..Bivec4::ZERO
+        });
+        // Rounded like it might be when stored in a file.
+        let arr = rotor
+            .into_mat4_array()
+            .map(|col| col.map(|x| (x * 1e4).round() / 1e4));
+
+        let got = dbg!(Rotor4::from_mat4_array(arr).unwrap());
+
+        let got_matrix: glam::Mat4 = got.into_mat4();
+        let expected_matrix: glam::Mat4 = rotor.into_mat4();
+        assert!(got_matrix.abs_diff_eq(expected_matrix, EPSILON));
+    }
+
+    #[test]
+    fn test_rotor_from_rotation_matrix_isoclinic() {
+        // Equal scalar and quadvector parts, the awkward case for recovering a rotor from a matrix.
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xy: PI / 2.0,
+            zw: PI / 2.0,
+            ..Bivec4::ZERO
+        });
+        dbg!(rotor);
+
+        let got = dbg!(Rotor4::from_rotation_mat4_array(rotor.into_mat4_array()));
+
+        assert!(rotor_approx_equal(got, rotor));
+    }
+
+    #[test]
+    fn test_rotor_from_rotation_matrix_half_turn() {
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xz: PI,
+            ..Bivec4::ZERO
+        });
+        let expected_matrix: glam::Mat4 = rotor.into_mat4();
+        dbg!(expected_matrix);
+
+        let got = dbg!(Rotor4::from_rotation_mat4_array(rotor.into_mat4_array()));
+        let got_matrix: glam::Mat4 = dbg!(got.into_mat4());
+
+        assert!(got_matrix.abs_diff_eq(expected_matrix, EPSILON));
+    }
+
     proptest! {
+        #[test]
+        fn test_rotor_integrate_angular_velocity_to_is_other_fuzz_test(from in arbitrary_rotor4(), to in arbitrary_rotor4(), dt in 0.01f32..10.0, vector in vec4_uniform(1.0)) {
+            let omega = dbg!(from.angular_velocity_to(&to, dt));
+
+            let got = dbg!(from.integrate(omega, dt));
+
+            assert!(vector_approx_equal(got.transform(vector), to.transform(vector)));
+        }
+
+        #[test]
+        fn test_rotor_integrate_steps_same_as_one_step_fuzz_test(rotor in arbitrary_rotor4(), omega in arbitrary_bivec4(PI)) {
+            let mut stepped = rotor;
+            for _ in 0..10 {
+                stepped = stepped.integrate(omega, 0.1);
+            }
+
+            let got = dbg!(rotor.integrate(omega, 1.0));
+
+            assert!(rotor_approx_equal(got, stepped));
+        }
+
         #[test]
         fn test_rotor_compose_identity_is_same_fuzz_test(rotor in arbitrary_rotor4()) {
             let left = dbg!(Rotor4::IDENTITY.compose(rotor));
@@ -1747,6 +2433,16 @@ mod test {
                 assert!(rotor_approx_equal(right, Rotor4::IDENTITY));
         }
 
+        #[test]
+        fn test_drotor_compose_inverse_is_identity_fuzz_test(angles in arbitrary_bivec4(TAU), vector in vec4_uniform(1.0)) {
+            let rotor = DRotor4::from_bivec_angles(to_dbivec(angles));
+            let vector = vector.as_dvec4();
+
+            let got = dbg!(rotor.compose(rotor.inverse()).transform(vector));
+
+            assert!(got.abs_diff_eq(vector, 1e-12));
+        }
+
         #[test]
         fn test_rotor_compose_stability_fuzz_test(rotor in arbitrary_rotor4()) {
             const COMPOSE_ITERS: usize = 100;
@@ -1806,6 +2502,56 @@ mod test {
             assert!(rotor_approx_equal(got, rotor) || rotor_approx_equal(minus_got, rotor));
         }
 
+        #[test]
+        fn test_rotor_from_rotation_matrix_fuzz_test(rotor in arbitrary_rotor4()) {
+            let got = dbg!(Rotor4::from_mat4_array(rotor.into_mat4_array()).unwrap());
+
+            let minus_got = Rotor4 {
+                c: -got.c,
+                bivec: -got.bivec,
+                xyzw: -got.xyzw,
+            };
+            assert!(rotor_approx_equal(got, rotor) || rotor_approx_equal(minus_got, rotor));
+        }
+
+        #[test]
+        fn test_rotor_quaternion_pair_round_trip_fuzz_test(rotor in arbitrary_rotor4()) {
+            let (left, right): (glam::Quat, glam::Quat) = dbg!(rotor.to_quaternion_pair());
+            let got = dbg!(Rotor4::from_quaternion_pair(left, right));
+
+            assert!(left.is_normalized() && right.is_normalized());
+            assert!(rotor_approx_equal(got, rotor));
+        }
+
+        #[test]
+        fn test_rotor_quaternion_pair_transform_fuzz_test(rotor in arbitrary_rotor4(), vector in vec4_uniform(4.0)) {
+            let (left, right): (glam::Quat, glam::Quat) = rotor.to_quaternion_pair();
+            let expected = glam::Vec4::from(left * glam::Quat::from_vec4(vector) * right);
+            dbg!(expected);
+
+            let got = dbg!(rotor.transform(vector));
+
+            assert!(vector_approx_equal(got, expected));
+        }
+
+        #[test]
+        fn test_rotor_compose_same_as_quaternion_products_fuzz_test(rotor1 in arbitrary_rotor4(), rotor2 in arbitrary_rotor4()) {
+            // Composing rotates by rotor1 then rotor2, so v -> left2 * left1 * v * right1 * right2.
+            let (left1, right1): (glam::Quat, glam::Quat) = rotor1.to_quaternion_pair();
+            let (left2, right2): (glam::Quat, glam::Quat) = rotor2.to_quaternion_pair();
+            let expected = Rotor4::from_quaternion_pair(left2 * left1, right1 * right2);
+            dbg!(expected);
+
+            let got = dbg!(rotor1.compose(rotor2));
+
+            let minus_got = Rotor4 {
+                c: -got.c,
+                bivec: -got.bivec,
+                xyzw: -got.xyzw,
+            };
+            assert!(rotor_approx_equal(got, expected) || rotor_approx_equal(minus_got, expected));
+        }
+
         #[test]
         fn test_rotor_from_into_bivec_angles_fuzz_test(bivec in arbitrary_bivec4(1.0), angle in -PI..PI) {
             // Gets ambiguous if the total rotation is >PI, so 'normalize'
@@ -1856,6 +2602,32 @@ mod test {
                 assert!(prod.abs_diff_eq(glam::Mat4::IDENTITY, EPSILON));
         }
 
+        #[test]
+        fn test_rotor_transform_matches_matrix_fuzz_test(rotor in arbitrary_rotor4(), vec in vec4_uniform(4.0)) {
+            let matrix: glam::Mat4 = rotor.into_mat4();
+
+            let got = dbg!(rotor.transform(vec));
+
+            assert!(got.abs_diff_eq(matrix * vec, EPSILON));
+        }
+
+        #[test]
+        fn test_rotor_transform_each_matches_transform_fuzz_test(rotor in arbitrary_rotor4(), vec1 in vec4_uniform(4.0), vec2 in vec4_uniform(4.0)) {
+            let mut vertices = [vec1, vec2].map(|position| crate::mesh::Vertex4 { position });
+
+            rotor.transform_each(&mut vertices);
+
+            assert!(vertices[0].position.abs_diff_eq(rotor.transform(vec1), EPSILON));
+            assert!(vertices[1].position.abs_diff_eq(rotor.transform(vec2), EPSILON));
+        }
+
+        #[test]
+        fn test_rotor_transform_vertex_matches_vector_fuzz_test(rotor in arbitrary_rotor4(), vec in vec4_uniform(4.0)) {
+            let got = rotor.transform(crate::mesh::Vertex4 { position: vec });
+
+            assert!(got.position.abs_diff_eq(rotor.transform(vec), EPSILON));
+        }
+
         #[test]
         fn test_rotor_transform_preserves_scale_fuzz_test(rotor in arbitrary_rotor4(), vec in vec4_uniform(4.0)) {
             let got = dbg!(rotor.pow(0.5).transform(vec));
@@ -1888,7 +2660,10 @@ mod test {
         }
     }
 
-    fn scalar_plus_quadvec_approx_equal(a: ScalarPlusQuadvec4, b: ScalarPlusQuadvec4) -> bool {
+    fn scalar_plus_quadvec_approx_equal(
+        a: ScalarPlusQuadvec4<f32>,
+        b: ScalarPlusQuadvec4<f32>,
+    ) -> bool {
         approx_equal(a.c, b.c) && approx_equal(a.xyzw, b.xyzw)
     }
 }
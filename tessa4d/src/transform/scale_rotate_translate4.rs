@@ -0,0 +1,405 @@
+use crate::{linear_algebra::Vector4, util::lerp};
+
+use super::{
+    affine4::Affine4,
+    rotate_scale_translate4::RotateScaleTranslate4,
+    rotor4::Rotor4,
+    traits::{Compose, InterpolateWith, Transform, TryInverse},
+};
+
+/// Transform with per-axis scale, rotation, and translation.
+/// Applies scale along the local axes, then rotation, then translation, the same order as Bevy's `Transform`.
+///
+/// Scaling a rotated transform along different axes shears it, which this can't represent.
+/// So composing two of these gives an [Affine4], use [ScaleRotateTranslate4::compose_approx] to stay in this form.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScaleRotateTranslate4<V> {
+    pub scale: V,
+    pub rotation: Rotor4,
+    pub translation: V,
+}
+
+impl<V: Vector4> ScaleRotateTranslate4<V> {
+    pub const IDENTITY: Self = Self {
+        scale: V::ONE,
+        rotation: Rotor4::IDENTITY,
+        translation: V::ZERO,
+    };
+
+    /// Returns a matrix that represents the combined scale and rotation from this transform.
+    pub fn get_scale_rotate_matrix(&self) -> V::Matrix4 {
+        Affine4::from(*self).linear
+    }
+
+    /// Returns a transform that applies this transform, and then the given rotation.
+    pub fn rotated(&self, rotation: Rotor4) -> Self {
+        Self {
+            scale: self.scale,
+            rotation: self.rotation.compose(rotation),
+            translation: rotation.transform(self.translation),
+        }
+    }
+
+    /// Returns a transform that applies this transform, and then the given uniform scale.
+    pub fn scaled(&self, scale: f32) -> Self {
+        Self {
+            scale: self.scale * scale,
+            rotation: self.rotation,
+            translation: self.translation * scale,
+        }
+    }
+
+    /// Returns a transform that applies this transform, and then the given translation.
+    pub fn translated(&self, offset: V) -> Self {
+        Self {
+            scale: self.scale,
+            rotation: self.rotation,
+            translation: self.translation + offset,
+        }
+    }
+
+    /// Composes two transforms, approximating the result if it shears (when `other` has non-uniform scale and `self` is rotated).
+    /// The approximation keeps the rotation and stretch that are closest to the exact [Affine4], see [Affine4::approx_scale_rotate_translate].
+    pub fn compose_approx(&self, other: Self) -> Self {
+        let composed = self.compose(other);
+        composed.approx_scale_rotate_translate().unwrap_or(Self {
+            // Singular, so there is no closest rotation. Still scale and place it roughly right.
+            scale: mul_elementwise(self.scale, other.scale),
+            rotation: self.rotation.compose(other.rotation),
+            translation: composed.translation,
+        })
+    }
+}
+
+impl<V: Vector4> Default for ScaleRotateTranslate4<V> {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl<V: Vector4> From<RotateScaleTranslate4<V>> for ScaleRotateTranslate4<V> {
+    fn from(value: RotateScaleTranslate4<V>) -> Self {
+        Self {
+            scale: V::ONE * value.scale,
+            rotation: value.rotation,
+            translation: value.translation,
+        }
+    }
+}
+
+impl<V: Vector4> From<ScaleRotateTranslate4<V>> for Affine4<V> {
+    fn from(value: ScaleRotateTranslate4<V>) -> Self {
+        let rotation: V::Matrix4 = value.rotation.into_mat4();
+        Self {
+            linear: rotation * Affine4::from_scale(value.scale).linear,
+            translation: value.translation,
+        }
+    }
+}
+
+impl<V: Vector4> Transform<V> for ScaleRotateTranslate4<V> {
+    fn transform(&self, operand: V) -> V {
+        self.rotation
+            .transform(mul_elementwise(operand, self.scale))
+            + self.translation
+    }
+
+    /// Builds the rotation matrix once and applies it to every operand.
+    fn transform_each<'a, I>(&self, operands: I)
+    where
+        I: IntoIterator<Item = &'a mut V>,
+        V: 'a,
+    {
+        let matrix: V::Matrix4 = self.rotation.into_mat4();
+        for operand in operands {
+            *operand = matrix * mul_elementwise(*operand, self.scale) + self.translation;
+        }
+    }
+}
+
+/// Exact, but generally shears so the result is an [Affine4].
+impl<V: Vector4> Compose<ScaleRotateTranslate4<V>> for ScaleRotateTranslate4<V> {
+    type Composed = Affine4<V>;
+    fn compose(&self, other: ScaleRotateTranslate4<V>) -> Self::Composed {
+        Affine4::from(*self).compose(Affine4::from(other))
+    }
+}
+
+/// Rotating and uniformly scaling afterwards never shears, so this stays exact.
+impl<V: Vector4> Compose<RotateScaleTranslate4<V>> for ScaleRotateTranslate4<V> {
+    type Composed = ScaleRotateTranslate4<V>;
+    fn compose(&self, other: RotateScaleTranslate4<V>) -> Self::Composed {
+        self.rotated(other.rotation)
+            .scaled(other.scale)
+            .translated(other.translation)
+    }
+}
+
+impl<V: Vector4> Compose<ScaleRotateTranslate4<V>> for RotateScaleTranslate4<V> {
+    type Composed = Affine4<V>;
+    fn compose(&self, other: ScaleRotateTranslate4<V>) -> Self::Composed {
+        Affine4::from(*self).compose(Affine4::from(other))
+    }
+}
+
+impl<V: Vector4> InterpolateWith for ScaleRotateTranslate4<V> {
+    type Scalar = f32;
+    fn interpolate_with(&self, other: &Self, fraction: f32) -> Self {
+        Self {
+            scale: lerp(self.scale, other.scale, fraction),
+            rotation: self.rotation.interpolate_with(&other.rotation, fraction),
+            translation: lerp(self.translation, other.translation, fraction),
+        }
+    }
+}
+
+/// The inverse scales along rotated axes, so it is only an [Affine4]. None if any scale component is zero.
+impl<V: Vector4> TryInverse for ScaleRotateTranslate4<V> {
+    type Inverted = Affine4<V>;
+    fn try_inverse(&self) -> Option<Self::Inverted> {
+        Affine4::from(*self).try_inverse()
+    }
+}
+
+fn mul_elementwise<V: Vector4>(a: V, b: V) -> V {
+    V::new(a.x() * b.x(), a.y() * b.y(), a.z() * b.z(), a.w() * b.w())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::proptest;
+    use std::f32::consts::PI;
+
+    use crate::{
+        transform::rotor4::{
+            test_util::{arbitrary_rotor4, rotor_approx_equal},
+            Bivec4,
+        },
+        util::test::proptest::{vec4_uniform, vec4_uniform_between},
+    };
+
+    const EPS: f32 = 1e-3;
+
+    fn quarter_turn_xy() -> Rotor4 {
+        Rotor4::from_bivec_angles(Bivec4 {
+            xy: PI / 2.0,
+            ..Bivec4::ZERO
+        })
+    }
+
+    #[test]
+    fn transform_scales_before_rotating() {
+        let transform = ScaleRotateTranslate4 {
+            scale: glam::vec4(2.0, 3.0, 4.0, 5.0),
+            rotation: quarter_turn_xy(),
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let vector = glam::vec4(1.0, 1.0, 1.0, 1.0);
+        let expected = glam::vec4(-2.0, 4.0, 7.0, 9.0);
+
+        let got = dbg!(transform.transform(vector));
+        let got_matrix = dbg!(transform.get_scale_rotate_matrix() * vector + transform.translation);
+
+        assert!(got.abs_diff_eq(expected, EPS));
+        assert!(got_matrix.abs_diff_eq(expected, EPS));
+    }
+
+    #[test]
+    fn transform_each_matches_transform() {
+        let transform = ScaleRotateTranslate4 {
+            scale: glam::vec4(2.0, 3.0, 4.0, 5.0),
+            rotation: quarter_turn_xy(),
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let mut vectors = [
+            glam::vec4(1.0, 1.0, 1.0, 1.0),
+            glam::vec4(-1.0, 0.5, 2.0, 0.0),
+        ];
+        let expected = vectors.map(|v| transform.transform(v));
+
+        transform.transform_each(&mut vectors);
+
+        for (got, expected) in vectors.into_iter().zip(expected) {
+            assert!(dbg!(got).abs_diff_eq(expected, EPS));
+        }
+    }
+
+    #[test]
+    fn compose_is_exact() {
+        let transform1 = ScaleRotateTranslate4 {
+            scale: glam::vec4(1.0, 2.0, 1.0, 1.0),
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xy: PI / 4.0,
+                ..Bivec4::ZERO
+            }),
+            translation: glam::vec4(1.0, 0.0, 0.0, 0.0),
+        };
+        let transform2 = ScaleRotateTranslate4 {
+            scale: glam::vec4(3.0, 1.0, 1.0, 1.0),
+            rotation: Rotor4::IDENTITY,
+            translation: glam::vec4(0.0, 1.0, 0.0, 0.0),
+        };
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+        let expected = transform2.transform(transform1.transform(vector));
+        dbg!(expected);
+
+        let got = dbg!(transform1.compose(transform2).transform(vector));
+
+        assert!(got.abs_diff_eq(expected, EPS));
+    }
+
+    #[test]
+    fn compose_approx_without_shear_is_exact() {
+        // Stretching along a rotated axis doesn't shear if the stretched axes line up.
+        let transform1 = ScaleRotateTranslate4 {
+            scale: glam::vec4(1.0, 2.0, 3.0, 4.0),
+            rotation: quarter_turn_xy(),
+            translation: glam::vec4(1.0, 0.0, 0.0, 0.0),
+        };
+        let transform2 = ScaleRotateTranslate4 {
+            scale: glam::vec4(5.0, 6.0, 1.0, 0.5),
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                zw: PI / 3.0,
+                ..Bivec4::ZERO
+            }),
+            translation: glam::vec4(0.0, 1.0, 0.0, 0.0),
+        };
+        let expected_scale = glam::vec4(6.0, 10.0, 3.0, 2.0);
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+        let expected = transform2.transform(transform1.transform(vector));
+        dbg!(expected);
+
+        let got = dbg!(transform1.compose_approx(transform2));
+
+        assert!(got.scale.abs_diff_eq(expected_scale, EPS));
+        assert!(got.transform(vector).abs_diff_eq(expected, EPS));
+    }
+
+    #[test]
+    fn compose_approx_with_shear_keeps_translation() {
+        let transform1 = ScaleRotateTranslate4 {
+            scale: glam::Vec4::ONE,
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xy: PI / 4.0,
+                ..Bivec4::ZERO
+            }),
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let transform2 = ScaleRotateTranslate4 {
+            scale: glam::vec4(2.0, 1.0, 1.0, 1.0),
+            ..ScaleRotateTranslate4::IDENTITY
+        };
+
+        let got = dbg!(transform1.compose_approx(transform2));
+
+        assert!(got
+            .transform(glam::Vec4::ZERO)
+            .abs_diff_eq(glam::vec4(2.0, 2.0, 3.0, 4.0), EPS));
+    }
+
+    #[test]
+    fn compose_with_rotate_scale_translate_is_exact() {
+        let transform1 = ScaleRotateTranslate4 {
+            scale: glam::vec4(1.0, 2.0, 3.0, 4.0),
+            rotation: quarter_turn_xy(),
+            translation: glam::vec4(1.0, 0.0, 0.0, 0.0),
+        };
+        let transform2 = RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(Bivec4 {
+                xz: PI / 3.0,
+                ..Bivec4::ZERO
+            }),
+            scale: 2.0,
+            translation: glam::vec4(0.0, 1.0, 0.0, 0.0),
+        };
+        let vector = glam::vec4(1.0, 2.0, 3.0, 4.0);
+
+        let got_after = dbg!(transform1.compose(transform2).transform(vector));
+        let got_before = dbg!(transform2.compose(transform1).transform(vector));
+
+        assert!(got_after.abs_diff_eq(transform2.transform(transform1.transform(vector)), EPS));
+        assert!(got_before.abs_diff_eq(transform1.transform(transform2.transform(vector)), EPS));
+    }
+
+    #[test]
+    fn from_rotate_scale_translate_is_same_transform() {
+        let rst = RotateScaleTranslate4 {
+            rotation: quarter_turn_xy(),
+            scale: 2.0,
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let vector = glam::vec4(5.0, 6.0, 7.0, 8.0);
+
+        let got = dbg!(ScaleRotateTranslate4::from(rst).transform(vector));
+
+        assert!(got.abs_diff_eq(rst.transform(vector), EPS));
+    }
+
+    #[test]
+    fn try_inverse_undoes_transform() {
+        let transform = ScaleRotateTranslate4 {
+            scale: glam::vec4(1.0, 2.0, -3.0, 0.5),
+            rotation: quarter_turn_xy(),
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let vector = glam::vec4(5.0, 6.0, 7.0, 8.0);
+
+        let transformed = dbg!(transform.transform(vector));
+        let untransformed = dbg!(transform.try_inverse().unwrap().transform(transformed));
+
+        assert!(untransformed.abs_diff_eq(vector, EPS));
+        assert!(ScaleRotateTranslate4 {
+            scale: glam::vec4(1.0, 0.0, 1.0, 1.0),
+            ..transform
+        }
+        .try_inverse()
+        .is_none());
+    }
+
+    #[test]
+    fn interpolate_with_interpolates() {
+        let transform1 = ScaleRotateTranslate4 {
+            scale: glam::vec4(1.0, 2.0, 3.0, 4.0),
+            rotation: Rotor4::IDENTITY,
+            translation: glam::vec4(1.0, 2.0, 3.0, 4.0),
+        };
+        let transform2 = ScaleRotateTranslate4 {
+            scale: glam::vec4(3.0, 2.0, 1.0, 0.0),
+            rotation: quarter_turn_xy(),
+            translation: glam::vec4(4.0, 3.0, 2.0, 1.0),
+        };
+        let expected_rotation = Rotor4::from_bivec_angles(Bivec4 {
+            xy: PI / 4.0,
+            ..Bivec4::ZERO
+        });
+
+        let got = dbg!(transform1.interpolate_with(&transform2, 0.5));
+
+        assert!(got.scale.abs_diff_eq(glam::vec4(2.0, 2.0, 2.0, 2.0), EPS));
+        assert!(rotor_approx_equal(got.rotation, expected_rotation));
+        assert!(got
+            .translation
+            .abs_diff_eq(glam::vec4(2.5, 2.5, 2.5, 2.5), EPS));
+    }
+
+    proptest! {
+        #[test]
+        fn approx_scale_rotate_translate_round_trips(
+            rotation in arbitrary_rotor4(),
+            scale in vec4_uniform_between(glam::vec4(-4.0, 0.25, 0.25, 0.25), glam::Vec4::splat(4.0)),
+            translation in vec4_uniform(10.0),
+            vector in vec4_uniform(2.0),
+        ) {
+            // Keep clear of -0.25..0.25 so it's nowhere near singular.
+            let scale = glam::vec4(if scale.x.abs() < 0.25 { 1.0 } else { scale.x }, scale.y, scale.z, scale.w);
+            let transform = ScaleRotateTranslate4 { scale, rotation, translation };
+
+            let got = dbg!(Affine4::from(transform).approx_scale_rotate_translate().unwrap());
+
+            assert!(got.transform(vector).abs_diff_eq(transform.transform(vector), EPS * 16.0));
+        }
+    }
+}
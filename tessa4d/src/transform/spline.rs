@@ -0,0 +1,243 @@
+use super::traits::InterpolateSpline;
+use crate::linear_algebra::Scalar;
+
+/// Smooth curve passing through a sequence of keyframes, evenly spaced in time.
+#[derive(Clone, Debug)]
+pub struct Spline<T: InterpolateSpline> {
+    keyframes: Vec<T>,
+    controls: Vec<T::Control>,
+}
+
+impl<T: InterpolateSpline> Spline<T> {
+    /// Builds a spline through `keyframes`, returns None if there are none.
+    /// The end keyframes use themselves as their missing neighbour.
+    pub fn new(keyframes: Vec<T>) -> Option<Self> {
+        let last = keyframes.len().checked_sub(1)?;
+        let controls = (0..=last)
+            .map(|i| {
+                T::spline_control(
+                    &keyframes[i.saturating_sub(1)],
+                    &keyframes[i],
+                    &keyframes[(i + 1).min(last)],
+                )
+            })
+            .collect();
+        Some(Self {
+            keyframes,
+            controls,
+        })
+    }
+
+    pub fn keyframes(&self) -> &[T] {
+        &self.keyframes
+    }
+
+    /// Samples the spline, where keyframe `i` is at `time = i`. Times outside the keyframes are clamped.
+    pub fn sample(&self, time: f32) -> T {
+        let last = self.keyframes.len() - 1;
+        let time = time.clamp(0.0, last as f32);
+        // Landing exactly on a keyframe always gives fraction 0, including the last one, so keyframes are hit exactly.
+        let index = time.floor() as usize;
+        let next = (index + 1).min(last);
+        self.keyframes[index].interpolate_spline(
+            &self.controls[index],
+            &self.keyframes[next],
+            &self.controls[next],
+            T::Scalar::from_f32(time - index as f32),
+        )
+    }
+}
+
+/// Only the keyframes are stored, the controls are rebuilt from them when deserializing.
+#[cfg(feature = "serde")]
+impl<T: InterpolateSpline + serde::Serialize> serde::Serialize for Spline<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.keyframes.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: InterpolateSpline + serde::Deserialize<'de>> serde::Deserialize<'de> for Spline<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyframes = Vec::<T>::deserialize(deserializer)?;
+        Self::new(keyframes)
+            .ok_or_else(|| serde::de::Error::custom("spline must have at least one keyframe"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use glam::Vec4;
+    use proptest::{prop_assert, proptest};
+    use std::f32::consts::PI;
+
+    use crate::transform::{
+        rotate_scale_translate4::RotateScaleTranslate4,
+        rotor4::{
+            test_util::{arbitrary_bivec4, arbitrary_rotor4},
+            Bivec4, Rotor4,
+        },
+        traits::{Compose, InterpolateWith, Transform},
+    };
+
+    const EPS: f32 = 1e-3;
+
+    fn rotation_error(a: Rotor4, b: Rotor4) -> f32 {
+        let v = glam::vec4(1.0, 2.0, 3.0, 4.0).normalize();
+        (a.transform(v) - b.transform(v)).length()
+    }
+
+    fn keyframe(angles: Bivec4, translation: Vec4, scale: f32) -> RotateScaleTranslate4<Vec4> {
+        RotateScaleTranslate4 {
+            rotation: Rotor4::from_bivec_angles(angles),
+            scale,
+            translation,
+        }
+    }
+
+    #[test]
+    fn empty_spline_is_none() {
+        assert!(Spline::<Rotor4>::new(vec![]).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip_rebuilds_controls() {
+        let spline = Spline::new(vec![
+            keyframe(Bivec4::ZERO, Vec4::ZERO, 1.0),
+            keyframe(
+                Bivec4 {
+                    xw: 1.0,
+                    ..Bivec4::ZERO
+                },
+                Vec4::X,
+                2.0,
+            ),
+            keyframe(
+                Bivec4 {
+                    yz: -0.5,
+                    ..Bivec4::ZERO
+                },
+                Vec4::Y,
+                0.5,
+            ),
+        ])
+        .unwrap();
+
+        let json = serde_json::to_string(&spline).unwrap();
+        let got: Spline<RotateScaleTranslate4<Vec4>> = serde_json::from_str(&json).unwrap();
+
+        let v = glam::vec4(1.0, -2.0, 0.5, 3.0);
+        for time in [0.0, 0.4, 1.0, 1.7] {
+            let expected = spline.sample(time).transform(v);
+            assert!(got.sample(time).transform(v).abs_diff_eq(expected, EPS));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_rejects_empty_spline() {
+        let got = serde_json::from_str::<Spline<Rotor4>>("[]");
+
+        assert!(got.is_err());
+    }
+
+    #[test]
+    fn single_keyframe_is_constant() {
+        let rotor = Rotor4::from_bivec_angles(Bivec4 {
+            xy: 1.0,
+            ..Bivec4::ZERO
+        });
+        let spline = Spline::new(vec![rotor]).unwrap();
+
+        assert!(rotation_error(spline.sample(0.0), rotor) < EPS);
+        assert!(rotation_error(spline.sample(2.5), rotor) < EPS);
+    }
+
+    #[test]
+    fn squad_with_rotations_in_one_plane_is_slerp() {
+        let rotors: Vec<_> = [0.0, 0.5, 1.0, 1.5]
+            .into_iter()
+            .map(|angle| {
+                Rotor4::from_bivec_angles(Bivec4 {
+                    xz: angle,
+                    ..Bivec4::ZERO
+                })
+            })
+            .collect();
+        let spline = Spline::new(rotors.clone()).unwrap();
+
+        for t in [0.0, 0.25, 0.5, 0.9] {
+            let got = spline.sample(1.0 + t);
+            let expected = rotors[1].interpolate_with(&rotors[2], t);
+            assert!(dbg!(rotation_error(got, expected)) < EPS);
+        }
+    }
+
+    #[test]
+    fn collinear_translations_stay_on_line() {
+        let spline = Spline::new(
+            (0..4)
+                .map(|i| keyframe(Bivec4::ZERO, Vec4::ONE * i as f32, 1.0))
+                .collect(),
+        )
+        .unwrap();
+
+        let got = spline.sample(1.5).translation;
+
+        assert!(dbg!(got).abs_diff_eq(Vec4::splat(1.5), EPS));
+    }
+
+    #[test]
+    fn sample_clamps_time() {
+        let first = keyframe(Bivec4::ZERO, Vec4::ZERO, 1.0);
+        let last = keyframe(
+            Bivec4 {
+                wy: PI / 3.0,
+                ..Bivec4::ZERO
+            },
+            Vec4::ONE,
+            2.0,
+        );
+        let spline = Spline::new(vec![first, last]).unwrap();
+
+        assert!(spline.sample(-1.0).translation.abs_diff_eq(Vec4::ZERO, EPS));
+        assert!(spline.sample(5.0).translation.abs_diff_eq(Vec4::ONE, EPS));
+        assert!(rotation_error(spline.sample(5.0).rotation, last.rotation) < EPS);
+    }
+
+    proptest! {
+        #[test]
+        fn passes_through_keyframes(a in arbitrary_rotor4(), b in arbitrary_rotor4(), c in arbitrary_rotor4(), d in arbitrary_rotor4()) {
+            let rotors = vec![a, b, c, d];
+            let spline = Spline::new(rotors.clone()).unwrap();
+
+            for (i, rotor) in rotors.into_iter().enumerate() {
+                prop_assert!(rotation_error(spline.sample(i as f32), rotor) < EPS);
+            }
+        }
+
+        #[test]
+        fn velocity_continuous_at_keyframes(a in arbitrary_rotor4(), to_b in arbitrary_bivec4(1.0), to_c in arbitrary_bivec4(1.0)) {
+            // Keep the steps under a half turn, where the rotor log is only approximate.
+            let b = a.compose(Rotor4::from_bivec_angles(to_b));
+            let c = b.compose(Rotor4::from_bivec_angles(to_c));
+            let spline = Spline::new(vec![
+                RotateScaleTranslate4 { rotation: a, scale: 1.0, translation: Vec4::ZERO },
+                RotateScaleTranslate4 { rotation: b, scale: 2.0, translation: glam::vec4(1.0, 3.0, 0.0, -1.0) },
+                RotateScaleTranslate4 { rotation: c, scale: 0.5, translation: glam::vec4(2.0, 0.0, 1.0, 1.0) },
+            ]).unwrap();
+            let v = glam::vec4(0.5, -1.0, 0.25, 1.0);
+            let h = 1e-2;
+            let sample = |time: f32| spline.sample(time).transform(v);
+
+            // Second order one-sided differences, extrapolated from inside each segment rather than through the keyframe.
+            // They amplify the rounding in the f32 rotor log by about 8 / h, so the tolerance leaves room for that.
+            let velocity_before = (sample(1.0 - h) * 2.5 - sample(1.0 - 2.0 * h) * 4.0 + sample(1.0 - 3.0 * h) * 1.5) / h;
+            let velocity_after = (sample(1.0 + h) * -2.5 + sample(1.0 + 2.0 * h) * 4.0 - sample(1.0 + 3.0 * h) * 1.5) / h;
+
+            prop_assert!(velocity_before.abs_diff_eq(velocity_after, 0.1), "{velocity_before} != {velocity_after}");
+        }
+    }
+}
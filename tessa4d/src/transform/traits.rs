@@ -1,8 +1,24 @@
 //! Traits for 4D transforms.
 
+use crate::linear_algebra::Scalar;
+
 pub trait Transform<T> {
     /// Applies this transformation to a vector representing a point.
     fn transform(&self, operand: T) -> T;
+
+    /// Applies this transformation to each operand in place, e.g. all the vertices of a mesh.
+    /// Transforms with per-call setup, like building a rotor's matrix, override this to only do it once.
+    /// Not available on `dyn Transform`, which keeps the trait object-safe.
+    fn transform_each<'a, I>(&self, operands: I)
+    where
+        Self: Sized,
+        I: IntoIterator<Item = &'a mut T>,
+        T: Copy + 'a,
+    {
+        for operand in operands {
+            *operand = self.transform(*operand);
+        }
+    }
 }
 
 pub trait Compose<Other> {
@@ -34,6 +50,26 @@ impl<T: Inverse> TryInverse for T {
 
 /// For transforms that can be interpolated.
 pub trait InterpolateWith {
+    /// Precision of the fraction, the same as the transform's.
+    type Scalar: Scalar;
     /// Interpolate between two transforms. Implementations must support fraction between 0 and 1 inclusive.
-    fn interpolate_with(&self, other: &Self, fraction: f32) -> Self;
+    fn interpolate_with(&self, other: &Self, fraction: Self::Scalar) -> Self;
+}
+
+/// For transforms that can be smoothly interpolated through several keyframes, see [Spline](super::spline::Spline).
+pub trait InterpolateSpline: Sized {
+    /// Precision of the fraction along the curve, the same as the keyframes'.
+    type Scalar: Scalar;
+    /// Data computed once per keyframe from its neighbours, e.g. tangents or control points.
+    type Control;
+    /// Computes the control for `current`, the neighbours are needed for the curve to be smooth through it.
+    fn spline_control(previous: &Self, current: &Self, next: &Self) -> Self::Control;
+    /// Interpolate along the curve from self at 0 to other at 1. Implementations must support fraction between 0 and 1 inclusive.
+    fn interpolate_spline(
+        &self,
+        control: &Self::Control,
+        other: &Self,
+        other_control: &Self::Control,
+        fraction: Self::Scalar,
+    ) -> Self;
 }
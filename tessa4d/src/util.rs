@@ -1,12 +1,97 @@
 use std::ops::{Add, Mul};
 
-pub fn approx_equal(a: f32, b: f32, eps: f32) -> bool {
+use crate::linear_algebra::Scalar;
+
+pub fn approx_equal<S: Scalar>(a: S, b: S, eps: S) -> bool {
     (a - b).abs() < eps
 }
 
 /// Linear interpolation from a to b, evaluates to a at t=0 and b at t=1 with a straight line in between.
-pub fn lerp<T: Add<T, Output = T> + Mul<f32, Output = T>>(a: T, b: T, t: f32) -> T {
-    a * (1.0 - t) + b * t
+pub fn lerp<S: Scalar, T: Add<T, Output = T> + Mul<S, Output = T>>(a: T, b: T, t: S) -> T {
+    a * (S::ONE - t) + b * t
+}
+
+/// Cubic Hermite curve from a at t=0 to b at t=1, with derivatives `a_tangent` and `b_tangent` at the ends.
+pub fn cubic_hermite<S: Scalar, T: Add<T, Output = T> + Mul<S, Output = T>>(
+    a: T,
+    a_tangent: T,
+    b: T,
+    b_tangent: T,
+    t: S,
+) -> T {
+    let [two, three] = [2.0, 3.0].map(S::from_f32);
+    let t2 = t * t;
+    let t3 = t2 * t;
+    a * (two * t3 - three * t2 + S::ONE)
+        + a_tangent * (t3 - two * t2 + t)
+        + b * (-two * t3 + three * t2)
+        + b_tangent * (t3 - t2)
+}
+
+/// Determinant of a square matrix, by Gaussian elimination with partial pivoting.
+pub(crate) fn determinant<S: Scalar, const N: usize>(mut matrix: [[S; N]; N]) -> S {
+    let mut det = S::ONE;
+    for col in 0..N {
+        let pivot = (col..N)
+            .max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))
+            .unwrap_or(col);
+        if matrix[pivot][col] == S::ZERO {
+            return S::ZERO;
+        }
+        if pivot != col {
+            matrix.swap(pivot, col);
+            det = -det;
+        }
+        det *= matrix[col][col];
+        let pivot_row = matrix[col];
+        for row in matrix.iter_mut().skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (element, pivot_element) in row.iter_mut().zip(pivot_row).skip(col) {
+                *element -= factor * pivot_element;
+            }
+        }
+    }
+    det
+}
+
+/// Inverse of a square matrix by Gauss-Jordan elimination, None if it is singular.
+/// Doesn't care about row- or column-major order, the inverse of the transpose is the transpose of the inverse.
+pub(crate) fn inverse<const N: usize>(mut matrix: [[f32; N]; N]) -> Option<[[f32; N]; N]> {
+    let mut inverse: [[f32; N]; N] =
+        std::array::from_fn(|i| std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 }));
+    for col in 0..N {
+        let pivot = (col..N)
+            .max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))
+            .unwrap_or(col);
+        if matrix[pivot][col] == 0.0 {
+            return None;
+        }
+        matrix.swap(pivot, col);
+        inverse.swap(pivot, col);
+        let scale = matrix[col][col].recip();
+        matrix[col] = matrix[col].map(|x| x * scale);
+        inverse[col] = inverse[col].map(|x| x * scale);
+        let (pivot_row, pivot_inverse_row) = (matrix[col], inverse[col]);
+        for (row, (matrix_row, inverse_row)) in
+            matrix.iter_mut().zip(inverse.iter_mut()).enumerate()
+        {
+            if row == col {
+                continue;
+            }
+            let factor = matrix_row[col];
+            for (element, pivot_element) in matrix_row.iter_mut().zip(pivot_row) {
+                *element -= factor * pivot_element;
+            }
+            for (element, pivot_element) in inverse_row.iter_mut().zip(pivot_inverse_row) {
+                *element -= factor * pivot_element;
+            }
+        }
+    }
+    inverse
+        .iter()
+        .flatten()
+        .all(|x| x.is_finite())
+        .then_some(inverse)
 }
 
 #[cfg(test)]